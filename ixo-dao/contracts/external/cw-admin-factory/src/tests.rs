@@ -103,9 +103,7 @@ pub fn test_set_admin() {
         initial_items: None,
     };
 
-    // multi-test does not support UpdateAdmin yet :(
-    // https://github.com/CosmWasm/cw-plus/blob/14f4e922fac9e2097a8efa99e5b71d04747e340a/packages/multi-test/src/wasm.rs#L477
-    let err = app
+    let res = app
         .execute_contract(
             Addr::unchecked("CREATOR"),
             factory_addr,
@@ -116,12 +114,18 @@ pub fn test_set_admin() {
             },
             &[],
         )
-        .unwrap_err();
+        .unwrap();
 
-    assert!(matches!(
-        err.downcast().unwrap(),
-        cw_multi_test::error::Error::UnsupportedWasmMsg(_)
-    ))
+    // The new contract is its own admin.
+    let core_addr = res
+        .events
+        .iter()
+        .flat_map(|event| &event.attributes)
+        .find(|attr| attr.key == "set contract admin as itself")
+        .map(|attr| Addr::unchecked(&attr.value))
+        .unwrap();
+    let info = app.contract_data(&core_addr).unwrap();
+    assert_eq!(info.admin, Some(core_addr));
 }
 
 #[test]
@@ -34,7 +34,7 @@ pub struct Suite {
 
 impl Suite {
     pub fn init() -> Result<Suite> {
-        let mut app = mock_app();
+        let app = mock_app();
         let owner = "owner".to_owned();
         let cw1_id = app.store_code(contract_cw1());
 
@@ -1,12 +1,16 @@
 use cosmwasm_schema::write_api;
 
 use cw1155::{Cw1155ExecuteMsg, Cw1155QueryMsg};
-use cw1155_base::msg::InstantiateMsg;
+use cw1155_base::{
+    msg::{InstantiateMsg, MigrateMsg},
+    Extension,
+};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
-        execute: Cw1155ExecuteMsg,
+        execute: Cw1155ExecuteMsg<Extension>,
         query: Cw1155QueryMsg,
+        migrate: MigrateMsg,
     }
 }
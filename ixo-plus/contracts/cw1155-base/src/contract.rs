@@ -1,9 +1,20 @@
-use crate::{error::ContractError, execute, msg::InstantiateMsg, query, state::MINTER};
+use crate::{
+    error::ContractError,
+    execute,
+    msg::{InstantiateMsg, MigrateMsg},
+    query,
+    state::{
+        role_key, BalancesMigration, ADMIN, BALANCES_MIGRATION, CONTRACT_INFO, LEGACY_MINTER, ROLES,
+    },
+    Extension,
+};
 use cosmwasm_std::{
-    entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    entry_point, to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
 };
-use cw1155::{Cw1155ExecuteMsg, Cw1155QueryMsg};
+use cw1155::{ContractInfoResponse, Cw1155ExecuteMsg, Cw1155QueryMsg, Role};
 use cw2::set_contract_version;
+use cw_utils::ensure_from_older_version;
+use serde::{de::DeserializeOwned, Serialize};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "crates.io:cw1155-base";
@@ -17,8 +28,22 @@ pub fn instantiate(
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    CONTRACT_INFO.save(
+        deps.storage,
+        &ContractInfoResponse {
+            name: msg.name,
+            symbol: msg.symbol,
+        },
+    )?;
     let minter = deps.api.addr_validate(&msg.minter)?;
-    MINTER.save(deps.storage, &minter)?;
+    let admin = match msg.admin {
+        Some(admin) => deps.api.addr_validate(&admin)?,
+        None => minter.clone(),
+    };
+    ADMIN.save(deps.storage, &admin)?;
+    for role in [Role::Minter, Role::MetadataEditor] {
+        ROLES.save(deps.storage, (role_key(&role), &minter, ""), &Empty {})?;
+    }
     Ok(Response::default())
 }
 
@@ -34,8 +59,29 @@ pub fn execute(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    msg: Cw1155ExecuteMsg,
+    msg: Cw1155ExecuteMsg<Extension>,
 ) -> Result<Response, ContractError> {
+    execute_with_extension(deps, env, info, msg)
+}
+
+/// Handles execute messages of a contract storing the on-chain metadata of tokens as `T`.
+/// Contracts with their own metadata type can call it from their entry point.
+pub fn execute_with_extension<T>(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw1155ExecuteMsg<T>,
+) -> Result<Response, ContractError>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    // until the balances are migrated, the holder index and supplies can't be updated
+    if !matches!(msg, Cw1155ExecuteMsg::MigrateBalances { .. })
+        && BALANCES_MIGRATION.may_load(deps.storage)?.is_some()
+    {
+        return Err(ContractError::MigrationPending {});
+    }
+
     let env = ExecuteEnv { deps, env, info };
     match msg {
         Cw1155ExecuteMsg::SendFrom {
@@ -56,9 +102,24 @@ pub fn execute(
             token_id,
             value,
             uri,
+            max_supply,
+            supply_policy,
+            extension,
+            msg,
+        } => execute::mint(
+            env,
+            to,
+            token_id,
+            value,
+            uri,
+            max_supply,
+            supply_policy,
+            extension,
             msg,
-        } => execute::mint(env, to, token_id, value, uri, msg),
-        Cw1155ExecuteMsg::BatchMint { to, batch, msg } => execute::batch_mint(env, to, batch, msg),
+        ),
+        Cw1155ExecuteMsg::BatchMint { to, batch, msg } => {
+            execute::batch_mint::<T>(env, to, batch, msg)
+        }
         Cw1155ExecuteMsg::Burn {
             from,
             token_id,
@@ -69,11 +130,62 @@ pub fn execute(
             execute::approve_all(env, operator, expires)
         }
         Cw1155ExecuteMsg::RevokeAll { operator } => execute::revoke_all(env, operator),
+        Cw1155ExecuteMsg::Approve {
+            spender,
+            token_id,
+            amount,
+            expires,
+        } => execute::approve(env, spender, token_id, amount, expires),
+        Cw1155ExecuteMsg::Retire {
+            token_id,
+            amount,
+            beneficiary,
+            reason,
+        } => execute::retire(env, token_id, amount, beneficiary, reason),
+        Cw1155ExecuteMsg::UpdateMetadata {
+            token_id,
+            url,
+            extension,
+            freeze,
+        } => execute::update_metadata(env, token_id, url, extension, freeze),
+        Cw1155ExecuteMsg::GrantRole {
+            role,
+            address,
+            token_id_prefix,
+        } => execute::grant_role(env, role, address, token_id_prefix),
+        Cw1155ExecuteMsg::RevokeRole {
+            role,
+            address,
+            token_id_prefix,
+        } => execute::revoke_role(env, role, address, token_id_prefix),
+        Cw1155ExecuteMsg::TransferAdmin { new_admin } => execute::transfer_admin(env, new_admin),
+        Cw1155ExecuteMsg::AcceptAdmin {} => execute::accept_admin(env),
+        Cw1155ExecuteMsg::SetTransferRestrictions {
+            token_id,
+            restrictions,
+        } => execute::set_transfer_restrictions(env, token_id, restrictions),
+        Cw1155ExecuteMsg::UpdateHolderList {
+            token_id,
+            list,
+            add,
+            remove,
+        } => execute::update_holder_list(env, token_id, list, add, remove),
+        Cw1155ExecuteMsg::Pause {} => execute::set_paused(env, true),
+        Cw1155ExecuteMsg::Unpause {} => execute::set_paused(env, false),
+        Cw1155ExecuteMsg::MigrateBalances { limit } => execute::migrate_balances(env, limit),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, env: Env, msg: Cw1155QueryMsg) -> StdResult<Binary> {
+    query_with_extension::<Extension>(deps, env, msg)
+}
+
+/// Handles queries of a contract storing the on-chain metadata of tokens as `T`.
+pub fn query_with_extension<T>(deps: Deps, env: Env, msg: Cw1155QueryMsg) -> StdResult<Binary>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
     match msg {
         Cw1155QueryMsg::Balance { owner, token_id } => {
             to_binary(&query::balance(deps, owner, token_id)?)
@@ -97,7 +209,28 @@ pub fn query(deps: Deps, env: Env, msg: Cw1155QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         )?),
-        Cw1155QueryMsg::TokenInfo { token_id } => to_binary(&query::token_info(deps, token_id)?),
+        Cw1155QueryMsg::Allowance {
+            owner,
+            spender,
+            token_id,
+        } => to_binary(&query::allowance(deps, owner, spender, token_id)?),
+        Cw1155QueryMsg::AllAllowances {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => to_binary(&query::all_allowances(
+            deps,
+            env,
+            owner,
+            include_expired.unwrap_or(false),
+            start_after,
+            limit,
+        )?),
+        Cw1155QueryMsg::ContractInfo {} => to_binary(&query::contract_info(deps)?),
+        Cw1155QueryMsg::TokenInfo { token_id } => {
+            to_binary(&query::token_info::<T>(deps, token_id)?)
+        }
         Cw1155QueryMsg::Tokens {
             owner,
             start_after,
@@ -106,5 +239,98 @@ pub fn query(deps: Deps, env: Env, msg: Cw1155QueryMsg) -> StdResult<Binary> {
         Cw1155QueryMsg::AllTokens { start_after, limit } => {
             to_binary(&query::all_tokens(deps, start_after, limit)?)
         }
+        Cw1155QueryMsg::Owners {
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query::owners(deps, token_id, start_after, limit)?),
+        Cw1155QueryMsg::Admin {} => to_binary(&query::admin(deps)?),
+        Cw1155QueryMsg::HasRole {
+            role,
+            address,
+            token_id,
+        } => to_binary(&query::has_role(deps, role, address, token_id)?),
+        Cw1155QueryMsg::RoleGrants {
+            role,
+            start_after,
+            limit,
+        } => to_binary(&query::role_grants(deps, role, start_after, limit)?),
+        Cw1155QueryMsg::Paused {} => to_binary(&query::paused(deps)?),
+        Cw1155QueryMsg::TransferRestrictions { token_id } => {
+            to_binary(&query::transfer_restrictions(deps, token_id)?)
+        }
+        Cw1155QueryMsg::HolderList {
+            token_id,
+            list,
+            start_after,
+            limit,
+        } => to_binary(&query::holder_list(
+            deps,
+            token_id,
+            list,
+            start_after,
+            limit,
+        )?),
+        Cw1155QueryMsg::Retirement { id } => to_binary(&query::retirement(deps, id)?),
+        Cw1155QueryMsg::Retirements {
+            owner,
+            token_id,
+            start_after,
+            limit,
+        } => to_binary(&query::retirements(
+            deps,
+            owner,
+            token_id,
+            start_after,
+            limit,
+        )?),
+        Cw1155QueryMsg::RetiredSupply { token_id } => {
+            to_binary(&query::retired_supply(deps, token_id)?)
+        }
+        Cw1155QueryMsg::RoyaltyInfo {
+            token_id,
+            sale_price,
+        } => to_binary(&query::royalty_info::<T>(deps, token_id, sale_price)?),
+        Cw1155QueryMsg::CheckRoyalties {} => to_binary(&query::check_royalties(deps)?),
+        Cw1155QueryMsg::Supply { token_id } => to_binary(&query::supply(deps, token_id)?),
+        Cw1155QueryMsg::AllSupplies { start_after, limit } => {
+            to_binary(&query::all_supplies(deps, start_after, limit)?)
+        }
     }
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let original_version =
+        ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if original_version < "0.17.0".parse::<semver::Version>().unwrap() {
+        // The single minter of collections instantiated before roles becomes their admin, and
+        // keeps minting and editing metadata of all tokens
+        if let Some(minter) = LEGACY_MINTER.may_load(deps.storage)? {
+            ADMIN.save(deps.storage, &minter)?;
+            for role in [Role::Minter, Role::MetadataEditor] {
+                ROLES.save(deps.storage, (role_key(&role), &minter, ""), &Empty {})?;
+            }
+            LEGACY_MINTER.remove(deps.storage);
+        }
+
+        // Collections instantiated before the holder index and supply tracking rebuild them
+        // from their balances, the first chunk here and the rest with `MigrateBalances`
+        BALANCES_MIGRATION.save(deps.storage, &BalancesMigration { last: None })?;
+    }
+    let done = execute::migrate_balances_chunk(deps.storage, execute::MIGRATION_DEFAULT_LIMIT)?;
+
+    if msg.name.is_some() || msg.symbol.is_some() {
+        let mut contract_info = CONTRACT_INFO.may_load(deps.storage)?.unwrap_or_default();
+        if let Some(name) = msg.name {
+            contract_info.name = name;
+        }
+        if let Some(symbol) = msg.symbol {
+            contract_info.symbol = symbol;
+        }
+        CONTRACT_INFO.save(deps.storage, &contract_info)?;
+    }
+
+    Ok(Response::new().add_attribute("balances_migrated", done.to_string()))
+}
@@ -9,9 +9,42 @@ pub enum ContractError {
     #[error("token_id already claimed")]
     Claimed {},
 
+    #[error("Supply settings can only be set by the first mint of a token")]
+    SupplySettingsLocked {},
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("Expired")]
     Expired {},
+
+    #[error("Invalid zero amount")]
+    InvalidZeroAmount {},
+
+    #[error("Metadata of {token_id} is frozen")]
+    MetadataFrozen { token_id: String },
+
+    #[error("Cannot set to own account")]
+    CannotSetOwnAccount {},
+
+    #[error("Transfers are paused")]
+    Paused {},
+
+    #[error("{token_id} is soulbound")]
+    Soulbound { token_id: String },
+
+    #[error("{holder} is not allowed to hold {token_id}")]
+    RestrictedHolder { token_id: String, holder: String },
+
+    #[error("Royalty percentage must be between 0 and 100")]
+    InvalidRoyaltyPercentage {},
+
+    #[error("Royalty fields of the token extension are malformed")]
+    InvalidRoyalty {},
+
+    #[error("Minting would exceed the max supply of {token_id}")]
+    MaxSupplyExceeded { token_id: String },
+
+    #[error("Balances are still being migrated, finish with MigrateBalances")]
+    MigrationPending {},
 }
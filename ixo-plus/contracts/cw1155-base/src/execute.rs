@@ -1,19 +1,34 @@
-use cosmwasm_std::{Addr, Binary, DepsMut, Response, StdResult, SubMsg, Uint128};
-use cw1155::{ApproveAllEvent, Cw1155BatchReceiveMsg, Cw1155ReceiveMsg, TokenId, TransferEvent};
+use cosmwasm_std::{
+    Addr, Binary, DepsMut, Empty, Order, Response, StdError, StdResult, Storage, SubMsg, Uint128,
+};
+use cw1155::{
+    AllowanceResponse, ApproveAllEvent, ApproveEvent, Cw1155BatchReceiveMsg, Cw1155ReceiveMsg,
+    HolderList, MetadataEvent, RetireEvent, Role, SupplyPolicy, TokenId, TransferEvent,
+    TransferRestrictions,
+};
+use cw_storage_plus::Bound;
 use cw_utils::{Event, Expiration};
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
     contract::ExecuteEnv,
-    helpers::guard_can_approve,
-    state::{APPROVES, BALANCES, MINTER, TOKENS},
+    helpers::{
+        guard_can_spend, guard_has_role, guard_is_admin, guard_transfer_allowed, validate_royalties,
+    },
+    state::{
+        retirements, role_key, token_extensions, RetirementInfo, ADMIN, ALLOWANCES, ALLOW_LIST,
+        APPROVES, BALANCES, BALANCES_MIGRATION, DENY_LIST, FROZEN_METADATA, MAX_SUPPLY, PAUSED,
+        PENDING_ADMIN, RETIRED_SUPPLY, RETIREMENT_COUNT, ROLES, SUPPLY_POLICY, TOKENS,
+        TOKEN_OWNERS, TOTAL_SUPPLY, TRANSFER_RESTRICTIONS,
+    },
     ContractError,
 };
 
-/// When from is None: mint new coins
-/// When to is None: burn coins
+/// When from is None: mint new coins, increasing the total supply
+/// When to is None: burn coins, decreasing the total supply
 /// When both are None: no token balance is changed, pointless but valid
 ///
-/// Make sure permissions are checked before calling this.
+/// Make sure permissions are checked before calling this, transfer restrictions are checked here.
 fn transfer_inner<'a>(
     deps: &'a mut DepsMut,
     from: Option<&'a Addr>,
@@ -21,24 +36,60 @@ fn transfer_inner<'a>(
     token_id: &'a str,
     amount: Uint128,
 ) -> Result<TransferEvent<'a>, ContractError> {
+    guard_transfer_allowed(deps.storage, from, to, token_id)?;
+
+    match (from, to) {
+        (None, Some(_)) => {
+            let supply = TOTAL_SUPPLY
+                .may_load(deps.storage, token_id)?
+                .unwrap_or_default()
+                .checked_add(amount)
+                .map_err(StdError::from)?;
+            if let Some(max_supply) = MAX_SUPPLY.may_load(deps.storage, token_id)? {
+                if supply > max_supply {
+                    return Err(ContractError::MaxSupplyExceeded {
+                        token_id: token_id.to_string(),
+                    });
+                }
+            }
+            TOTAL_SUPPLY.save(deps.storage, token_id, &supply)?;
+        }
+        (Some(_), None) => {
+            TOTAL_SUPPLY.update(
+                deps.storage,
+                token_id,
+                |supply: Option<Uint128>| -> StdResult<_> {
+                    Ok(supply.unwrap_or_default().checked_sub(amount)?)
+                },
+            )?;
+        }
+        _ => {}
+    }
+
     if let Some(from_addr) = from {
-        BALANCES.update(
+        let balance = BALANCES.update(
             deps.storage,
             (from_addr, token_id),
             |balance: Option<Uint128>| -> StdResult<_> {
                 Ok(balance.unwrap_or_default().checked_sub(amount)?)
             },
         )?;
+        if balance.is_zero() {
+            TOKEN_OWNERS.remove(deps.storage, (token_id, from_addr));
+        }
     }
 
     if let Some(to_addr) = to {
-        BALANCES.update(
+        let balance = BALANCES.update(
             deps.storage,
             (to_addr, token_id),
             |balance: Option<Uint128>| -> StdResult<_> {
                 Ok(balance.unwrap_or_default().checked_add(amount)?)
             },
         )?;
+        if !balance.is_zero() {
+            TOKEN_OWNERS.save(deps.storage, (token_id, to_addr), &Empty {})?;
+        }
     }
 
     Ok(TransferEvent {
@@ -49,6 +100,50 @@ fn transfer_inner<'a>(
     })
 }
 
+/// Checks that `token_id` can be minted according to its supply policy.
+/// On the first mint of a token, it stores its uri, extension and supply settings.
+fn prepare_mint<T>(
+    deps: &mut DepsMut,
+    token_id: &str,
+    uri: &str,
+    extension: T,
+    amount: Uint128,
+    max_supply: Option<Uint128>,
+    supply_policy: Option<SupplyPolicy>,
+) -> Result<(), ContractError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if TOKENS.has(deps.storage, token_id) {
+        if max_supply.is_some() || supply_policy.is_some() {
+            return Err(ContractError::SupplySettingsLocked {});
+        }
+        return match SUPPLY_POLICY
+            .may_load(deps.storage, token_id)?
+            .unwrap_or_default()
+        {
+            SupplyPolicy::Fixed => Err(ContractError::Claimed {}),
+            SupplyPolicy::Open => Ok(()),
+        };
+    }
+
+    if matches!(max_supply, Some(max_supply) if amount > max_supply) {
+        return Err(ContractError::MaxSupplyExceeded {
+            token_id: token_id.to_string(),
+        });
+    }
+    validate_royalties(deps.api, &extension)?;
+
+    // we must save some valid data here, Addition to save uri as token_info
+    TOKENS.save(deps.storage, token_id, &uri.to_string())?;
+    token_extensions().save(deps.storage, token_id, &extension)?;
+    SUPPLY_POLICY.save(deps.storage, token_id, &supply_policy.unwrap_or_default())?;
+    if let Some(max_supply) = max_supply {
+        MAX_SUPPLY.save(deps.storage, token_id, &max_supply)?;
+    }
+    Ok(())
+}
+
 pub fn send_from(
     env: ExecuteEnv,
     from: String,
@@ -66,7 +161,7 @@ pub fn send_from(
         info,
     } = env;
 
-    guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
+    guard_can_spend(&mut deps, &env, &from_addr, &info.sender, &token_id, amount)?;
 
     let mut rsp = Response::default();
 
@@ -95,27 +190,37 @@ pub fn send_from(
     Ok(rsp)
 }
 
-pub fn mint(
+#[allow(clippy::too_many_arguments)]
+pub fn mint<T>(
     env: ExecuteEnv,
     to: String,
     token_id: TokenId,
     amount: Uint128,
-        //Addition to save uri as token info
-        uri: String,
+    //Addition to save uri as token info
+    uri: String,
+    max_supply: Option<Uint128>,
+    supply_policy: Option<SupplyPolicy>,
+    extension: T,
     msg: Option<Binary>,
-) -> Result<Response, ContractError> {
+) -> Result<Response, ContractError>
+where
+    T: Serialize + DeserializeOwned,
+{
     let ExecuteEnv { mut deps, info, .. } = env;
 
     let to_addr = deps.api.addr_validate(&to)?;
 
-    if info.sender != MINTER.load(deps.storage)? {
-        return Err(ContractError::Unauthorized {});
-    }
+    guard_has_role(deps.storage, &Role::Minter, &info.sender, Some(&token_id))?;
 
-    //If token id exists already do not mint any more.
-    if TOKENS.has(deps.storage, &token_id) {
-        return Err(ContractError::Claimed {});
-    }
+    prepare_mint(
+        &mut deps,
+        &token_id,
+        &uri,
+        extension,
+        amount,
+        max_supply,
+        supply_policy,
+    )?;
 
     let mut rsp = Response::default();
 
@@ -135,12 +240,6 @@ pub fn mint(
         )]
     }
 
-    // insert if not exist
-    if !TOKENS.has(deps.storage, &token_id) {
-        // we must save some valid data here, Addition to save uri as token_info
-        TOKENS.save(deps.storage, &token_id, &uri)?;
-    }
-
     Ok(rsp)
 }
 
@@ -159,7 +258,7 @@ pub fn burn(
     let from_addr = deps.api.addr_validate(&from)?;
 
     // whoever can transfer these tokens can burn
-    guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
+    guard_can_spend(&mut deps, &env, &from_addr, &info.sender, &token_id, amount)?;
 
     let mut rsp = Response::default();
     let event = transfer_inner(&mut deps, Some(&from_addr), None, &token_id, amount)?;
@@ -167,6 +266,58 @@ pub fn burn(
     Ok(rsp)
 }
 
+pub fn retire(
+    env: ExecuteEnv,
+    token_id: TokenId,
+    amount: Uint128,
+    beneficiary: Option<String>,
+    reason: Option<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv {
+        mut deps,
+        info,
+        env,
+    } = env;
+
+    if amount.is_zero() {
+        return Err(ContractError::InvalidZeroAmount {});
+    }
+
+    let mut rsp = Response::default();
+    let event = transfer_inner(&mut deps, Some(&info.sender), None, &token_id, amount)?;
+    event.add_attributes(&mut rsp);
+
+    RETIRED_SUPPLY.update(
+        deps.storage,
+        &token_id,
+        |retired: Option<Uint128>| -> StdResult<_> {
+            Ok(retired.unwrap_or_default().checked_add(amount)?)
+        },
+    )?;
+
+    let id = RETIREMENT_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+    RETIREMENT_COUNT.save(deps.storage, &id)?;
+    let retirement = RetirementInfo {
+        beneficiary: beneficiary.unwrap_or_else(|| info.sender.to_string()),
+        owner: info.sender,
+        token_id,
+        amount,
+        reason,
+        timestamp: env.block.time,
+    };
+    retirements().save(deps.storage, id, &retirement)?;
+
+    RetireEvent {
+        id,
+        owner: retirement.owner.as_ref(),
+        token_id: &retirement.token_id,
+        amount,
+        beneficiary: &retirement.beneficiary,
+    }
+    .add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
 pub fn batch_send_from(
     env: ExecuteEnv,
     from: String,
@@ -183,10 +334,9 @@ pub fn batch_send_from(
     let from_addr = deps.api.addr_validate(&from)?;
     let to_addr = deps.api.addr_validate(&to)?;
 
-    guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
-
     let mut rsp = Response::default();
     for (token_id, amount, _uri) in batch.iter() {
+        guard_can_spend(&mut deps, &env, &from_addr, &info.sender, token_id, *amount)?;
         let event = transfer_inner(
             &mut deps,
             Some(&from_addr),
@@ -212,15 +362,18 @@ pub fn batch_send_from(
     Ok(rsp)
 }
 
-pub fn batch_mint(
+pub fn batch_mint<T>(
     env: ExecuteEnv,
     to: String,
     batch: Vec<(TokenId, Uint128, String)>,
     msg: Option<Binary>,
-) -> Result<Response, ContractError> {
+) -> Result<Response, ContractError>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
     let ExecuteEnv { mut deps, info, .. } = env;
-    if info.sender != MINTER.load(deps.storage)? {
-        return Err(ContractError::Unauthorized {});
+    for (token_id, _, _) in batch.iter() {
+        guard_has_role(deps.storage, &Role::Minter, &info.sender, Some(token_id))?;
     }
 
     let to_addr = deps.api.addr_validate(&to)?;
@@ -228,19 +381,10 @@ pub fn batch_mint(
     let mut rsp = Response::default();
 
     for (token_id, amount, uri) in batch.iter() {
-        //If token id exists already do not mint any more.
-        if TOKENS.has(deps.storage, &token_id) {
-            return Err(ContractError::Claimed {});
-        }
+        prepare_mint(&mut deps, token_id, uri, T::default(), *amount, None, None)?;
 
         let event = transfer_inner(&mut deps, None, Some(&to_addr), token_id, *amount)?;
         event.add_attributes(&mut rsp);
-
-        // insert if not exist
-        if !TOKENS.has(deps.storage, token_id) {
-            // we must save some valid data here
-            TOKENS.save(deps.storage, token_id, &uri)?;
-        }
     }
 
     if let Some(msg) = msg {
@@ -271,10 +415,9 @@ pub fn batch_burn(
 
     let from_addr = deps.api.addr_validate(&from)?;
 
-    guard_can_approve(deps.as_ref(), &env, &from_addr, &info.sender)?;
-
     let mut rsp = Response::default();
     for (token_id, amount, _uri) in batch.into_iter() {
+        guard_can_spend(&mut deps, &env, &from_addr, &info.sender, &token_id, amount)?;
         let event = transfer_inner(&mut deps, Some(&from_addr), None, &token_id, amount)?;
         event.add_attributes(&mut rsp);
     }
@@ -322,3 +465,280 @@ pub fn revoke_all(env: ExecuteEnv, operator: String) -> Result<Response, Contrac
     .add_attributes(&mut rsp);
     Ok(rsp)
 }
+
+pub fn approve(
+    env: ExecuteEnv,
+    spender: String,
+    token_id: TokenId,
+    amount: Uint128,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, env } = env;
+
+    // reject expired data as invalid
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == info.sender {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    let key = (&info.sender, &spender_addr, token_id.as_str());
+    if amount.is_zero() {
+        ALLOWANCES.remove(deps.storage, key);
+    } else {
+        ALLOWANCES.save(
+            deps.storage,
+            key,
+            &AllowanceResponse {
+                allowance: amount,
+                expires,
+            },
+        )?;
+    }
+
+    let mut rsp = Response::default();
+    ApproveEvent {
+        owner: info.sender.as_ref(),
+        spender: &spender,
+        token_id: &token_id,
+        amount,
+    }
+    .add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn update_metadata<T>(
+    env: ExecuteEnv,
+    token_id: TokenId,
+    url: Option<String>,
+    extension: Option<T>,
+    freeze: Option<bool>,
+) -> Result<Response, ContractError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_has_role(
+        deps.storage,
+        &Role::MetadataEditor,
+        &info.sender,
+        Some(&token_id),
+    )?;
+
+    let mut current_url = TOKENS.load(deps.storage, &token_id)?;
+    if FROZEN_METADATA
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default()
+    {
+        return Err(ContractError::MetadataFrozen { token_id });
+    }
+
+    if let Some(url) = url {
+        TOKENS.save(deps.storage, &token_id, &url)?;
+        current_url = url;
+    }
+    if let Some(extension) = extension {
+        validate_royalties(deps.api, &extension)?;
+        token_extensions().save(deps.storage, &token_id, &extension)?;
+    }
+    if freeze.unwrap_or_default() {
+        FROZEN_METADATA.save(deps.storage, &token_id, &true)?;
+    }
+
+    let mut rsp = Response::default();
+    MetadataEvent {
+        url: &current_url,
+        token_id: &token_id,
+    }
+    .add_attributes(&mut rsp);
+    Ok(rsp)
+}
+
+pub fn grant_role(
+    env: ExecuteEnv,
+    role: Role,
+    address: String,
+    token_id_prefix: Option<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_is_admin(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let prefix = token_id_prefix.unwrap_or_default();
+    ROLES.save(
+        deps.storage,
+        (role_key(&role), &address, &prefix),
+        &Empty {},
+    )?;
+
+    Ok(Response::new()
+        .add_attribute("action", "grant_role")
+        .add_attribute("role", role_key(&role))
+        .add_attribute("address", address)
+        .add_attribute("token_id_prefix", prefix))
+}
+
+pub fn revoke_role(
+    env: ExecuteEnv,
+    role: Role,
+    address: String,
+    token_id_prefix: Option<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_is_admin(deps.storage, &info.sender)?;
+
+    let address = deps.api.addr_validate(&address)?;
+    let prefix = token_id_prefix.unwrap_or_default();
+    ROLES.remove(deps.storage, (role_key(&role), &address, &prefix));
+
+    Ok(Response::new()
+        .add_attribute("action", "revoke_role")
+        .add_attribute("role", role_key(&role))
+        .add_attribute("address", address)
+        .add_attribute("token_id_prefix", prefix))
+}
+
+pub fn transfer_admin(env: ExecuteEnv, new_admin: String) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_is_admin(deps.storage, &info.sender)?;
+
+    let new_admin = deps.api.addr_validate(&new_admin)?;
+    PENDING_ADMIN.save(deps.storage, &new_admin)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "transfer_admin")
+        .add_attribute("pending_admin", new_admin))
+}
+
+pub fn accept_admin(env: ExecuteEnv) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    if PENDING_ADMIN.may_load(deps.storage)? != Some(info.sender.clone()) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    ADMIN.save(deps.storage, &info.sender)?;
+    PENDING_ADMIN.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "accept_admin")
+        .add_attribute("admin", info.sender))
+}
+
+pub fn set_transfer_restrictions(
+    env: ExecuteEnv,
+    token_id: TokenId,
+    restrictions: TransferRestrictions,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_has_role(deps.storage, &Role::Minter, &info.sender, Some(&token_id))?;
+
+    TRANSFER_RESTRICTIONS.save(deps.storage, &token_id, &restrictions)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "set_transfer_restrictions")
+        .add_attribute("token_id", token_id)
+        .add_attribute("soulbound", restrictions.soulbound.to_string())
+        .add_attribute("allow_list_only", restrictions.allow_list_only.to_string()))
+}
+
+pub fn update_holder_list(
+    env: ExecuteEnv,
+    token_id: TokenId,
+    list: HolderList,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_has_role(deps.storage, &Role::Minter, &info.sender, Some(&token_id))?;
+
+    let holders = match list {
+        HolderList::Allow => ALLOW_LIST,
+        HolderList::Deny => DENY_LIST,
+    };
+    for holder in add.iter() {
+        let holder = deps.api.addr_validate(holder)?;
+        holders.save(deps.storage, (&token_id, &holder), &Empty {})?;
+    }
+    for holder in remove.iter() {
+        let holder = deps.api.addr_validate(holder)?;
+        holders.remove(deps.storage, (&token_id, &holder));
+    }
+
+    Ok(Response::new()
+        .add_attribute("action", "update_holder_list")
+        .add_attribute("token_id", token_id)
+        .add_attribute(
+            "list",
+            match list {
+                HolderList::Allow => "allow",
+                HolderList::Deny => "deny",
+            },
+        )
+        .add_attribute("added", add.join(","))
+        .add_attribute("removed", remove.join(",")))
+}
+
+pub fn set_paused(env: ExecuteEnv, paused: bool) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, info, .. } = env;
+    guard_has_role(deps.storage, &Role::Pauser, &info.sender, None)?;
+
+    PAUSED.save(deps.storage, &paused)?;
+
+    Ok(Response::new().add_attribute("action", if paused { "pause" } else { "unpause" }))
+}
+
+/// Number of balances read by `migrate` and by default by `MigrateBalances`
+pub const MIGRATION_DEFAULT_LIMIT: u32 = 100;
+const MIGRATION_MAX_LIMIT: u32 = 1000;
+
+pub fn migrate_balances(env: ExecuteEnv, limit: Option<u32>) -> Result<Response, ContractError> {
+    let ExecuteEnv { deps, .. } = env;
+    let limit = limit
+        .unwrap_or(MIGRATION_DEFAULT_LIMIT)
+        .min(MIGRATION_MAX_LIMIT);
+    let done = migrate_balances_chunk(deps.storage, limit)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "migrate_balances")
+        .add_attribute("done", done.to_string()))
+}
+
+/// Rebuilds the holder index, and the total supplies if needed, from up to `limit` balances
+/// following the last one read. Returns whether all balances were read.
+pub fn migrate_balances_chunk(storage: &mut dyn Storage, limit: u32) -> StdResult<bool> {
+    let mut migration = match BALANCES_MIGRATION.may_load(storage)? {
+        Some(migration) => migration,
+        None => return Ok(true),
+    };
+
+    let start = migration
+        .last
+        .as_ref()
+        .map(|(owner, token_id)| Bound::exclusive((owner, token_id.as_str())));
+    let balances = BALANCES
+        .range(storage, start, None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    let done = balances.len() < limit as usize;
+
+    for ((owner, token_id), balance) in &balances {
+        if !balance.is_zero() {
+            TOKEN_OWNERS.save(storage, (token_id, owner), &Empty {})?;
+        }
+        TOTAL_SUPPLY.update(storage, token_id, |supply| -> StdResult<_> {
+            Ok(supply.unwrap_or_default().checked_add(*balance)?)
+        })?;
+    }
+
+    if done {
+        BALANCES_MIGRATION.remove(storage);
+    } else {
+        migration.last = balances.last().map(|(key, _)| key.clone());
+        BALANCES_MIGRATION.save(storage, &migration)?;
+    }
+    Ok(done)
+}
@@ -1,6 +1,17 @@
-use cosmwasm_std::{Addr, Deps, Env, StdResult};
+use cosmwasm_std::{
+    from_slice, to_vec, Addr, Api, Deps, DepsMut, Env, Order, StdError, StdResult, Storage,
+    Uint128,
+};
+use cw1155::Role;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
 
-use crate::{state::APPROVES, ContractError};
+use crate::{
+    state::{
+        role_key, ADMIN, ALLOWANCES, ALLOW_LIST, APPROVES, DENY_LIST, PAUSED, ROLES,
+        TRANSFER_RESTRICTIONS,
+    },
+    ContractError,
+};
 
 /// returns true if the sender can execute approve or reject on the contract
 pub fn check_can_approve(deps: Deps, env: &Env, owner: &Addr, operator: &Addr) -> StdResult<bool> {
@@ -28,3 +39,163 @@ pub fn guard_can_approve(
         Ok(())
     }
 }
+
+/// Authorizes `operator` to move `amount` of `token_id` from `owner`.
+/// The owner and operators approved for all tokens can move any amount, other senders use up
+/// their allowance for the token.
+pub fn guard_can_spend(
+    deps: &mut DepsMut,
+    env: &Env,
+    owner: &Addr,
+    operator: &Addr,
+    token_id: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    if check_can_approve(deps.as_ref(), env, owner, operator)? {
+        return Ok(());
+    }
+
+    let key = (owner, operator, token_id);
+    let mut allowance = ALLOWANCES
+        .may_load(deps.storage, key)?
+        .ok_or(ContractError::Unauthorized {})?;
+    if allowance.expires.is_expired(&env.block) {
+        return Err(ContractError::Expired {});
+    }
+    allowance.allowance = allowance
+        .allowance
+        .checked_sub(amount)
+        .map_err(StdError::overflow)?;
+    if allowance.allowance.is_zero() {
+        ALLOWANCES.remove(deps.storage, key);
+    } else {
+        ALLOWANCES.save(deps.storage, key, &allowance)?;
+    }
+    Ok(())
+}
+
+/// Returns whether `address` has `role` for `token_id`, or for all token ids if `None`
+pub fn has_role(
+    storage: &dyn Storage,
+    role: &Role,
+    address: &Addr,
+    token_id: Option<&str>,
+) -> StdResult<bool> {
+    for prefix in
+        ROLES
+            .prefix((role_key(role), address))
+            .keys(storage, None, None, Order::Ascending)
+    {
+        let prefix = prefix?;
+        let granted = match token_id {
+            Some(token_id) => token_id.starts_with(&prefix),
+            None => prefix.is_empty(),
+        };
+        if granted {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+pub fn guard_has_role(
+    storage: &dyn Storage,
+    role: &Role,
+    address: &Addr,
+    token_id: Option<&str>,
+) -> Result<(), ContractError> {
+    if !has_role(storage, role, address, token_id)? {
+        Err(ContractError::Unauthorized {})
+    } else {
+        Ok(())
+    }
+}
+
+pub fn guard_is_admin(storage: &dyn Storage, address: &Addr) -> Result<(), ContractError> {
+    if address != ADMIN.load(storage)? {
+        Err(ContractError::Unauthorized {})
+    } else {
+        Ok(())
+    }
+}
+
+/// Checks that `token_id` can move from `from` to `to`, according to the pause and its transfer
+/// restrictions
+pub fn guard_transfer_allowed(
+    storage: &dyn Storage,
+    from: Option<&Addr>,
+    to: Option<&Addr>,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    let restrictions = TRANSFER_RESTRICTIONS
+        .may_load(storage, token_id)?
+        .unwrap_or_default();
+    if from.is_some() && to.is_some() {
+        if PAUSED.may_load(storage)?.unwrap_or_default() {
+            return Err(ContractError::Paused {});
+        }
+        if restrictions.soulbound {
+            return Err(ContractError::Soulbound {
+                token_id: token_id.to_string(),
+            });
+        }
+    }
+
+    for holder in from.iter().chain(to.iter()) {
+        if DENY_LIST.has(storage, (token_id, holder)) {
+            return Err(ContractError::RestrictedHolder {
+                token_id: token_id.to_string(),
+                holder: holder.to_string(),
+            });
+        }
+    }
+    if let Some(to) = to {
+        if restrictions.allow_list_only && !ALLOW_LIST.has(storage, (token_id, to)) {
+            return Err(ContractError::RestrictedHolder {
+                token_id: token_id.to_string(),
+                holder: to.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The royalty fields of token extensions like `cw1155::Metadata`, other fields are ignored
+#[derive(Serialize, Deserialize, Default)]
+pub struct Royalties {
+    pub royalty_percentage: Option<u64>,
+    pub royalty_payment_address: Option<String>,
+}
+
+/// Which royalty fields a token extension has, whatever their values
+#[derive(Deserialize)]
+struct RoyaltyFields {
+    royalty_percentage: Option<IgnoredAny>,
+    royalty_payment_address: Option<IgnoredAny>,
+}
+
+/// Reads the royalties of a token extension, extensions without royalty fields have none.
+/// Errors if the fields are set with the wrong types.
+pub fn royalties<T: Serialize>(extension: &T) -> StdResult<Royalties> {
+    let data = to_vec(extension)?;
+    let fields: Option<RoyaltyFields> = from_slice(&data).unwrap_or_default();
+    match fields {
+        Some(RoyaltyFields {
+            royalty_percentage: None,
+            royalty_payment_address: None,
+        })
+        | None => Ok(Royalties::default()),
+        Some(_) => from_slice(&data),
+    }
+}
+
+pub fn validate_royalties<T: Serialize>(api: &dyn Api, extension: &T) -> Result<(), ContractError> {
+    let royalties = royalties(extension).map_err(|_| ContractError::InvalidRoyalty {})?;
+    if matches!(royalties.royalty_percentage, Some(percentage) if percentage > 100) {
+        return Err(ContractError::InvalidRoyaltyPercentage {});
+    }
+    if let Some(address) = royalties.royalty_payment_address {
+        api.addr_validate(&address)?;
+    }
+    Ok(())
+}
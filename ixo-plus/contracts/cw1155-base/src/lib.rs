@@ -17,5 +17,10 @@ pub mod state;
 
 pub use crate::error::ContractError;
 
+use cosmwasm_std::Empty;
+
+// This is a simple type to let us handle empty extensions
+pub type Extension = Option<Empty>;
+
 #[cfg(test)]
 mod tests;
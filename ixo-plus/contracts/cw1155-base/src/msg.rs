@@ -2,8 +2,22 @@ use cosmwasm_schema::cw_serde;
 
 #[cw_serde]
 pub struct InstantiateMsg {
-    /// The minter is the only one who can create new tokens.
+    /// Name of the collection
+    pub name: String,
+    /// Symbol of the collection
+    pub symbol: String,
+    /// The minter is granted the minter and metadata editor roles for all tokens.
     /// This is designed for a base token platform that is controlled by an external program or
     /// contract.
     pub minter: String,
+    /// The admin grants and revokes roles, the minter if unset.
+    pub admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {
+    /// Sets the name of the collection, e.g. for collections instantiated without one
+    pub name: Option<String>,
+    /// Sets the symbol of the collection, e.g. for collections instantiated without one
+    pub symbol: Option<String>,
 }
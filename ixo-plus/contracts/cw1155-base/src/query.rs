@@ -1,14 +1,25 @@
-use cosmwasm_std::{Addr, Deps, Env, Order, StdResult};
+use cosmwasm_std::{Addr, Decimal, Deps, Env, Order, StdResult, Uint128};
 use cw1155::{
-    ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse, IsApprovedForAllResponse,
-    TokenInfoResponse, TokensResponse,
+    AdminResponse, AllAllowancesResponse, AllSuppliesResponse, AllowanceInfo, AllowanceResponse,
+    ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse, CheckRoyaltiesResponse,
+    ContractInfoResponse, HasRoleResponse, HolderList, HolderListResponse,
+    IsApprovedForAllResponse, OwnersResponse, PausedResponse, RetiredSupplyResponse, Retirement,
+    RetirementsResponse, Role, RoleGrant, RoleGrantsResponse, RoyaltiesInfoResponse,
+    SupplyResponse, TokenId, TokenInfoResponse, TokenOwner, TokenSupply, TokensResponse,
+    TransferRestrictions,
 };
 use cw_storage_plus::Bound;
 use cw_utils::{maybe_addr, Expiration};
+use serde::{de::DeserializeOwned, Serialize};
 
 use crate::{
-    helpers::check_can_approve,
-    state::{APPROVES, BALANCES, TOKENS},
+    helpers::{self, check_can_approve, royalties},
+    state::{
+        self, role_key, token_extensions, RetirementInfo, ADMIN, ALLOWANCES, ALLOW_LIST, APPROVES,
+        BALANCES, CONTRACT_INFO, DENY_LIST, FROZEN_METADATA, MAX_SUPPLY, PAUSED, PENDING_ADMIN,
+        RETIRED_SUPPLY, ROLES, SUPPLY_POLICY, TOKENS, TOKEN_OWNERS, TOTAL_SUPPLY,
+        TRANSFER_RESTRICTIONS,
+    },
 };
 
 pub const DEFAULT_LIMIT: u32 = 10;
@@ -74,10 +85,78 @@ pub fn approved_for_all(
     Ok(ApprovedForAllResponse { operators })
 }
 
-pub fn token_info(deps: Deps, token_id: String) -> StdResult<TokenInfoResponse> {
+pub fn allowance(
+    deps: Deps,
+    owner: String,
+    spender: String,
+    token_id: String,
+) -> StdResult<AllowanceResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let spender = deps.api.addr_validate(&spender)?;
+
+    let allowance = ALLOWANCES
+        .may_load(deps.storage, (&owner, &spender, &token_id))?
+        .unwrap_or_default();
+
+    Ok(allowance)
+}
+
+pub fn all_allowances(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    include_expired: bool,
+    start_after: Option<(String, TokenId)>,
+    limit: Option<u32>,
+) -> StdResult<AllAllowancesResponse> {
+    let owner = deps.api.addr_validate(&owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|(spender, token_id)| -> StdResult<_> {
+            Ok((deps.api.addr_validate(&spender)?, token_id))
+        })
+        .transpose()?;
+    let start = start_after
+        .as_ref()
+        .map(|(spender, token_id)| Bound::exclusive((spender, token_id.as_str())));
+
+    let allowances = ALLOWANCES
+        .sub_prefix(&owner)
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|r| {
+            include_expired || r.is_err() || !r.as_ref().unwrap().1.expires.is_expired(&env.block)
+        })
+        .take(limit)
+        .map(|item| {
+            item.map(|((spender, token_id), allowance)| AllowanceInfo {
+                spender: spender.into(),
+                token_id,
+                allowance: allowance.allowance,
+                expires: allowance.expires,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(AllAllowancesResponse { allowances })
+}
+
+pub fn token_info<T>(deps: Deps, token_id: String) -> StdResult<TokenInfoResponse<T>>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
     let url = TOKENS.load(deps.storage, &token_id)?;
+    let extension = token_extensions()
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default();
+    let frozen = FROZEN_METADATA
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default();
 
-    Ok(TokenInfoResponse { url })
+    Ok(TokenInfoResponse {
+        url,
+        extension,
+        frozen,
+    })
 }
 
 pub fn is_approved_for_all(
@@ -128,3 +207,267 @@ pub fn all_tokens(
 
     Ok(TokensResponse { tokens })
 }
+
+pub fn owners(
+    deps: Deps,
+    token_id: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OwnersResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let owners = TOKEN_OWNERS
+        .prefix(&token_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|owner| -> StdResult<_> {
+            let owner = owner?;
+            let balance = BALANCES.load(deps.storage, (&owner, &token_id))?;
+            Ok(TokenOwner {
+                owner: owner.into(),
+                balance,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(OwnersResponse { owners })
+}
+
+pub fn supply(deps: Deps, token_id: String) -> StdResult<SupplyResponse> {
+    let total_supply = TOTAL_SUPPLY
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default();
+    let max_supply = MAX_SUPPLY.may_load(deps.storage, &token_id)?;
+    let supply_policy = SUPPLY_POLICY
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default();
+
+    Ok(SupplyResponse {
+        total_supply,
+        max_supply,
+        supply_policy,
+    })
+}
+
+pub fn all_supplies(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllSuppliesResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.as_ref().map(|s| Bound::exclusive(s.as_str()));
+
+    let supplies = TOKENS
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|token_id| -> StdResult<_> {
+            let token_id = token_id?;
+            let SupplyResponse {
+                total_supply,
+                max_supply,
+                supply_policy,
+            } = supply(deps, token_id.clone())?;
+            Ok(TokenSupply {
+                token_id,
+                total_supply,
+                max_supply,
+                supply_policy,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(AllSuppliesResponse { supplies })
+}
+
+fn build_retirement(id: u64, retirement: RetirementInfo) -> Retirement {
+    Retirement {
+        id,
+        owner: retirement.owner.into(),
+        token_id: retirement.token_id,
+        amount: retirement.amount,
+        beneficiary: retirement.beneficiary,
+        reason: retirement.reason,
+        timestamp: retirement.timestamp,
+    }
+}
+
+pub fn retirement(deps: Deps, id: u64) -> StdResult<Retirement> {
+    let retirement = state::retirements().load(deps.storage, id)?;
+    Ok(build_retirement(id, retirement))
+}
+
+pub fn retirements(
+    deps: Deps,
+    owner: Option<String>,
+    token_id: Option<String>,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<RetirementsResponse> {
+    let owner = maybe_addr(deps.api, owner)?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+
+    let retirements = state::retirements();
+    let range = match (owner, token_id) {
+        (Some(owner), Some(token_id)) => retirements
+            .idx
+            .owner_token_id
+            .prefix((owner, token_id))
+            .range(deps.storage, start, None, Order::Ascending),
+        (Some(owner), None) => {
+            retirements
+                .idx
+                .owner
+                .prefix(owner)
+                .range(deps.storage, start, None, Order::Ascending)
+        }
+        (None, Some(token_id)) => retirements.idx.token_id.prefix(token_id).range(
+            deps.storage,
+            start,
+            None,
+            Order::Ascending,
+        ),
+        (None, None) => retirements.range(deps.storage, start, None, Order::Ascending),
+    };
+    let retirements = range
+        .take(limit)
+        .map(|item| item.map(|(id, retirement)| build_retirement(id, retirement)))
+        .collect::<StdResult<_>>()?;
+
+    Ok(RetirementsResponse { retirements })
+}
+
+pub fn retired_supply(deps: Deps, token_id: String) -> StdResult<RetiredSupplyResponse> {
+    let retired_supply = RETIRED_SUPPLY
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default();
+
+    Ok(RetiredSupplyResponse { retired_supply })
+}
+
+pub fn admin(deps: Deps) -> StdResult<AdminResponse> {
+    let admin = ADMIN.load(deps.storage)?;
+    let pending_admin = PENDING_ADMIN.may_load(deps.storage)?;
+
+    Ok(AdminResponse {
+        admin: admin.into(),
+        pending_admin: pending_admin.map(Into::into),
+    })
+}
+
+pub fn has_role(
+    deps: Deps,
+    role: Role,
+    address: String,
+    token_id: Option<String>,
+) -> StdResult<HasRoleResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let has_role = helpers::has_role(deps.storage, &role, &address, token_id.as_deref())?;
+
+    Ok(HasRoleResponse { has_role })
+}
+
+pub fn role_grants(
+    deps: Deps,
+    role: Role,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<RoleGrantsResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_after = start_after
+        .map(|(address, prefix)| -> StdResult<_> {
+            Ok((deps.api.addr_validate(&address)?, prefix))
+        })
+        .transpose()?;
+    let start = start_after
+        .as_ref()
+        .map(|(address, prefix)| Bound::exclusive((address, prefix.as_str())));
+
+    let grants = ROLES
+        .sub_prefix(role_key(&role))
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| {
+            item.map(|(address, prefix)| RoleGrant {
+                address: address.into(),
+                token_id_prefix: Some(prefix).filter(|prefix| !prefix.is_empty()),
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(RoleGrantsResponse { grants })
+}
+
+pub fn paused(deps: Deps) -> StdResult<PausedResponse> {
+    let paused = PAUSED.may_load(deps.storage)?.unwrap_or_default();
+
+    Ok(PausedResponse { paused })
+}
+
+pub fn transfer_restrictions(deps: Deps, token_id: String) -> StdResult<TransferRestrictions> {
+    Ok(TRANSFER_RESTRICTIONS
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default())
+}
+
+pub fn holder_list(
+    deps: Deps,
+    token_id: String,
+    list: HolderList,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<HolderListResponse> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start_addr = maybe_addr(deps.api, start_after)?;
+    let start = start_addr.as_ref().map(Bound::exclusive);
+
+    let holders = match list {
+        HolderList::Allow => ALLOW_LIST,
+        HolderList::Deny => DENY_LIST,
+    };
+    let holders = holders
+        .prefix(&token_id)
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(Into::into))
+        .collect::<StdResult<_>>()?;
+
+    Ok(HolderListResponse { holders })
+}
+
+pub fn contract_info(deps: Deps) -> StdResult<ContractInfoResponse> {
+    Ok(CONTRACT_INFO.may_load(deps.storage)?.unwrap_or_default())
+}
+
+/// NOTE: default behaviour here is to round down
+/// EIP2981 specifies that the rounding behaviour is at the discretion of the implementer
+pub fn royalty_info<T>(
+    deps: Deps,
+    token_id: String,
+    sale_price: Uint128,
+) -> StdResult<RoyaltiesInfoResponse>
+where
+    T: Serialize + DeserializeOwned + Default,
+{
+    TOKENS.load(deps.storage, &token_id)?;
+    let extension: T = token_extensions()
+        .may_load(deps.storage, &token_id)?
+        .unwrap_or_default();
+    let royalties = royalties(&extension)?;
+
+    let royalty_percentage = Decimal::percent(royalties.royalty_percentage.unwrap_or_default());
+    Ok(RoyaltiesInfoResponse {
+        address: royalties.royalty_payment_address.unwrap_or_default(),
+        royalty_amount: sale_price * royalty_percentage,
+    })
+}
+
+/// As royalties are specified at token level and not at contract level, every token managed by
+/// this contract should be checked on sale to see if royalties are owed, and to whom
+pub fn check_royalties(_deps: Deps) -> StdResult<CheckRoyaltiesResponse> {
+    Ok(CheckRoyaltiesResponse {
+        royalty_payments: true,
+    })
+}
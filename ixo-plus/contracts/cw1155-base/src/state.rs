@@ -1,13 +1,128 @@
-use cosmwasm_std::{Addr, Uint128};
-use cw1155::Expiration;
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, Empty, Timestamp, Uint128};
+use cw1155::{
+    AllowanceResponse, ContractInfoResponse, Expiration, Role, SupplyPolicy, TransferRestrictions,
+};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-/// Store the minter address who have permission to mint new tokens.
-pub const MINTER: Item<Addr> = Item::new("minter");
+/// Store the name and symbol of the collection.
+pub const CONTRACT_INFO: Item<ContractInfoResponse> = Item::new("collection_info");
+/// Store the single minter of collections instantiated before roles, until `migrate` moves it
+/// to `ROLES`.
+pub const LEGACY_MINTER: Item<Addr> = Item::new("minter");
+/// Store the admin address who manages the roles.
+pub const ADMIN: Item<Addr> = Item::new("admin");
+/// Store the address proposed as the next admin, until it accepts.
+pub const PENDING_ADMIN: Item<Addr> = Item::new("pending_admin");
+/// Store the role grants, `(role, address, token_id_prefix) -> ()`.
+/// An empty prefix grants the role for all token ids.
+pub const ROLES: Map<(&str, &Addr, &str), Empty> = Map::new("roles");
 /// Store the balance map, `(owner, token_id) -> balance`
 pub const BALANCES: Map<(&Addr, &str), Uint128> = Map::new("balances");
+/// Index of the holders of each token, `(token_id, owner) -> ()`, for the non-zero `BALANCES`
+pub const TOKEN_OWNERS: Map<(&str, &Addr), Empty> = Map::new("token_owners");
+/// Store the progress of rebuilding `TOKEN_OWNERS` and `TOTAL_SUPPLY` from `BALANCES` after
+/// migrating a collection from a version without them, removed once done.
+pub const BALANCES_MIGRATION: Item<BalancesMigration> = Item::new("balances_migration");
 /// Store the approval status, `(owner, spender) -> expiration`
 pub const APPROVES: Map<(&Addr, &Addr), Expiration> = Map::new("approves");
+/// Store the allowances of single tokens, `(owner, spender, token_id) -> allowance`
+pub const ALLOWANCES: Map<(&Addr, &Addr, &str), AllowanceResponse> = Map::new("allowances");
 /// Store the tokens metadata url, also supports enumerating tokens,
 /// An entry for token_id must exist as long as there's tokens in circulation.
 pub const TOKENS: Map<&str, String> = Map::new("tokens");
+/// Store the frozen status of token metadata, `token_id -> frozen`
+pub const FROZEN_METADATA: Map<&str, bool> = Map::new("frozen_metadata");
+/// Store the amount of each token in circulation, `token_id -> total supply`
+pub const TOTAL_SUPPLY: Map<&str, Uint128> = Map::new("total_supply");
+/// Store the optional supply cap, `token_id -> max supply`, set by the first mint of a token
+pub const MAX_SUPPLY: Map<&str, Uint128> = Map::new("max_supply");
+/// Store whether more of a token can be minted, `token_id -> supply policy`,
+/// set by the first mint of a token. Missing means `Fixed`.
+pub const SUPPLY_POLICY: Map<&str, SupplyPolicy> = Map::new("supply_policy");
+
+/// Store whether transfers between accounts are paused
+pub const PAUSED: Item<bool> = Item::new("paused");
+/// Store the transfer restrictions of tokens, `token_id -> restrictions`
+pub const TRANSFER_RESTRICTIONS: Map<&str, TransferRestrictions> =
+    Map::new("transfer_restrictions");
+/// Store the accounts allowed to hold a token, `(token_id, holder) -> ()`
+pub const ALLOW_LIST: Map<(&str, &Addr), Empty> = Map::new("allow_list");
+/// Store the accounts denied to send or receive a token, `(token_id, holder) -> ()`
+pub const DENY_LIST: Map<(&str, &Addr), Empty> = Map::new("deny_list");
+
+/// Returns the storage key of `role` in `ROLES`
+pub fn role_key(role: &Role) -> &'static str {
+    match role {
+        Role::Minter => "minter",
+        Role::MetadataEditor => "metadata_editor",
+        Role::Pauser => "pauser",
+    }
+}
+
+/// Store the on-chain metadata of tokens, `token_id -> extension`.
+/// Missing for tokens minted before extensions were stored, which read as the default.
+pub fn token_extensions<'a, T>() -> Map<'a, &'a str, T>
+where
+    T: Serialize + DeserializeOwned,
+{
+    Map::new("token_extensions")
+}
+
+/// Store the total amount retired of each token, `token_id -> retired supply`
+pub const RETIRED_SUPPLY: Map<&str, Uint128> = Map::new("retired_supply");
+/// Store the number of retirement certificates, which is the last retirement id
+pub const RETIREMENT_COUNT: Item<u64> = Item::new("retirement_count");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BalancesMigration {
+    /// The last balance read, `(owner, token_id)`, `None` before the first chunk
+    pub last: Option<(Addr, String)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RetirementInfo {
+    pub owner: Addr,
+    pub token_id: String,
+    pub amount: Uint128,
+    pub beneficiary: String,
+    pub reason: Option<String>,
+    pub timestamp: Timestamp,
+}
+
+pub struct RetirementIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, RetirementInfo, u64>,
+    pub token_id: MultiIndex<'a, String, RetirementInfo, u64>,
+    pub owner_token_id: MultiIndex<'a, (Addr, String), RetirementInfo, u64>,
+}
+
+impl<'a> IndexList<RetirementInfo> for RetirementIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RetirementInfo>> + '_> {
+        let v: Vec<&dyn Index<RetirementInfo>> =
+            vec![&self.owner, &self.token_id, &self.owner_token_id];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Store the retirement certificates, `id -> retirement`, indexed by owner, token_id and both
+pub fn retirements<'a>() -> IndexedMap<'a, u64, RetirementInfo, RetirementIndexes<'a>> {
+    let indexes = RetirementIndexes {
+        owner: MultiIndex::new(
+            |_pk, r: &RetirementInfo| r.owner.clone(),
+            "retirements",
+            "retirements__owner",
+        ),
+        token_id: MultiIndex::new(
+            |_pk, r: &RetirementInfo| r.token_id.clone(),
+            "retirements",
+            "retirements__token_id",
+        ),
+        owner_token_id: MultiIndex::new(
+            |_pk, r: &RetirementInfo| (r.owner.clone(), r.token_id.clone()),
+            "retirements",
+            "retirements__owner_token_id",
+        ),
+    };
+    IndexedMap::new("retirements", indexes)
+}
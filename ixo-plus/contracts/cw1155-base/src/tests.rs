@@ -1,17 +1,19 @@
 use crate::{
-  contract::{execute, instantiate, query},
-  msg::InstantiateMsg,
-  ContractError,
+  contract::{execute, execute_with_extension, instantiate, migrate, query, query_with_extension},
+  msg::{InstantiateMsg, MigrateMsg},
+  state::{BALANCES, LEGACY_MINTER, TOKEN_OWNERS, TOTAL_SUPPLY},
+  ContractError, Extension,
 };
 use cosmwasm_std::{
   testing::{mock_dependencies, mock_env, mock_info},
-  to_binary, Binary, Response,
+  to_binary, Addr, Binary, Order, Response, Uint128,
 };
 use cw1155::{
-  ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse, Cw1155BatchReceiveMsg,
-  Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg, IsApprovedForAllResponse,
-  TokenInfoResponse, TokensResponse,
+  AllAllowancesResponse, AllSuppliesResponse, AllowanceInfo, AllowanceResponse, ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse,
+  Cw1155BatchReceiveMsg, Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg,
+  AdminResponse, CheckRoyaltiesResponse, ContractInfoResponse, HasRoleResponse, HolderList, HolderListResponse, IsApprovedForAllResponse, OwnersResponse, PausedResponse, Metadata, RetiredSupplyResponse, Retirement, RetirementsResponse, Role, RoleGrant, RoleGrantsResponse, RoyaltiesInfoResponse, SupplyPolicy, TransferRestrictions, SupplyResponse, TokenInfoResponse, TokenOwner, TokenSupply, TokensResponse,
 };
+use cw2::{get_contract_version, set_contract_version};
 use cw_utils::Expiration;
 
 #[test]
@@ -44,7 +46,10 @@ fn check_transfers() {
 
   let mut deps = mock_dependencies();
   let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
       minter: minter.clone(),
+      admin: None,
   };
   let res = instantiate(deps.as_mut(), mock_env(), mock_info("operator", &[]), msg).unwrap();
   assert_eq!(0, res.messages.len());
@@ -55,6 +60,9 @@ fn check_transfers() {
       token_id: token1.clone(),
       value: 1u64.into(),
       uri: uri.clone(),
+      max_supply: None,
+      supply_policy: None,
+      extension: None,
       msg: None,
   };
   assert!(matches!(
@@ -370,7 +378,10 @@ fn check_send_contract() {
 
   let mut deps = mock_dependencies();
   let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
       minter: minter.clone(),
+      admin: None,
   };
   let res = instantiate(deps.as_mut(), mock_env(), mock_info("operator", &[]), msg).unwrap();
   assert_eq!(0, res.messages.len());
@@ -384,6 +395,9 @@ fn check_send_contract() {
           token_id: token2.clone(),
           value: 1u64.into(),
           uri: uri.clone(),
+          max_supply: None,
+          supply_policy: None,
+          extension: None,
           msg: None,
       },
   )
@@ -400,6 +414,9 @@ fn check_send_contract() {
               token_id: token1.clone(),
               value: 1u64.into(),
               uri: uri.clone(),
+              max_supply: None,
+              supply_policy: None,
+              extension: None,
               msg: Some(dummy_msg.clone()),
           },
       )
@@ -466,7 +483,10 @@ fn check_queries() {
 
   let mut deps = mock_dependencies();
   let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
       minter: minter.clone(),
+      admin: None,
   };
   let res = instantiate(deps.as_mut(), mock_env(), mock_info("operator", &[]), msg).unwrap();
   assert_eq!(0, res.messages.len());
@@ -538,7 +558,11 @@ fn check_queries() {
               token_id: "token5".to_owned()
           },
       ),
-      to_binary(&TokenInfoResponse { url: uri.clone() })
+      to_binary(&TokenInfoResponse::<Extension> {
+          url: uri.clone(),
+          extension: None,
+          frozen: false,
+      })
   );
 
   for user in users[1..].iter() {
@@ -590,7 +614,10 @@ fn approval_expires() {
   };
 
   let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
       minter: minter.clone(),
+      admin: None,
   };
   let res = instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
   assert_eq!(0, res.messages.len());
@@ -604,6 +631,9 @@ fn approval_expires() {
           token_id: token1,
           value: 1u64.into(),
           uri: uri.clone(),
+          max_supply: None,
+          supply_policy: None,
+          extension: None,
           msg: None,
       },
   )
@@ -665,7 +695,10 @@ fn mint_overflow() {
 
   let env = mock_env();
   let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
       minter: minter.clone(),
+      admin: None,
   };
   let res = instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
   assert_eq!(0, res.messages.len());
@@ -679,6 +712,9 @@ fn mint_overflow() {
           token_id: token1.clone(),
           value: u128::MAX.into(),
           uri: uri.clone(),
+          max_supply: None,
+          supply_policy: None,
+          extension: None,
           msg: None,
       },
   )
@@ -694,6 +730,9 @@ fn mint_overflow() {
   //             token_id: token1,
   //             value: 1u64.into(),
   //             uri: uri.clone(),
+  //             max_supply: None,
+  //             supply_policy: None,
+  //             extension: None,
   //             msg: None,
   //         },
   //     ),
@@ -703,3 +742,1655 @@ fn mint_overflow() {
   //     }))
   // ));
 }
+
+#[test]
+fn track_supply() {
+  let mut deps = mock_dependencies();
+  let token1 = "token1".to_owned();
+  let token2 = "token2".to_owned();
+  let token3 = "token3".to_owned();
+  let minter = String::from("minter");
+  let user1 = String::from("user1");
+  let uri = String::from("uri");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  // the first mint can't exceed the cap it sets
+  let err = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::Mint {
+          to: user1.clone(),
+          token_id: token1.clone(),
+          value: 11u64.into(),
+          uri: uri.clone(),
+          max_supply: Some(10u64.into()),
+          supply_policy: None,
+          extension: None,
+          msg: None,
+      },
+  )
+  .unwrap_err();
+  assert!(matches!(err, ContractError::MaxSupplyExceeded { token_id } if token_id == token1));
+
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::Mint {
+          to: user1.clone(),
+          token_id: token1.clone(),
+          value: 10u64.into(),
+          uri: uri.clone(),
+          max_supply: Some(10u64.into()),
+          supply_policy: None,
+          extension: None,
+          msg: None,
+      },
+  )
+  .unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::BatchMint {
+          to: user1.clone(),
+          batch: vec![
+              (token2.clone(), 5u64.into(), uri.clone()),
+              (token3.clone(), 7u64.into(), uri.clone()),
+          ],
+          msg: None,
+      },
+  )
+  .unwrap();
+
+  // transfers don't change the supply, burns do
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(user1.as_ref(), &[]),
+      Cw1155ExecuteMsg::SendFrom {
+          from: user1.clone(),
+          to: minter.clone(),
+          token_id: token1.clone(),
+          value: 4u64.into(),
+          msg: None,
+      },
+  )
+  .unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(user1.as_ref(), &[]),
+      Cw1155ExecuteMsg::BatchBurn {
+          from: user1.clone(),
+          batch: vec![
+              (token1.clone(), 1u64.into(), String::new()),
+              (token3.clone(), 7u64.into(), String::new()),
+          ],
+      },
+  )
+  .unwrap();
+
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Supply {
+              token_id: token1.clone(),
+          },
+      ),
+      to_binary(&SupplyResponse {
+          total_supply: 9u64.into(),
+          max_supply: Some(10u64.into()),
+          supply_policy: SupplyPolicy::Fixed,
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Supply {
+              token_id: "unknown".to_owned(),
+          },
+      ),
+      to_binary(&SupplyResponse {
+          total_supply: 0u64.into(),
+          max_supply: None,
+          supply_policy: SupplyPolicy::Fixed,
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env,
+          Cw1155QueryMsg::AllSupplies {
+              start_after: Some(token1.clone()),
+              limit: None,
+          },
+      ),
+      to_binary(&AllSuppliesResponse {
+          supplies: vec![
+              TokenSupply {
+                  token_id: token2,
+                  total_supply: 5u64.into(),
+                  max_supply: None,
+                  supply_policy: SupplyPolicy::Fixed,
+              },
+              TokenSupply {
+                  token_id: token3,
+                  total_supply: 0u64.into(),
+                  max_supply: None,
+                  supply_policy: SupplyPolicy::Fixed,
+              },
+          ],
+      })
+  );
+}
+
+#[test]
+fn mint_open_supply() {
+  let mut deps = mock_dependencies();
+  let fixed = "fixed".to_owned();
+  let open = "open".to_owned();
+  let minter = String::from("minter");
+  let user1 = String::from("user1");
+  let user2 = String::from("user2");
+  let uri = String::from("uri");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  let mint = |token_id: &str, to: &str, value: u64, supply_policy, max_supply| {
+      Cw1155ExecuteMsg::Mint {
+          to: to.to_owned(),
+          token_id: token_id.to_owned(),
+          value: value.into(),
+          uri: uri.clone(),
+          max_supply,
+          supply_policy,
+          extension: None,
+          msg: None,
+      }
+  };
+
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      mint(&fixed, &user1, 1, None, None),
+  )
+  .unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      mint(&open, &user1, 5, Some(SupplyPolicy::Open), Some(20u64.into())),
+  )
+  .unwrap();
+
+  // a fixed token id stays unique
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(minter.as_ref(), &[]),
+          mint(&fixed, &user2, 1, None, None),
+      ),
+      Err(ContractError::Claimed {})
+  ));
+  // the supply settings can't be changed later
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(minter.as_ref(), &[]),
+          mint(&open, &user2, 1, None, Some(100u64.into())),
+      ),
+      Err(ContractError::SupplySettingsLocked {})
+  ));
+
+  // an open token id can be topped up, also in batch, up to its cap
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      mint(&open, &user2, 10, None, None),
+  )
+  .unwrap();
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(minter.as_ref(), &[]),
+          Cw1155ExecuteMsg::BatchMint {
+              to: user2.clone(),
+              batch: vec![(fixed.clone(), 1u64.into(), String::new())],
+              msg: None,
+          },
+      ),
+      Err(ContractError::Claimed {})
+  ));
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::BatchMint {
+          to: user2.clone(),
+          batch: vec![(open.clone(), 5u64.into(), String::new())],
+          msg: None,
+      },
+  )
+  .unwrap();
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(minter.as_ref(), &[]),
+          mint(&open, &user2, 1, None, None),
+      ),
+      Err(ContractError::MaxSupplyExceeded { .. })
+  ));
+
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Supply {
+              token_id: open.clone(),
+          },
+      ),
+      to_binary(&SupplyResponse {
+          total_supply: 20u64.into(),
+          max_supply: Some(20u64.into()),
+          supply_policy: SupplyPolicy::Open,
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Balance {
+              owner: user2,
+              token_id: open.clone(),
+          },
+      ),
+      to_binary(&BalanceResponse {
+          balance: 15u64.into()
+      })
+  );
+  // the uri of later mints is ignored
+  assert_eq!(
+      query(deps.as_ref(), env, Cw1155QueryMsg::TokenInfo { token_id: open }),
+      to_binary(&TokenInfoResponse::<Extension> {
+          url: uri,
+          extension: None,
+          frozen: false,
+      })
+  );
+}
+
+#[test]
+fn token_allowances() {
+  let mut deps = mock_dependencies();
+  let token1 = "token1".to_owned();
+  let token2 = "token2".to_owned();
+  let minter = String::from("minter");
+  let user1 = String::from("user1");
+  let user2 = String::from("user2");
+  let spender = String::from("spender");
+
+  let env = {
+      let mut env = mock_env();
+      env.block.height = 10;
+      env
+  };
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::BatchMint {
+          to: user1.clone(),
+          batch: vec![
+              (token1.clone(), 10u64.into(), String::new()),
+              (token2.clone(), 10u64.into(), String::new()),
+          ],
+          msg: None,
+      },
+  )
+  .unwrap();
+
+  // can't approve yourself
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(user1.as_ref(), &[]),
+          Cw1155ExecuteMsg::Approve {
+              spender: user1.clone(),
+              token_id: token1.clone(),
+              amount: 5u64.into(),
+              expires: None,
+          },
+      ),
+      Err(ContractError::CannotSetOwnAccount {})
+  ));
+
+  assert_eq!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(user1.as_ref(), &[]),
+          Cw1155ExecuteMsg::Approve {
+              spender: spender.clone(),
+              token_id: token1.clone(),
+              amount: 5u64.into(),
+              expires: None,
+          },
+      )
+      .unwrap(),
+      Response::new()
+          .add_attribute("action", "approve")
+          .add_attribute("owner", &user1)
+          .add_attribute("spender", &spender)
+          .add_attribute("token_id", &token1)
+          .add_attribute("amount", 5u64.to_string())
+  );
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(user1.as_ref(), &[]),
+      Cw1155ExecuteMsg::Approve {
+          spender: spender.clone(),
+          token_id: token2.clone(),
+          amount: 3u64.into(),
+          expires: Some(Expiration::AtHeight(20)),
+      },
+  )
+  .unwrap();
+
+  // the allowance only covers its token id and amount
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(spender.as_ref(), &[]),
+          Cw1155ExecuteMsg::SendFrom {
+              from: user1.clone(),
+              to: user2.clone(),
+              token_id: token1.clone(),
+              value: 6u64.into(),
+              msg: None,
+          },
+      ),
+      Err(ContractError::Std(_))
+  ));
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(spender.as_ref(), &[]),
+          Cw1155ExecuteMsg::SendFrom {
+              from: user2.clone(),
+              to: spender.clone(),
+              token_id: token1.clone(),
+              value: 1u64.into(),
+              msg: None,
+          },
+      ),
+      Err(ContractError::Unauthorized {})
+  ));
+
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(spender.as_ref(), &[]),
+      Cw1155ExecuteMsg::BatchSendFrom {
+          from: user1.clone(),
+          to: user2.clone(),
+          batch: vec![
+              (token1.clone(), 2u64.into(), String::new()),
+              (token2.clone(), 3u64.into(), String::new()),
+          ],
+          msg: None,
+      },
+  )
+  .unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(spender.as_ref(), &[]),
+      Cw1155ExecuteMsg::Burn {
+          from: user1.clone(),
+          token_id: token1.clone(),
+          value: 1u64.into(),
+      },
+  )
+  .unwrap();
+
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Allowance {
+              owner: user1.clone(),
+              spender: spender.clone(),
+              token_id: token1.clone(),
+          },
+      ),
+      to_binary(&AllowanceResponse {
+          allowance: 2u64.into(),
+          expires: Expiration::Never {},
+      })
+  );
+  // a used up allowance is removed
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::AllAllowances {
+              owner: user1.clone(),
+              include_expired: None,
+              start_after: None,
+              limit: None,
+          },
+      ),
+      to_binary(&AllAllowancesResponse {
+          allowances: vec![AllowanceInfo {
+              spender: spender.clone(),
+              token_id: token1.clone(),
+              allowance: 2u64.into(),
+              expires: Expiration::Never {},
+          }],
+      })
+  );
+
+  // expired allowances can't be used
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(user1.as_ref(), &[]),
+      Cw1155ExecuteMsg::Approve {
+          spender: spender.clone(),
+          token_id: token2.clone(),
+          amount: 3u64.into(),
+          expires: Some(Expiration::AtHeight(20)),
+      },
+  )
+  .unwrap();
+  let env = {
+      let mut env = mock_env();
+      env.block.height = 20;
+      env
+  };
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(spender.as_ref(), &[]),
+          Cw1155ExecuteMsg::Burn {
+              from: user1.clone(),
+              token_id: token2.clone(),
+              value: 1u64.into(),
+          },
+      ),
+      Err(ContractError::Expired {})
+  ));
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::AllAllowances {
+              owner: user1.clone(),
+              include_expired: Some(true),
+              start_after: Some((spender.clone(), token1.clone())),
+              limit: None,
+          },
+      ),
+      to_binary(&AllAllowancesResponse {
+          allowances: vec![AllowanceInfo {
+              spender: spender.clone(),
+              token_id: token2,
+              allowance: 3u64.into(),
+              expires: Expiration::AtHeight(20),
+          }],
+      })
+  );
+
+  // zero removes the allowance
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(user1.as_ref(), &[]),
+      Cw1155ExecuteMsg::Approve {
+          spender: spender.clone(),
+          token_id: token1.clone(),
+          amount: Uint128::zero(),
+          expires: None,
+      },
+  )
+  .unwrap();
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env,
+          mock_info(spender.as_ref(), &[]),
+          Cw1155ExecuteMsg::Burn {
+              from: user1,
+              token_id: token1,
+              value: 1u64.into(),
+          },
+      ),
+      Err(ContractError::Unauthorized {})
+  ));
+}
+
+#[test]
+fn update_metadata() {
+  type MetadataExtension = Option<Metadata>;
+
+  let mut deps = mock_dependencies();
+  let token1 = "token1".to_owned();
+  let minter = String::from("minter");
+  let user1 = String::from("user1");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  let metadata = Metadata {
+      name: Some("Cookstove credits".to_owned()),
+      decimals: Some(0),
+      vintage: Some("2022".to_owned()),
+      project_id: Some("project1".to_owned()),
+      ..Metadata::default()
+  };
+  execute_with_extension(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::Mint {
+          to: user1.clone(),
+          token_id: token1.clone(),
+          value: 10u64.into(),
+          uri: "uri".to_owned(),
+          max_supply: None,
+          supply_policy: None,
+          extension: Some(metadata.clone()),
+          msg: None,
+      },
+  )
+  .unwrap();
+
+  let token_info = |deps: cosmwasm_std::Deps| {
+      query_with_extension::<MetadataExtension>(
+          deps,
+          mock_env(),
+          Cw1155QueryMsg::TokenInfo {
+              token_id: token1.clone(),
+          },
+      )
+  };
+  assert_eq!(
+      token_info(deps.as_ref()),
+      to_binary(&TokenInfoResponse {
+          url: "uri".to_owned(),
+          extension: Some(metadata.clone()),
+          frozen: false,
+      })
+  );
+
+  let update = |url: Option<&str>, extension, freeze| {
+      Cw1155ExecuteMsg::<MetadataExtension>::UpdateMetadata {
+          token_id: token1.clone(),
+          url: url.map(str::to_owned),
+          extension,
+          freeze,
+      }
+  };
+
+  // only the minter can update metadata
+  assert!(matches!(
+      execute_with_extension(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(user1.as_ref(), &[]),
+          update(Some("new_uri"), None, None),
+      ),
+      Err(ContractError::Unauthorized {})
+  ));
+
+  let metadata = Metadata {
+      description: Some("Retired in 2023".to_owned()),
+      ..metadata
+  };
+  assert_eq!(
+      execute_with_extension(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(minter.as_ref(), &[]),
+          update(None, Some(Some(metadata.clone())), Some(true)),
+      )
+      .unwrap(),
+      Response::new()
+          .add_attribute("action", "set_metadata")
+          .add_attribute("url", "uri")
+          .add_attribute("token_id", &token1)
+  );
+  assert_eq!(
+      token_info(deps.as_ref()),
+      to_binary(&TokenInfoResponse {
+          url: "uri".to_owned(),
+          extension: Some(metadata),
+          frozen: true,
+      })
+  );
+
+  // frozen metadata can't be changed anymore
+  assert!(matches!(
+      execute_with_extension(
+          deps.as_mut(),
+          env,
+          mock_info(minter.as_ref(), &[]),
+          update(Some("new_uri"), None, None),
+      ),
+      Err(ContractError::MetadataFrozen { .. })
+  ));
+}
+
+#[test]
+fn retire_tokens() {
+  let mut deps = mock_dependencies();
+  let token1 = "token1".to_owned();
+  let token2 = "token2".to_owned();
+  let minter = String::from("minter");
+  let user1 = String::from("user1");
+  let user2 = String::from("user2");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  let open = Some(SupplyPolicy::Open);
+  for (user, token_id, supply_policy) in [(&user1, &token1, open.clone()), (&user1, &token2, None), (&user2, &token1, None)] {
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(minter.as_ref(), &[]),
+          Cw1155ExecuteMsg::Mint {
+              to: user.clone(),
+              token_id: token_id.clone(),
+              value: 10u64.into(),
+              uri: "uri".to_owned(),
+              max_supply: None,
+              supply_policy,
+              extension: None,
+              msg: None,
+          },
+      )
+      .unwrap();
+  }
+
+  let retire = |token_id: &str, amount: u64, beneficiary: Option<&str>| Cw1155ExecuteMsg::Retire {
+      token_id: token_id.to_owned(),
+      amount: amount.into(),
+      beneficiary: beneficiary.map(str::to_owned),
+      reason: Some("offset".to_owned()),
+  };
+
+  // invalid amount
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), retire(&token1, 0, None)),
+      Err(ContractError::InvalidZeroAmount {})
+  ));
+
+  let rsp = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(user1.as_ref(), &[]),
+      retire(&token1, 4, Some("acme")),
+  )
+  .unwrap();
+  assert_eq!(
+      rsp,
+      Response::new()
+          .add_attribute("action", "transfer")
+          .add_attribute("token_id", &token1)
+          .add_attribute("amount", 4u64.to_string())
+          .add_attribute("from", &user1)
+          .add_attribute("action", "retire")
+          .add_attribute("retirement_id", "1")
+          .add_attribute("owner", &user1)
+          .add_attribute("token_id", &token1)
+          .add_attribute("amount", 4u64.to_string())
+          .add_attribute("beneficiary", "acme")
+  );
+  execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), retire(&token2, 1, None)).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info(user2.as_ref(), &[]), retire(&token1, 2, None)).unwrap();
+
+  // the retired tokens are burned
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Balance {
+              owner: user1.clone(),
+              token_id: token1.clone(),
+          },
+      ),
+      to_binary(&BalanceResponse {
+          balance: 6u64.into()
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Supply {
+              token_id: token1.clone(),
+          },
+      ),
+      to_binary(&SupplyResponse {
+          total_supply: 14u64.into(),
+          max_supply: None,
+          supply_policy: SupplyPolicy::Open,
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::RetiredSupply {
+              token_id: token1.clone(),
+          },
+      ),
+      to_binary(&RetiredSupplyResponse {
+          retired_supply: 6u64.into()
+      })
+  );
+
+  let certificate = |id: u64, owner: &str, token_id: &str, amount: u64, beneficiary: &str| Retirement {
+      id,
+      owner: owner.to_owned(),
+      token_id: token_id.to_owned(),
+      amount: amount.into(),
+      beneficiary: beneficiary.to_owned(),
+      reason: Some("offset".to_owned()),
+      timestamp: env.block.time,
+  };
+  assert_eq!(
+      query(deps.as_ref(), env.clone(), Cw1155QueryMsg::Retirement { id: 1 }),
+      to_binary(&certificate(1, &user1, &token1, 4, "acme"))
+  );
+
+  let retirements = |owner: Option<&String>, token_id: Option<&String>, start_after| {
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::Retirements {
+              owner: owner.cloned(),
+              token_id: token_id.cloned(),
+              start_after,
+              limit: None,
+          },
+      )
+  };
+  assert_eq!(
+      retirements(None, None, Some(1)),
+      to_binary(&RetirementsResponse {
+          retirements: vec![certificate(2, &user1, &token2, 1, &user1), certificate(3, &user2, &token1, 2, &user2)]
+      })
+  );
+  assert_eq!(
+      retirements(Some(&user1), None, None),
+      to_binary(&RetirementsResponse {
+          retirements: vec![certificate(1, &user1, &token1, 4, "acme"), certificate(2, &user1, &token2, 1, &user1)]
+      })
+  );
+  assert_eq!(
+      retirements(None, Some(&token1), None),
+      to_binary(&RetirementsResponse {
+          retirements: vec![certificate(1, &user1, &token1, 4, "acme"), certificate(3, &user2, &token1, 2, &user2)]
+      })
+  );
+  assert_eq!(
+      retirements(Some(&user1), Some(&token2), None),
+      to_binary(&RetirementsResponse {
+          retirements: vec![certificate(2, &user1, &token2, 1, &user1)]
+      })
+  );
+  assert_eq!(
+      retirements(Some(&user1), Some(&token1), Some(1)),
+      to_binary(&RetirementsResponse { retirements: vec![] })
+  );
+
+  // cannot retire more than owned
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), retire(&token1, 7, None)),
+      Err(ContractError::Std(_))
+  ));
+}
+
+#[test]
+fn roles() {
+  let mut deps = mock_dependencies();
+  let minter = String::from("minter");
+  let admin = String::from("admin");
+  let issuer = String::from("issuer");
+  let dao = String::from("dao");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: Some(admin.clone()),
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  let mint = |token_id: &str| Cw1155ExecuteMsg::Mint {
+      to: dao.clone(),
+      token_id: token_id.to_owned(),
+      value: 1u64.into(),
+      uri: "uri".to_owned(),
+      max_supply: None,
+      supply_policy: None,
+      extension: None,
+      msg: None,
+  };
+  let grant = |role, address: &String, token_id_prefix: Option<&str>| Cw1155ExecuteMsg::GrantRole {
+      role,
+      address: address.clone(),
+      token_id_prefix: token_id_prefix.map(str::to_owned),
+  };
+
+  // only the admin manages roles
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), grant(Role::Minter, &issuer, None)),
+      Err(ContractError::Unauthorized {})
+  ));
+  let rsp = execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(admin.as_ref(), &[]),
+      grant(Role::Minter, &issuer, Some("cookstove/")),
+  )
+  .unwrap();
+  assert_eq!(
+      rsp,
+      Response::new()
+          .add_attribute("action", "grant_role")
+          .add_attribute("role", "minter")
+          .add_attribute("address", &issuer)
+          .add_attribute("token_id_prefix", "cookstove/")
+  );
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(admin.as_ref(), &[]),
+      grant(Role::MetadataEditor, &dao, Some("cookstove/")),
+  )
+  .unwrap();
+
+  // minters can only mint their token ids
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(issuer.as_ref(), &[]), mint("solar/1")),
+      Err(ContractError::Unauthorized {})
+  ));
+  execute(deps.as_mut(), env.clone(), mock_info(issuer.as_ref(), &[]), mint("cookstove/1")).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint("solar/1")).unwrap();
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(issuer.as_ref(), &[]),
+          Cw1155ExecuteMsg::BatchMint {
+              to: dao.clone(),
+              batch: vec![
+                  ("cookstove/2".to_owned(), 1u64.into(), "uri".to_owned()),
+                  ("solar/2".to_owned(), 1u64.into(), "uri".to_owned()),
+              ],
+              msg: None,
+          },
+      ),
+      Err(ContractError::Unauthorized {})
+  ));
+
+  // metadata editors can only update their token ids
+  let update = |token_id: &str| Cw1155ExecuteMsg::UpdateMetadata {
+      token_id: token_id.to_owned(),
+      url: Some("new_uri".to_owned()),
+      extension: None,
+      freeze: None,
+  };
+  execute(deps.as_mut(), env.clone(), mock_info(dao.as_ref(), &[]), update("cookstove/1")).unwrap();
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(dao.as_ref(), &[]), update("solar/1")),
+      Err(ContractError::Unauthorized {})
+  ));
+
+  let has_role = |deps: cosmwasm_std::Deps, role, address: &String, token_id: Option<&str>| {
+      query(
+          deps,
+          mock_env(),
+          Cw1155QueryMsg::HasRole {
+              role,
+              address: address.clone(),
+              token_id: token_id.map(str::to_owned),
+          },
+      )
+  };
+  assert_eq!(
+      has_role(deps.as_ref(), Role::Minter, &issuer, Some("cookstove/3")),
+      to_binary(&HasRoleResponse { has_role: true })
+  );
+  assert_eq!(
+      has_role(deps.as_ref(), Role::Minter, &issuer, None),
+      to_binary(&HasRoleResponse { has_role: false })
+  );
+  assert_eq!(
+      has_role(deps.as_ref(), Role::Minter, &minter, None),
+      to_binary(&HasRoleResponse { has_role: true })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::RoleGrants {
+              role: Role::Minter,
+              start_after: None,
+              limit: None,
+          },
+      ),
+      to_binary(&RoleGrantsResponse {
+          grants: vec![
+              RoleGrant {
+                  address: issuer.clone(),
+                  token_id_prefix: Some("cookstove/".to_owned()),
+              },
+              RoleGrant {
+                  address: minter.clone(),
+                  token_id_prefix: None,
+              },
+          ]
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::RoleGrants {
+              role: Role::Minter,
+              start_after: Some((issuer.clone(), "cookstove/".to_owned())),
+              limit: None,
+          },
+      ),
+      to_binary(&RoleGrantsResponse {
+          grants: vec![RoleGrant {
+              address: minter.clone(),
+              token_id_prefix: None,
+          }]
+      })
+  );
+
+  // revoked roles are gone
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(admin.as_ref(), &[]),
+      Cw1155ExecuteMsg::RevokeRole {
+          role: Role::Minter,
+          address: issuer.clone(),
+          token_id_prefix: Some("cookstove/".to_owned()),
+      },
+  )
+  .unwrap();
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(issuer.as_ref(), &[]), mint("cookstove/3")),
+      Err(ContractError::Unauthorized {})
+  ));
+
+  // the admin is transferred in two steps
+  assert!(matches!(
+      execute(
+          deps.as_mut(),
+          env.clone(),
+          mock_info(dao.as_ref(), &[]),
+          Cw1155ExecuteMsg::TransferAdmin { new_admin: dao.clone() },
+      ),
+      Err(ContractError::Unauthorized {})
+  ));
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(admin.as_ref(), &[]),
+      Cw1155ExecuteMsg::TransferAdmin { new_admin: dao.clone() },
+  )
+  .unwrap();
+  assert_eq!(
+      query(deps.as_ref(), env.clone(), Cw1155QueryMsg::Admin {}),
+      to_binary(&AdminResponse {
+          admin: admin.clone(),
+          pending_admin: Some(dao.clone()),
+      })
+  );
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(issuer.as_ref(), &[]), Cw1155ExecuteMsg::AcceptAdmin {}),
+      Err(ContractError::Unauthorized {})
+  ));
+  execute(deps.as_mut(), env.clone(), mock_info(dao.as_ref(), &[]), Cw1155ExecuteMsg::AcceptAdmin {}).unwrap();
+  assert_eq!(
+      query(deps.as_ref(), env.clone(), Cw1155QueryMsg::Admin {}),
+      to_binary(&AdminResponse {
+          admin: dao.clone(),
+          pending_admin: None,
+      })
+  );
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(admin.as_ref(), &[]), grant(Role::Pauser, &admin, None)),
+      Err(ContractError::Unauthorized {})
+  ));
+  execute(deps.as_mut(), env, mock_info(dao.as_ref(), &[]), grant(Role::Pauser, &dao, None)).unwrap();
+}
+
+#[test]
+fn transfer_restrictions() {
+  let mut deps = mock_dependencies();
+  let token1 = "token1".to_owned();
+  let token2 = "token2".to_owned();
+  let minter = String::from("minter");
+  let user1 = String::from("user1");
+  let user2 = String::from("user2");
+  let user3 = String::from("user3");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  let mint = |to: &String, token_id: &String, supply_policy| Cw1155ExecuteMsg::Mint {
+      to: to.clone(),
+      token_id: token_id.clone(),
+      value: 10u64.into(),
+      uri: "uri".to_owned(),
+      max_supply: None,
+      supply_policy,
+      extension: None,
+      msg: None,
+  };
+  let send = |from: &String, to: &String, token_id: &String| Cw1155ExecuteMsg::SendFrom {
+      from: from.clone(),
+      to: to.clone(),
+      token_id: token_id.clone(),
+      value: 1u64.into(),
+      msg: None,
+  };
+
+  // only minters of the token set restrictions
+  let soulbound = Cw1155ExecuteMsg::SetTransferRestrictions {
+      token_id: token2.clone(),
+      restrictions: TransferRestrictions {
+          soulbound: true,
+          allow_list_only: false,
+      },
+  };
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), soulbound.clone()),
+      Err(ContractError::Unauthorized {})
+  ));
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), soulbound).unwrap();
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::TransferRestrictions {
+              token_id: token2.clone(),
+          },
+      ),
+      to_binary(&TransferRestrictions {
+          soulbound: true,
+          allow_list_only: false,
+      })
+  );
+
+  // soulbound tokens are minted, but not moved
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint(&user1, &token2, None)).unwrap();
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), send(&user1, &user2, &token2)),
+      Err(ContractError::Soulbound { .. })
+  ));
+
+  // allow list
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      mint(&user1, &token1, Some(SupplyPolicy::Open)),
+  )
+  .unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::SetTransferRestrictions {
+          token_id: token1.clone(),
+          restrictions: TransferRestrictions {
+              soulbound: false,
+              allow_list_only: true,
+          },
+      },
+  )
+  .unwrap();
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint(&user1, &token1, None)),
+      Err(ContractError::RestrictedHolder { .. })
+  ));
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::UpdateHolderList {
+          token_id: token1.clone(),
+          list: HolderList::Allow,
+          add: vec![user1.clone(), user2.clone(), user3.clone()],
+          remove: vec![user3.clone()],
+      },
+  )
+  .unwrap();
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env.clone(),
+          Cw1155QueryMsg::HolderList {
+              token_id: token1.clone(),
+              list: HolderList::Allow,
+              start_after: None,
+              limit: None,
+          },
+      ),
+      to_binary(&HolderListResponse {
+          holders: vec![user1.clone(), user2.clone()]
+      })
+  );
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint(&user1, &token1, None)).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), send(&user1, &user2, &token1)).unwrap();
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), send(&user1, &user3, &token1)),
+      Err(ContractError::RestrictedHolder { .. })
+  ));
+
+  // deny list
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::UpdateHolderList {
+          token_id: token1.clone(),
+          list: HolderList::Deny,
+          add: vec![user2.clone()],
+          remove: vec![],
+      },
+  )
+  .unwrap();
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), send(&user1, &user2, &token1)),
+      Err(ContractError::RestrictedHolder { .. })
+  ));
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user2.as_ref(), &[]), send(&user2, &user1, &token1)),
+      Err(ContractError::RestrictedHolder { .. })
+  ));
+
+  // pause
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), Cw1155ExecuteMsg::Pause {}),
+      Err(ContractError::Unauthorized {})
+  ));
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::GrantRole {
+          role: Role::Pauser,
+          address: minter.clone(),
+          token_id_prefix: None,
+      },
+  )
+  .unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), Cw1155ExecuteMsg::Pause {}).unwrap();
+  assert_eq!(
+      query(deps.as_ref(), env.clone(), Cw1155QueryMsg::Paused {}),
+      to_binary(&PausedResponse { paused: true })
+  );
+  assert!(matches!(
+      execute(deps.as_mut(), env.clone(), mock_info(user1.as_ref(), &[]), send(&user1, &user1, &token1)),
+      Err(ContractError::Paused {})
+  ));
+  // minting still works
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint(&user1, &token1, None)).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), Cw1155ExecuteMsg::Unpause {}).unwrap();
+  execute(deps.as_mut(), env, mock_info(user1.as_ref(), &[]), send(&user1, &user1, &token1)).unwrap();
+}
+
+#[test]
+fn migrate_legacy_collection() {
+  let mut deps = mock_dependencies();
+  let minter = String::from("minter");
+
+  // a collection instantiated before roles and contract info
+  set_contract_version(deps.as_mut().storage, "crates.io:cw1155-base", "0.15.0").unwrap();
+  LEGACY_MINTER.save(deps.as_mut().storage, &Addr::unchecked(&minter)).unwrap();
+  // and before the holder index
+  for (owner, balance) in [("user1", 5u64), ("user2", 0), ("user3", 2)] {
+      BALANCES.save(deps.as_mut().storage, (&Addr::unchecked(owner), "token1"), &balance.into()).unwrap();
+  }
+
+  migrate(
+      deps.as_mut(),
+      mock_env(),
+      MigrateMsg {
+          name: Some("Impact credits".to_owned()),
+          symbol: Some("CREDIT".to_owned()),
+      },
+  )
+  .unwrap();
+  assert_eq!(get_contract_version(deps.as_ref().storage).unwrap().version, env!("CARGO_PKG_VERSION"));
+  assert_eq!(LEGACY_MINTER.may_load(deps.as_ref().storage).unwrap(), None);
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          mock_env(),
+          Cw1155QueryMsg::Owners {
+              token_id: "token1".to_owned(),
+              start_after: None,
+              limit: None,
+          },
+      ),
+      to_binary(&OwnersResponse {
+          owners: vec![
+              TokenOwner {
+                  owner: "user1".to_owned(),
+                  balance: 5u64.into(),
+              },
+              TokenOwner {
+                  owner: "user3".to_owned(),
+                  balance: 2u64.into(),
+              },
+          ]
+      })
+  );
+  assert_eq!(
+      query(deps.as_ref(), mock_env(), Cw1155QueryMsg::ContractInfo {}),
+      to_binary(&ContractInfoResponse {
+          name: "Impact credits".to_owned(),
+          symbol: "CREDIT".to_owned(),
+      })
+  );
+  assert_eq!(
+      query(deps.as_ref(), mock_env(), Cw1155QueryMsg::Admin {}),
+      to_binary(&AdminResponse {
+          admin: minter.clone(),
+          pending_admin: None,
+      })
+  );
+  for role in [Role::Minter, Role::MetadataEditor] {
+      assert_eq!(
+          query(
+              deps.as_ref(),
+              mock_env(),
+              Cw1155QueryMsg::HasRole {
+                  role,
+                  address: minter.clone(),
+                  token_id: None,
+              },
+          ),
+          to_binary(&HasRoleResponse { has_role: true })
+      );
+  }
+
+  // the supply was rebuilt from the balances, so tokens minted before can be burned
+  execute(
+      deps.as_mut(),
+      mock_env(),
+      mock_info("user1", &[]),
+      Cw1155ExecuteMsg::Burn {
+          from: "user1".to_owned(),
+          token_id: "token1".to_owned(),
+          value: 3u64.into(),
+      },
+  )
+  .unwrap();
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          mock_env(),
+          Cw1155QueryMsg::Supply {
+              token_id: "token1".to_owned(),
+          },
+      ),
+      to_binary(&SupplyResponse {
+          total_supply: 4u64.into(),
+          max_supply: None,
+          supply_policy: SupplyPolicy::Fixed,
+      })
+  );
+
+  // migrating again keeps the contract info
+  migrate(
+      deps.as_mut(),
+      mock_env(),
+      MigrateMsg {
+          name: None,
+          symbol: Some("CRD".to_owned()),
+      },
+  )
+  .unwrap();
+  // and doesn't rebuild the supplies again
+  assert_eq!(TOTAL_SUPPLY.load(&deps.storage, "token1").unwrap(), Uint128::new(4));
+  assert_eq!(
+      query(deps.as_ref(), mock_env(), Cw1155QueryMsg::ContractInfo {}),
+      to_binary(&ContractInfoResponse {
+          name: "Impact credits".to_owned(),
+          symbol: "CRD".to_owned(),
+      })
+  );
+
+  // cannot migrate from a newer version nor another contract
+  let no_info = || MigrateMsg { name: None, symbol: None };
+  set_contract_version(deps.as_mut().storage, "crates.io:cw1155-base", "99.0.0").unwrap();
+  assert!(matches!(migrate(deps.as_mut(), mock_env(), no_info()), Err(ContractError::Std(_))));
+  set_contract_version(deps.as_mut().storage, "crates.io:cw20-base", "0.1.0").unwrap();
+  assert!(matches!(migrate(deps.as_mut(), mock_env(), no_info()), Err(ContractError::Std(_))));
+}
+
+#[test]
+fn migrate_balances_in_chunks() {
+  let mut deps = mock_dependencies();
+  // the last version without the holder index
+  set_contract_version(deps.as_mut().storage, "crates.io:cw1155-base", "0.16.0").unwrap();
+  LEGACY_MINTER.save(deps.as_mut().storage, &Addr::unchecked("minter")).unwrap();
+  // 150 balances, more than migrate reads at once
+  for user in 0..50 {
+      let owner = Addr::unchecked(format!("user{:02}", user));
+      for token_id in ["token1", "token2", "token3"] {
+          BALANCES.save(deps.as_mut().storage, (&owner, token_id), &2u64.into()).unwrap();
+      }
+  }
+
+  let rsp = migrate(deps.as_mut(), mock_env(), MigrateMsg { name: None, symbol: None }).unwrap();
+  assert_eq!(rsp, Response::new().add_attribute("balances_migrated", "false"));
+
+  // nothing else is accepted until all balances are migrated
+  let burn = || Cw1155ExecuteMsg::Burn {
+      from: "user00".to_owned(),
+      token_id: "token3".to_owned(),
+      value: 1u64.into(),
+  };
+  assert!(matches!(
+      execute(deps.as_mut(), mock_env(), mock_info("user00", &[]), burn()),
+      Err(ContractError::MigrationPending {})
+  ));
+
+  let migrate_balances = Cw1155ExecuteMsg::MigrateBalances { limit: Some(30) };
+  let rsp = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), migrate_balances.clone()).unwrap();
+  assert_eq!(rsp.attributes[1], ("done", "false"));
+  let rsp = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), migrate_balances).unwrap();
+  assert_eq!(rsp.attributes[1], ("done", "true"));
+
+  for token_id in ["token1", "token2", "token3"] {
+      assert_eq!(
+          TOKEN_OWNERS
+              .prefix(token_id)
+              .keys(&deps.storage, None, None, Order::Ascending)
+              .count(),
+          50
+      );
+      assert_eq!(TOTAL_SUPPLY.load(&deps.storage, token_id).unwrap(), Uint128::new(100));
+  }
+  execute(deps.as_mut(), mock_env(), mock_info("user00", &[]), burn()).unwrap();
+  assert_eq!(TOTAL_SUPPLY.load(&deps.storage, "token3").unwrap(), Uint128::new(99));
+}
+
+#[test]
+fn royalties() {
+  type MetadataExtension = Option<Metadata>;
+
+  let mut deps = mock_dependencies();
+  let minter = String::from("minter");
+  let creator = String::from("creator");
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  let mint = |token_id: &str, royalty_percentage| Cw1155ExecuteMsg::<MetadataExtension>::Mint {
+      to: creator.clone(),
+      token_id: token_id.to_owned(),
+      value: 1u64.into(),
+      uri: "uri".to_owned(),
+      max_supply: None,
+      supply_policy: None,
+      extension: Some(Metadata {
+          royalty_percentage,
+          royalty_payment_address: Some(creator.clone()),
+          ..Metadata::default()
+      }),
+      msg: None,
+  };
+  assert!(matches!(
+      execute_with_extension(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint("token1", Some(101))),
+      Err(ContractError::InvalidRoyaltyPercentage {})
+  ));
+  execute_with_extension(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint("token2", Some(10))).unwrap();
+  execute_with_extension(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint("token3", None)).unwrap();
+
+  // royalty fields of the wrong type aren't read as no royalties
+  #[derive(serde::Serialize, serde::Deserialize, Default)]
+  struct Malformed<P, A> {
+      royalty_percentage: P,
+      royalty_payment_address: A,
+  }
+  fn mint_malformed<P, A>(royalty_percentage: P, royalty_payment_address: A) -> Cw1155ExecuteMsg<Option<Malformed<P, A>>> {
+      Cw1155ExecuteMsg::Mint {
+          to: "creator".to_owned(),
+          token_id: "malformed".to_owned(),
+          value: 1u64.into(),
+          uri: "uri".to_owned(),
+          max_supply: None,
+          supply_policy: None,
+          extension: Some(Malformed {
+              royalty_percentage,
+              royalty_payment_address,
+          }),
+          msg: None,
+      }
+  }
+  assert!(matches!(
+      execute_with_extension(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint_malformed("5".to_owned(), "creator".to_owned())),
+      Err(ContractError::InvalidRoyalty {})
+  ));
+  assert!(matches!(
+      execute_with_extension(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint_malformed(-1, "creator".to_owned())),
+      Err(ContractError::InvalidRoyalty {})
+  ));
+  assert!(matches!(
+      execute_with_extension(deps.as_mut(), env.clone(), mock_info(minter.as_ref(), &[]), mint_malformed(5, 7)),
+      Err(ContractError::InvalidRoyalty {})
+  ));
+
+  let royalty_info = |deps: cosmwasm_std::Deps, token_id: &str| {
+      query_with_extension::<MetadataExtension>(
+          deps,
+          mock_env(),
+          Cw1155QueryMsg::RoyaltyInfo {
+              token_id: token_id.to_owned(),
+              sale_price: 1005u64.into(),
+          },
+      )
+  };
+  // rounded down
+  assert_eq!(
+      royalty_info(deps.as_ref(), "token2"),
+      to_binary(&RoyaltiesInfoResponse {
+          address: creator.clone(),
+          royalty_amount: 100u64.into(),
+      })
+  );
+  assert_eq!(
+      royalty_info(deps.as_ref(), "token3"),
+      to_binary(&RoyaltiesInfoResponse {
+          address: creator.clone(),
+          royalty_amount: Uint128::zero(),
+      })
+  );
+  assert!(royalty_info(deps.as_ref(), "unknown").is_err());
+  assert_eq!(
+      query_with_extension::<MetadataExtension>(deps.as_ref(), env, Cw1155QueryMsg::CheckRoyalties {}),
+      to_binary(&CheckRoyaltiesResponse {
+          royalty_payments: true
+      })
+  );
+}
+
+#[test]
+fn owners() {
+  let mut deps = mock_dependencies();
+  let minter = String::from("minter");
+  let users = ["user1", "user2", "user3"];
+  let token1 = "token1".to_owned();
+
+  let env = mock_env();
+  let msg = InstantiateMsg {
+      name: "Impact credits".to_owned(),
+      symbol: "CREDIT".to_owned(),
+      minter: minter.clone(),
+      admin: None,
+  };
+  instantiate(deps.as_mut(), env.clone(), mock_info("operator", &[]), msg).unwrap();
+
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(minter.as_ref(), &[]),
+      Cw1155ExecuteMsg::Mint {
+          to: users[0].to_owned(),
+          token_id: token1.clone(),
+          value: 10u64.into(),
+          uri: "uri".to_owned(),
+          max_supply: None,
+          supply_policy: None,
+          extension: None,
+          msg: None,
+      },
+  )
+  .unwrap();
+  let send = |from: &str, to: &str, value: u64| Cw1155ExecuteMsg::SendFrom {
+      from: from.to_owned(),
+      to: to.to_owned(),
+      token_id: token1.clone(),
+      value: value.into(),
+      msg: None,
+  };
+  execute(deps.as_mut(), env.clone(), mock_info(users[0], &[]), send(users[0], users[2], 3)).unwrap();
+  execute(deps.as_mut(), env.clone(), mock_info(users[0], &[]), send(users[0], users[1], 2)).unwrap();
+
+  let owners = |deps: cosmwasm_std::Deps, start_after: Option<&str>, limit: Option<u32>| {
+      query(
+          deps,
+          mock_env(),
+          Cw1155QueryMsg::Owners {
+              token_id: token1.clone(),
+              start_after: start_after.map(String::from),
+              limit,
+          },
+      )
+  };
+  let owner = |owner: &str, balance: u64| TokenOwner {
+      owner: owner.to_owned(),
+      balance: balance.into(),
+  };
+  assert_eq!(
+      owners(deps.as_ref(), None, None),
+      to_binary(&OwnersResponse {
+          owners: vec![owner(users[0], 5), owner(users[1], 2), owner(users[2], 3)]
+      })
+  );
+  assert_eq!(
+      owners(deps.as_ref(), Some(users[0]), Some(1)),
+      to_binary(&OwnersResponse {
+          owners: vec![owner(users[1], 2)]
+      })
+  );
+
+  // accounts whose balance goes back to 0 are no longer listed
+  execute(deps.as_mut(), env.clone(), mock_info(users[0], &[]), send(users[0], users[2], 5)).unwrap();
+  execute(
+      deps.as_mut(),
+      env.clone(),
+      mock_info(users[1], &[]),
+      Cw1155ExecuteMsg::Burn {
+          from: users[1].to_owned(),
+          token_id: token1.clone(),
+          value: 2u64.into(),
+      },
+  )
+  .unwrap();
+  assert_eq!(
+      owners(deps.as_ref(), None, None),
+      to_binary(&OwnersResponse {
+          owners: vec![owner(users[2], 8)]
+      })
+  );
+  assert_eq!(
+      query(
+          deps.as_ref(),
+          env,
+          Cw1155QueryMsg::Owners {
+              token_id: "unknown".to_owned(),
+              start_after: None,
+              limit: None,
+          },
+      ),
+      to_binary(&OwnersResponse { owners: vec![] })
+  );
+}
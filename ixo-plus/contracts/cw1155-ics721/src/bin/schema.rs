@@ -0,0 +1,12 @@
+use cosmwasm_schema::write_api;
+
+use cw1155_ics721::msg::{ExecuteMsg, InitMsg, MigrateMsg, QueryMsg};
+
+fn main() {
+    write_api! {
+        instantiate: InitMsg,
+        execute: ExecuteMsg,
+        query: QueryMsg,
+        migrate: MigrateMsg,
+    }
+}
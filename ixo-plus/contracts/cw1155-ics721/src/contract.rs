@@ -0,0 +1,473 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_binary, to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, IbcMsg, IbcQuery, MessageInfo,
+    Order, PortIdResponse, Response, StdResult, Uint128,
+};
+use serde::Deserialize;
+
+use cw1155::{Cw1155BatchReceiveMsg, Cw1155ExecuteMsg, Cw1155QueryMsg, Cw1155ReceiveMsg, TokenId};
+use cw2::set_contract_version;
+use cw_utils::{ensure_from_older_version, nonpayable};
+
+use crate::error::ContractError;
+use crate::ibc::{cw1155_batch, cw1155_msg, Ics721Packet};
+use crate::msg::{
+    AllowedResponse, ChannelBalance, ChannelResponse, ConfigResponse, ExecuteMsg, InitMsg,
+    ListChannelsResponse, MigrateMsg, PortResponse, QueryMsg, TransferMsg,
+};
+use crate::state::{
+    increase_channel_balance, Config, VoucherInfo, ADMIN, ALLOW_LIST, CHANNEL_INFO, CHANNEL_STATE,
+    CONFIG, VOUCHERS,
+};
+
+// version info for migration info
+const CONTRACT_NAME: &str = "crates.io:cw1155-ics721";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The part of the cw1155 `TokenInfoResponse` we send along, whatever the extension of the contract
+#[derive(Deserialize)]
+struct TokenUrl {
+    url: String,
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    mut deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    msg: InitMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let cfg = Config {
+        default_timeout: msg.default_timeout,
+        voucher_contract: deps.api.addr_validate(&msg.voucher_contract)?,
+    };
+    CONFIG.save(deps.storage, &cfg)?;
+
+    let admin = deps.api.addr_validate(&msg.gov_contract)?;
+    ADMIN.set(deps.branch(), Some(admin))?;
+
+    // add all allows
+    for allowed in msg.allowlist {
+        let contract = deps.api.addr_validate(&allowed)?;
+        ALLOW_LIST.save(deps.storage, &contract, &Empty {})?;
+    }
+    Ok(Response::default())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::BatchReceive(msg) => execute_batch_receive(deps, env, info, msg),
+        ExecuteMsg::Allow { contract } => execute_allow(deps, info, contract),
+        ExecuteMsg::UpdateAdmin { admin } => {
+            let admin = deps.api.addr_validate(&admin)?;
+            Ok(ADMIN.execute_update_admin(deps, info, Some(admin))?)
+        }
+    }
+}
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw1155ReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let msg: TransferMsg = from_binary(&wrapper.msg)?;
+    let sender = wrapper.from.ok_or(ContractError::NoSender {})?;
+    let sender = deps.api.addr_validate(&sender)?;
+    let tokens = vec![(wrapper.token_id, wrapper.amount)];
+    execute_transfer(deps, env, msg, info.sender, tokens, sender)
+}
+
+pub fn execute_batch_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw1155BatchReceiveMsg,
+) -> Result<Response, ContractError> {
+    nonpayable(&info)?;
+
+    let msg: TransferMsg = from_binary(&wrapper.msg)?;
+    let sender = wrapper.from.ok_or(ContractError::NoSender {})?;
+    let sender = deps.api.addr_validate(&sender)?;
+    let tokens = wrapper
+        .batch
+        .into_iter()
+        .map(|(token_id, amount, _)| (token_id, amount))
+        .collect();
+    execute_transfer(deps, env, msg, info.sender, tokens, sender)
+}
+
+/// Sends the tokens of `contract` this contract just received over the channel.
+/// Vouchers going back through the channel they arrived on are burned, as the remote chain holds
+/// the original tokens. All other tokens are escrowed here until they come back.
+pub fn execute_transfer(
+    deps: DepsMut,
+    env: Env,
+    msg: TransferMsg,
+    contract: Addr,
+    tokens: Vec<(TokenId, Uint128)>,
+    sender: Addr,
+) -> Result<Response, ContractError> {
+    if tokens.is_empty() || tokens.iter().any(|(_, amount)| amount.is_zero()) {
+        return Err(ContractError::NoFunds {});
+    }
+    // ensure the requested channel is registered
+    if !CHANNEL_INFO.has(deps.storage, &msg.channel) {
+        return Err(ContractError::NoSuchChannel { id: msg.channel });
+    }
+    let config = CONFIG.load(deps.storage)?;
+    let (local_ids, amounts): (Vec<TokenId>, Vec<Uint128>) = tokens.into_iter().unzip();
+
+    let (class_id, token_ids, burn) = if contract == config.voucher_contract {
+        let vouchers = local_ids
+            .iter()
+            .map(|token_id| {
+                VOUCHERS.may_load(deps.storage, token_id)?.ok_or_else(|| {
+                    ContractError::UnknownVoucher {
+                        token_id: token_id.clone(),
+                    }
+                })
+            })
+            .collect::<Result<Vec<VoucherInfo>, _>>()?;
+        let class_id = vouchers[0].class_id.clone();
+        if vouchers.iter().any(|voucher| voucher.class_id != class_id) {
+            return Err(ContractError::MixedClasses {});
+        }
+        let token_ids = vouchers
+            .into_iter()
+            .map(|voucher| voucher.token_id)
+            .collect();
+
+        // the class of a voucher looks like "port/channel/remote_class", and is sent as is so
+        // that the remote chain recognizes its own tokens by the prefix
+        match class_id.splitn(3, '/').collect::<Vec<_>>()[..] {
+            [_, channel, _] if channel == msg.channel => {
+                let burn = Cw1155ExecuteMsg::BatchBurn {
+                    from: env.contract.address.to_string(),
+                    batch: cw1155_batch(local_ids.clone(), &amounts),
+                };
+                let burn = cw1155_msg(&contract, &burn)?;
+                (class_id, token_ids, Some(burn))
+            }
+            _ => (class_id, token_ids, None),
+        }
+    } else {
+        if !ALLOW_LIST.has(deps.storage, &contract) {
+            return Err(ContractError::NotOnAllowList);
+        }
+        (contract.to_string(), local_ids.clone(), None)
+    };
+
+    // Update the balance now (optimistically) like ibctransfer modules.
+    // In on_packet_failure (ack with error message or a timeout), we reduce the balance appropriately.
+    if burn.is_none() {
+        for (token_id, amount) in token_ids.iter().zip(amounts.iter()) {
+            increase_channel_balance(deps.storage, &msg.channel, &class_id, token_id, *amount)?;
+        }
+    }
+
+    let token_uris = local_ids
+        .into_iter()
+        .map(|token_id| {
+            let query = Cw1155QueryMsg::TokenInfo { token_id };
+            let info: TokenUrl = deps.querier.query_wasm_smart(&contract, &query)?;
+            Ok(info.url)
+        })
+        .collect::<StdResult<_>>()?;
+
+    // delta from user is in seconds
+    let timeout_delta = match msg.timeout {
+        Some(t) => t,
+        None => config.default_timeout,
+    };
+    // timeout is in nanoseconds
+    let timeout = env.block.time.plus_seconds(timeout_delta);
+
+    // build ics721 packet
+    let packet = Ics721Packet {
+        class_id,
+        token_ids,
+        amounts,
+        token_uris,
+        sender: sender.to_string(),
+        receiver: msg.remote_address,
+    };
+    packet.validate()?;
+
+    // prepare ibc message
+    let send = IbcMsg::SendPacket {
+        channel_id: msg.channel,
+        data: to_binary(&packet)?,
+        timeout: timeout.into(),
+    };
+
+    // send response
+    let res = Response::new()
+        .add_messages(burn)
+        .add_message(send)
+        .add_attribute("action", "transfer")
+        .add_attribute("sender", &packet.sender)
+        .add_attribute("receiver", &packet.receiver)
+        .add_attribute("class_id", &packet.class_id)
+        .add_attribute("token_ids", packet.token_ids.join(","));
+    Ok(res)
+}
+
+/// The gov contract can allow new contracts. It cannot block them to avoid forcible sticking
+/// tokens in the channel.
+pub fn execute_allow(
+    deps: DepsMut,
+    info: MessageInfo,
+    contract: String,
+) -> Result<Response, ContractError> {
+    ADMIN.assert_admin(deps.as_ref(), &info.sender)?;
+
+    let addr = deps.api.addr_validate(&contract)?;
+    ALLOW_LIST.save(deps.storage, &addr, &Empty {})?;
+
+    let res = Response::new()
+        .add_attribute("action", "allow")
+        .add_attribute("contract", contract);
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Port {} => to_binary(&query_port(deps)?),
+        QueryMsg::ListChannels {} => to_binary(&query_list(deps)?),
+        QueryMsg::Channel { id } => to_binary(&query_channel(deps, id)?),
+        QueryMsg::Config {} => to_binary(&query_config(deps)?),
+        QueryMsg::Allowed { contract } => to_binary(&query_allowed(deps, contract)?),
+        QueryMsg::Voucher { token_id } => to_binary(&VOUCHERS.load(deps.storage, &token_id)?),
+        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+    }
+}
+
+fn query_port(deps: Deps) -> StdResult<PortResponse> {
+    let query = IbcQuery::PortId {}.into();
+    let PortIdResponse { port_id } = deps.querier.query(&query)?;
+    Ok(PortResponse { port_id })
+}
+
+fn query_list(deps: Deps) -> StdResult<ListChannelsResponse> {
+    let channels = CHANNEL_INFO
+        .range_raw(deps.storage, None, None, Order::Ascending)
+        .map(|r| r.map(|(_, v)| v))
+        .collect::<StdResult<_>>()?;
+    Ok(ListChannelsResponse { channels })
+}
+
+// make public for ibc tests
+pub fn query_channel(deps: Deps, id: String) -> StdResult<ChannelResponse> {
+    let info = CHANNEL_INFO.load(deps.storage, &id)?;
+    let balances = CHANNEL_STATE
+        .sub_prefix(&id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|r| {
+            r.map(|((class_id, token_id), state)| ChannelBalance {
+                class_id,
+                token_id,
+                outstanding: state.outstanding,
+                total_sent: state.total_sent,
+            })
+        })
+        .collect::<StdResult<_>>()?;
+
+    Ok(ChannelResponse { info, balances })
+}
+
+fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
+    let cfg = CONFIG.load(deps.storage)?;
+    let admin = ADMIN.get(deps)?.unwrap_or_else(|| Addr::unchecked(""));
+    let res = ConfigResponse {
+        default_timeout: cfg.default_timeout,
+        voucher_contract: cfg.voucher_contract.into(),
+        gov_contract: admin.into(),
+    };
+    Ok(res)
+}
+
+fn query_allowed(deps: Deps, contract: String) -> StdResult<AllowedResponse> {
+    let addr = deps.api.addr_validate(&contract)?;
+    let is_allowed = ALLOW_LIST.has(deps.storage, &addr);
+    Ok(AllowedResponse { is_allowed })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::test_helpers::*;
+
+    use cosmwasm_std::testing::{mock_env, mock_info};
+    use cosmwasm_std::{coins, IbcChannelCloseMsg};
+    use cw_controllers::AdminError;
+
+    use crate::ibc::ibc_channel_close;
+
+    #[test]
+    fn setup_and_query() {
+        let deps = setup(&["channel-3", "channel-7"], &["collection"]);
+
+        let raw_list = query(deps.as_ref(), mock_env(), QueryMsg::ListChannels {}).unwrap();
+        let list_res: ListChannelsResponse = from_binary(&raw_list).unwrap();
+        assert_eq!(2, list_res.channels.len());
+        assert_eq!("channel-3", list_res.channels[0].id);
+
+        let chan_res = query_channel(deps.as_ref(), "channel-3".to_string()).unwrap();
+        assert_eq!("channel-35", chan_res.info.counterparty_endpoint.channel_id);
+        assert!(chan_res.balances.is_empty());
+
+        let err = query_channel(deps.as_ref(), "channel-10".to_string()).unwrap_err();
+        assert!(matches!(err, cosmwasm_std::StdError::NotFound { .. }));
+
+        let raw = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+        let config: ConfigResponse = from_binary(&raw).unwrap();
+        assert_eq!(
+            config,
+            ConfigResponse {
+                default_timeout: DEFAULT_TIMEOUT,
+                voucher_contract: VOUCHER_CONTRACT.to_string(),
+                gov_contract: "gov".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn allow_contracts() {
+        let mut deps = setup(&["channel-3"], &[]);
+
+        let allowed = |deps: Deps, contract: &str| {
+            query_allowed(deps, contract.to_string())
+                .unwrap()
+                .is_allowed
+        };
+        assert!(!allowed(deps.as_ref(), "collection"));
+
+        // only the gov contract can allow more
+        let msg = ExecuteMsg::Allow {
+            contract: "collection".to_string(),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("foobar", &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Admin(AdminError::NotAdmin {}));
+        execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), msg).unwrap();
+        assert!(allowed(deps.as_ref(), "collection"));
+    }
+
+    #[test]
+    fn transfer_checks() {
+        let mut deps = setup(&["channel-3"], &["collection"]);
+        let transfer = to_binary(&TransferMsg {
+            channel: "channel-3".to_string(),
+            remote_address: "remote-rcpt".to_string(),
+            timeout: Some(7777),
+        })
+        .unwrap();
+        let receive = |from: Option<&str>, amount: u128, msg: &Binary| {
+            ExecuteMsg::Receive(Cw1155ReceiveMsg {
+                operator: "alice".to_string(),
+                from: from.map(String::from),
+                token_id: "a".to_string(),
+                amount: amount.into(),
+                msg: msg.clone(),
+            })
+        };
+        let info = mock_info("collection", &[]);
+
+        // tokens minted straight to the contract have no one to be refunded to
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            receive(None, 1, &transfer),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoSender {});
+
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            receive(Some("alice"), 0, &transfer),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NoFunds {});
+
+        let unknown_channel = to_binary(&TransferMsg {
+            channel: "channel-4".to_string(),
+            remote_address: "remote-rcpt".to_string(),
+            timeout: None,
+        })
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            info.clone(),
+            receive(Some("alice"), 1, &unknown_channel),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::NoSuchChannel {
+                id: "channel-4".to_string()
+            }
+        );
+
+        // no funds can be attached
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("collection", &coins(1, "ucosm")),
+            receive(Some("alice"), 1, &transfer),
+        )
+        .unwrap_err();
+        assert!(matches!(err, ContractError::Payment(_)));
+
+        // the timeout is the one asked for
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            info,
+            receive(Some("alice"), 1, &transfer),
+        )
+        .unwrap();
+        match &res.messages[0].msg {
+            cosmwasm_std::CosmosMsg::Ibc(IbcMsg::SendPacket { timeout, .. }) => {
+                let expected = mock_env().block.time.plus_seconds(7777);
+                assert_eq!(timeout.timestamp(), Some(expected));
+            }
+            msg => panic!("Unexpected message: {:?}", msg),
+        }
+
+        // we don't close channels ourselves
+        let close = IbcChannelCloseMsg::CloseInit {
+            channel: mock_channel("channel-3"),
+        };
+        let err = ibc_channel_close(deps.as_mut(), mock_env(), close).unwrap_err();
+        assert_eq!(err, ContractError::CannotCloseChannel {});
+    }
+}
@@ -0,0 +1,54 @@
+use thiserror::Error;
+
+use cosmwasm_std::StdError;
+use cw_controllers::AdminError;
+use cw_utils::PaymentError;
+
+/// Never is a placeholder to ensure we don't return any errors
+#[derive(Error, Debug)]
+pub enum Never {}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("Channel doesn't exist: {id}")]
+    NoSuchChannel { id: String },
+
+    #[error("Didn't send any tokens")]
+    NoFunds {},
+
+    #[error("Only supports channel with ibc version cw1155-ics721-1, got {version}")]
+    InvalidIbcVersion { version: String },
+
+    #[error("Only supports unordered channel")]
+    OnlyOrderedChannel {},
+
+    #[error("Insufficient funds to redeem voucher on channel")]
+    InsufficientFunds {},
+
+    #[error("Packet must have as many amounts and token uris as token ids")]
+    InvalidPacket {},
+
+    #[error("Tokens minted to this contract can't be transferred, as there is no one to refund")]
+    NoSender {},
+
+    #[error("All tokens of a transfer must belong to the same class")]
+    MixedClasses {},
+
+    #[error("Not a voucher minted by this contract: {token_id}")]
+    UnknownVoucher { token_id: String },
+
+    #[error("The channel can only be closed by the counterparty")]
+    CannotCloseChannel {},
+
+    #[error("You can only send cw1155 tokens that have been explicitly allowed by governance")]
+    NotOnAllowList,
+}
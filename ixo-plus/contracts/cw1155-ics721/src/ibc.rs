@@ -0,0 +1,897 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    attr, entry_point, from_binary, to_binary, Addr, Api, Binary, CosmosMsg, DepsMut, Empty, Env,
+    IbcBasicResponse, IbcChannel, IbcChannelCloseMsg, IbcChannelConnectMsg, IbcChannelOpenMsg,
+    IbcOrder, IbcPacket, IbcPacketAckMsg, IbcPacketReceiveMsg, IbcPacketTimeoutMsg,
+    IbcReceiveResponse, StdResult, Storage, Uint128, WasmMsg,
+};
+use cw1155::{Cw1155ExecuteMsg, SupplyPolicy, TokenId};
+
+use crate::error::{ContractError, Never};
+use crate::state::{
+    reduce_channel_balance, ChannelInfo, Config, VoucherInfo, CHANNEL_INFO, CONFIG, VOUCHERS,
+};
+
+pub const ICS721_VERSION: &str = "cw1155-ics721-1";
+pub const ICS721_ORDERING: IbcOrder = IbcOrder::Unordered;
+
+/// The format for sending a batch of cw1155 tokens of one class.
+/// It follows the ics721 packet, with an amount for every token id.
+/// This is compatible with the JSON serialization
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Ics721Packet {
+    /// the class of the tokens, i.e. the cw1155 contract on their origin chain,
+    /// prefixed with the port and channel of every hop they went through
+    pub class_id: String,
+    pub token_ids: Vec<TokenId>,
+    /// the amount transferred of every token id
+    pub amounts: Vec<Uint128>,
+    /// the url of every token id
+    pub token_uris: Vec<String>,
+    /// the sender address
+    pub sender: String,
+    /// the recipient address on the destination chain
+    pub receiver: String,
+}
+
+impl Ics721Packet {
+    pub fn validate(&self) -> Result<(), ContractError> {
+        if self.token_ids.is_empty() || self.amounts.iter().any(Uint128::is_zero) {
+            return Err(ContractError::NoFunds {});
+        }
+        if self.amounts.len() != self.token_ids.len()
+            || self.token_uris.len() != self.token_ids.len()
+        {
+            return Err(ContractError::InvalidPacket {});
+        }
+        Ok(())
+    }
+
+    fn tokens(&self) -> impl Iterator<Item = (&TokenId, &Uint128)> {
+        self.token_ids.iter().zip(self.amounts.iter())
+    }
+}
+
+/// This is a generic ICS acknowledgement format.
+/// Proto defined here: https://github.com/cosmos/cosmos-sdk/blob/v0.42.0/proto/ibc/core/channel/v1/channel.proto#L141-L147
+/// This is compatible with the JSON serialization
+#[cw_serde]
+pub enum Ics721Ack {
+    Result(Binary),
+    Error(String),
+}
+
+// create a serialized success message
+fn ack_success() -> Binary {
+    let res = Ics721Ack::Result(b"1".into());
+    to_binary(&res).unwrap()
+}
+
+// create a serialized error message
+fn ack_fail(err: String) -> Binary {
+    let res = Ics721Ack::Error(err);
+    to_binary(&res).unwrap()
+}
+
+/// The token id of the vouchers of `token_id`, for a remote `class_id` prefixed with the port and
+/// channel the tokens arrived on. The class is hashed like the ibc denoms of the bank module, so
+/// that the voucher id stays short however many hops the tokens made.
+pub fn voucher_token_id(class_id: &str, token_id: &str) -> String {
+    let hash = Sha256::digest(class_id.as_bytes());
+    let hex: String = hash.iter().map(|byte| format!("{:02X}", byte)).collect();
+    format!("ibc/{}/{}", hex, token_id)
+}
+
+/// The cw1155 contract holding the tokens of a local class, and their token ids on it.
+/// Classes that came from a remote chain carry the path they arrived by, their tokens are vouchers.
+/// Other classes are the address of a cw1155 contract of this chain.
+pub(crate) fn local_tokens(
+    api: &dyn Api,
+    config: &Config,
+    class_id: &str,
+    token_ids: &[TokenId],
+) -> StdResult<(Addr, Vec<TokenId>)> {
+    if class_id.contains('/') {
+        let vouchers = token_ids
+            .iter()
+            .map(|token_id| voucher_token_id(class_id, token_id))
+            .collect();
+        Ok((config.voucher_contract.clone(), vouchers))
+    } else {
+        Ok((api.addr_validate(class_id)?, token_ids.to_vec()))
+    }
+}
+
+pub(crate) fn cw1155_msg(
+    contract: &Addr,
+    msg: &Cw1155ExecuteMsg<Option<Empty>>,
+) -> StdResult<CosmosMsg> {
+    Ok(WasmMsg::Execute {
+        contract_addr: contract.to_string(),
+        msg: to_binary(msg)?,
+        funds: vec![],
+    }
+    .into())
+}
+
+/// The batch of a cw1155 message, the uris are not used
+pub(crate) fn cw1155_batch(
+    token_ids: Vec<TokenId>,
+    amounts: &[Uint128],
+) -> Vec<(TokenId, Uint128, String)> {
+    token_ids
+        .into_iter()
+        .zip(amounts.iter())
+        .map(|(token_id, amount)| (token_id, *amount, String::new()))
+        .collect()
+}
+
+// mint the vouchers of remote tokens, recording the ones we didn't mint before
+fn mint_vouchers(
+    storage: &mut dyn Storage,
+    config: &Config,
+    class_id: &str,
+    packet: &Ics721Packet,
+    to: &str,
+) -> Result<Vec<CosmosMsg>, ContractError> {
+    let mut messages = vec![];
+    for ((token_id, amount), uri) in packet.tokens().zip(packet.token_uris.iter()) {
+        let voucher = voucher_token_id(class_id, token_id);
+        // the supply of a voucher stays open, so that more tokens can arrive later
+        let supply_policy = if VOUCHERS.has(storage, &voucher) {
+            None
+        } else {
+            let info = VoucherInfo {
+                class_id: class_id.to_string(),
+                token_id: token_id.clone(),
+            };
+            VOUCHERS.save(storage, &voucher, &info)?;
+            Some(SupplyPolicy::Open)
+        };
+        let mint = Cw1155ExecuteMsg::Mint {
+            to: to.to_string(),
+            token_id: voucher,
+            value: *amount,
+            uri: uri.clone(),
+            max_supply: None,
+            supply_policy,
+            extension: None,
+            msg: None,
+        };
+        messages.push(cw1155_msg(&config.voucher_contract, &mint)?);
+    }
+    Ok(messages)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+/// enforces ordering and versioning constraints
+pub fn ibc_channel_open(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelOpenMsg,
+) -> Result<(), ContractError> {
+    enforce_order_and_version(msg.channel(), msg.counterparty_version())?;
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+/// record the channel in CHANNEL_INFO
+pub fn ibc_channel_connect(
+    deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelConnectMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    // we need to check the counter party version in try and ack (sometimes here)
+    enforce_order_and_version(msg.channel(), msg.counterparty_version())?;
+
+    let channel: IbcChannel = msg.into();
+    let info = ChannelInfo {
+        id: channel.endpoint.channel_id,
+        counterparty_endpoint: channel.counterparty_endpoint,
+        connection_id: channel.connection_id,
+    };
+    CHANNEL_INFO.save(deps.storage, &info.id, &info)?;
+
+    Ok(IbcBasicResponse::default())
+}
+
+fn enforce_order_and_version(
+    channel: &IbcChannel,
+    counterparty_version: Option<&str>,
+) -> Result<(), ContractError> {
+    if channel.version != ICS721_VERSION {
+        return Err(ContractError::InvalidIbcVersion {
+            version: channel.version.clone(),
+        });
+    }
+    if let Some(version) = counterparty_version {
+        if version != ICS721_VERSION {
+            return Err(ContractError::InvalidIbcVersion {
+                version: version.to_string(),
+            });
+        }
+    }
+    if channel.order != ICS721_ORDERING {
+        return Err(ContractError::OnlyOrderedChannel {});
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+/// we never close a channel ourselves, as it would lock the tokens escrowed for it
+pub fn ibc_channel_close(
+    _deps: DepsMut,
+    _env: Env,
+    msg: IbcChannelCloseMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    match msg {
+        IbcChannelCloseMsg::CloseInit { .. } => Err(ContractError::CannotCloseChannel {}),
+        // the counterparty closed it anyway, nothing we can do about it
+        IbcChannelCloseMsg::CloseConfirm { .. } => Ok(IbcBasicResponse::default()),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+/// Release tokens coming back from escrow, or mint vouchers for remote ones.
+/// We should not return an error if possible, but rather an acknowledgement of failure
+pub fn ibc_packet_receive(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketReceiveMsg,
+) -> Result<IbcReceiveResponse, Never> {
+    let packet = msg.packet;
+
+    do_ibc_packet_receive(deps, &env, &packet).or_else(|err| {
+        Ok(IbcReceiveResponse::new()
+            .set_ack(ack_fail(err.to_string()))
+            .add_attributes(vec![
+                attr("action", "receive"),
+                attr("success", "false"),
+                attr("error", err.to_string()),
+            ]))
+    })
+}
+
+// this does the work of ibc_packet_receive, we wrap it to turn errors into acknowledgements
+fn do_ibc_packet_receive(
+    deps: DepsMut,
+    env: &Env,
+    packet: &IbcPacket,
+) -> Result<IbcReceiveResponse, ContractError> {
+    let msg: Ics721Packet = from_binary(&packet.data)?;
+    msg.validate()?;
+    let receiver = deps.api.addr_validate(&msg.receiver)?;
+    let config = CONFIG.load(deps.storage)?;
+    let channel = &packet.dest.channel_id;
+
+    // If the tokens went from here to the remote chain, the class looks like "port/channel/class",
+    // with the port and channel they arrived on there, and our class after them.
+    // Otherwise it is a remote class, which we prefix with our own port and channel.
+    let source_prefix = format!("{}/{}/", packet.src.port_id, packet.src.channel_id);
+    let messages = match msg.class_id.strip_prefix(&source_prefix) {
+        Some(class_id) => {
+            // make sure we have enough balance for this
+            for (token_id, amount) in msg.tokens() {
+                reduce_channel_balance(deps.storage, channel, class_id, token_id, *amount)?;
+            }
+            let (contract, token_ids) = local_tokens(deps.api, &config, class_id, &msg.token_ids)?;
+            let send = Cw1155ExecuteMsg::BatchSendFrom {
+                from: env.contract.address.to_string(),
+                to: receiver.to_string(),
+                batch: cw1155_batch(token_ids, &msg.amounts),
+                msg: None,
+            };
+            vec![cw1155_msg(&contract, &send)?]
+        }
+        None => {
+            let class_id = format!(
+                "{}/{}/{}",
+                packet.dest.port_id, packet.dest.channel_id, msg.class_id
+            );
+            mint_vouchers(deps.storage, &config, &class_id, &msg, receiver.as_str())?
+        }
+    };
+
+    // These are plain messages rather than submessages: if one fails, the whole receive fails and
+    // the packet is acknowledged with an error or times out, refunding the sender either way.
+    let res = IbcReceiveResponse::new()
+        .set_ack(ack_success())
+        .add_messages(messages)
+        .add_attribute("action", "receive")
+        .add_attribute("sender", msg.sender)
+        .add_attribute("receiver", msg.receiver)
+        .add_attribute("class_id", msg.class_id)
+        .add_attribute("token_ids", msg.token_ids.join(","))
+        .add_attribute("success", "true");
+
+    Ok(res)
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+/// check if success or failure and update balance, or return tokens
+pub fn ibc_packet_ack(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketAckMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let ics721msg: Ics721Ack = from_binary(&msg.acknowledgement.data)?;
+    match ics721msg {
+        Ics721Ack::Result(_) => on_packet_success(msg.original_packet),
+        Ics721Ack::Error(err) => on_packet_failure(deps, &env, msg.original_packet, err),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+/// return tokens to original sender (same as failure in ibc_packet_ack)
+pub fn ibc_packet_timeout(
+    deps: DepsMut,
+    env: Env,
+    msg: IbcPacketTimeoutMsg,
+) -> Result<IbcBasicResponse, ContractError> {
+    let packet = msg.packet;
+    on_packet_failure(deps, &env, packet, "timeout".to_string())
+}
+
+fn on_packet_success(packet: IbcPacket) -> Result<IbcBasicResponse, ContractError> {
+    let msg: Ics721Packet = from_binary(&packet.data)?;
+
+    // similar event messages like ibctransfer module
+    let attributes = vec![
+        attr("action", "acknowledge"),
+        attr("sender", &msg.sender),
+        attr("receiver", &msg.receiver),
+        attr("class_id", &msg.class_id),
+        attr("token_ids", msg.token_ids.join(",")),
+        attr("success", "true"),
+    ];
+
+    Ok(IbcBasicResponse::new().add_attributes(attributes))
+}
+
+// return the tokens to sender
+fn on_packet_failure(
+    deps: DepsMut,
+    env: &Env,
+    packet: IbcPacket,
+    err: String,
+) -> Result<IbcBasicResponse, ContractError> {
+    let msg: Ics721Packet = from_binary(&packet.data)?;
+    let config = CONFIG.load(deps.storage)?;
+    let channel = &packet.src.channel_id;
+
+    // vouchers going back to their origin were burned when sent, we mint them again. Only they
+    // are sent with a class prefixed by the port and channel of the packet, as they arrived on it
+    let voucher_prefix = format!("{}/{}/", packet.src.port_id, channel);
+    let messages = if msg.class_id.starts_with(&voucher_prefix) {
+        mint_vouchers(deps.storage, &config, &msg.class_id, &msg, &msg.sender)?
+    } else {
+        // undo the balance update on failure (as we pre-emptively added it on send)
+        for (token_id, amount) in msg.tokens() {
+            reduce_channel_balance(deps.storage, channel, &msg.class_id, token_id, *amount)?;
+        }
+        let (contract, token_ids) = local_tokens(deps.api, &config, &msg.class_id, &msg.token_ids)?;
+        let send = Cw1155ExecuteMsg::BatchSendFrom {
+            from: env.contract.address.to_string(),
+            to: msg.sender.clone(),
+            batch: cw1155_batch(token_ids, &msg.amounts),
+            msg: None,
+        };
+        vec![cw1155_msg(&contract, &send)?]
+    };
+
+    // similar event messages like ibctransfer module
+    let res = IbcBasicResponse::new()
+        .add_messages(messages)
+        .add_attribute("action", "acknowledge")
+        .add_attribute("sender", msg.sender)
+        .add_attribute("receiver", msg.receiver)
+        .add_attribute("class_id", msg.class_id)
+        .add_attribute("token_ids", msg.token_ids.join(","))
+        .add_attribute("success", "false")
+        .add_attribute("error", err);
+
+    Ok(res)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::*;
+
+    use crate::contract::{execute, query_channel};
+    use crate::msg::{ChannelBalance, ExecuteMsg, TransferMsg};
+    use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
+    use cosmwasm_std::{
+        to_vec, Deps, IbcAcknowledgement, IbcEndpoint, IbcMsg, Response, Timestamp,
+    };
+    use cw1155::Cw1155BatchReceiveMsg;
+
+    #[test]
+    fn check_ack_json() {
+        let success = Ics721Ack::Result(b"1".into());
+        let fail = Ics721Ack::Error("bad token".into());
+
+        let success_json = String::from_utf8(to_vec(&success).unwrap()).unwrap();
+        assert_eq!(r#"{"result":"MQ=="}"#, success_json.as_str());
+
+        let fail_json = String::from_utf8(to_vec(&fail).unwrap()).unwrap();
+        assert_eq!(r#"{"error":"bad token"}"#, fail_json.as_str());
+    }
+
+    #[test]
+    fn check_packet_json() {
+        let packet = Ics721Packet {
+            class_id: "wasm1collection".to_string(),
+            token_ids: vec!["credit-1".to_string()],
+            amounts: vec![Uint128::new(12345)],
+            token_uris: vec!["ipfs://credit-1".to_string()],
+            sender: "cosmos1zedxv25ah8fksmg2lzrndrpkvsjqgk4zt5ff7n".to_string(),
+            receiver: "wasm1fucynrfkrt684pm8jrt8la5h2csvs5cnldcgqc".to_string(),
+        };
+        let expected = r#"{"classId":"wasm1collection","tokenIds":["credit-1"],"amounts":["12345"],"tokenUris":["ipfs://credit-1"],"sender":"cosmos1zedxv25ah8fksmg2lzrndrpkvsjqgk4zt5ff7n","receiver":"wasm1fucynrfkrt684pm8jrt8la5h2csvs5cnldcgqc"}"#;
+
+        let encoded = String::from_utf8(to_vec(&packet).unwrap()).unwrap();
+        assert_eq!(expected, encoded.as_str());
+    }
+
+    fn transfer(
+        deps: DepsMut,
+        contract: &str,
+        channel: &str,
+        tokens: &[(&str, u128)],
+    ) -> Result<Response, ContractError> {
+        let transfer = TransferMsg {
+            channel: channel.to_string(),
+            remote_address: "remote-rcpt".to_string(),
+            timeout: None,
+        };
+        let msg = ExecuteMsg::BatchReceive(Cw1155BatchReceiveMsg {
+            operator: "alice".to_string(),
+            from: Some("alice".to_string()),
+            batch: tokens
+                .iter()
+                .map(|(token_id, amount)| (token_id.to_string(), (*amount).into(), String::new()))
+                .collect(),
+            msg: to_binary(&transfer).unwrap(),
+        });
+        execute(deps, mock_env(), mock_info(contract, &[]), msg)
+    }
+
+    // the packet sent by a transfer, as it comes back to us in an ack or timeout
+    fn sent_packet(res: &Response, my_channel: &str) -> (Ics721Packet, IbcPacket) {
+        let data = match &res.messages.last().unwrap().msg {
+            CosmosMsg::Ibc(IbcMsg::SendPacket { data, .. }) => data.clone(),
+            msg => panic!("Unexpected message: {:?}", msg),
+        };
+        let packet = IbcPacket::new(
+            data.clone(),
+            IbcEndpoint {
+                port_id: CONTRACT_PORT.to_string(),
+                channel_id: my_channel.to_string(),
+            },
+            IbcEndpoint {
+                port_id: REMOTE_PORT.to_string(),
+                channel_id: format!("{}5", my_channel),
+            },
+            3,
+            Timestamp::from_seconds(1665321069).into(),
+        );
+        (from_binary(&data).unwrap(), packet)
+    }
+
+    fn mock_receive_packet(
+        my_channel: &str,
+        class_id: &str,
+        tokens: &[(&str, u128)],
+        receiver: &str,
+    ) -> IbcPacket {
+        let data = Ics721Packet {
+            class_id: class_id.to_string(),
+            token_ids: tokens.iter().map(|(id, _)| id.to_string()).collect(),
+            amounts: tokens.iter().map(|(_, amount)| (*amount).into()).collect(),
+            token_uris: tokens
+                .iter()
+                .map(|(id, _)| format!("remote:{}", id))
+                .collect(),
+            sender: "remote-sender".to_string(),
+            receiver: receiver.to_string(),
+        };
+        IbcPacket::new(
+            to_binary(&data).unwrap(),
+            IbcEndpoint {
+                port_id: REMOTE_PORT.to_string(),
+                channel_id: format!("{}5", my_channel),
+            },
+            IbcEndpoint {
+                port_id: CONTRACT_PORT.to_string(),
+                channel_id: my_channel.to_string(),
+            },
+            3,
+            Timestamp::from_seconds(1665321069).into(),
+        )
+    }
+
+    fn send_msg(contract: &str, to: &str, tokens: &[(&str, u128)]) -> CosmosMsg {
+        let send = Cw1155ExecuteMsg::BatchSendFrom {
+            from: MOCK_CONTRACT_ADDR.to_string(),
+            to: to.to_string(),
+            batch: tokens
+                .iter()
+                .map(|(token_id, amount)| (token_id.to_string(), (*amount).into(), String::new()))
+                .collect(),
+            msg: None,
+        };
+        cw1155_msg(&Addr::unchecked(contract), &send).unwrap()
+    }
+
+    fn mint_msg(to: &str, token_id: &str, amount: u128, uri: &str, first: bool) -> CosmosMsg {
+        let mint = Cw1155ExecuteMsg::Mint {
+            to: to.to_string(),
+            token_id: token_id.to_string(),
+            value: amount.into(),
+            uri: uri.to_string(),
+            max_supply: None,
+            supply_policy: if first {
+                Some(SupplyPolicy::Open)
+            } else {
+                None
+            },
+            extension: None,
+            msg: None,
+        };
+        cw1155_msg(&Addr::unchecked(VOUCHER_CONTRACT), &mint).unwrap()
+    }
+
+    fn outstanding(deps: Deps, channel: &str) -> Vec<(String, String, u128)> {
+        query_channel(deps, channel.to_string())
+            .unwrap()
+            .balances
+            .into_iter()
+            .map(|balance: ChannelBalance| {
+                (
+                    balance.class_id,
+                    balance.token_id,
+                    balance.outstanding.u128(),
+                )
+            })
+            .collect()
+    }
+
+    fn ack_error(packet: IbcPacket) -> IbcPacketAckMsg {
+        let ack = IbcAcknowledgement::new(ack_fail("remote failure".to_string()));
+        IbcPacketAckMsg::new(ack, packet)
+    }
+
+    #[test]
+    fn send_and_redeem_local_tokens() {
+        let send_channel = "channel-9";
+        let mut deps = setup(&["channel-1", send_channel], &["collection"]);
+
+        // tokens of a contract off the allow list are rejected
+        let err = transfer(deps.as_mut(), "other", send_channel, &[("a", 1)]).unwrap_err();
+        assert_eq!(err, ContractError::NotOnAllowList);
+
+        // the tokens are escrowed and sent with their uris
+        let res = transfer(
+            deps.as_mut(),
+            "collection",
+            send_channel,
+            &[("a", 10), ("b", 5)],
+        )
+        .unwrap();
+        assert_eq!(1, res.messages.len());
+        let (packet, _) = sent_packet(&res, send_channel);
+        assert_eq!(
+            packet,
+            Ics721Packet {
+                class_id: "collection".to_string(),
+                token_ids: vec!["a".to_string(), "b".to_string()],
+                amounts: vec![Uint128::new(10), Uint128::new(5)],
+                token_uris: vec!["uri:a".to_string(), "uri:b".to_string()],
+                sender: "alice".to_string(),
+                receiver: "remote-rcpt".to_string(),
+            }
+        );
+        assert_eq!(
+            outstanding(deps.as_ref(), send_channel),
+            vec![
+                ("collection".to_string(), "a".to_string(), 10),
+                ("collection".to_string(), "b".to_string(), 5)
+            ]
+        );
+
+        // they come back with the class prefixed by the remote port and channel
+        let class_id = format!("{}/{}5/collection", REMOTE_PORT, send_channel);
+        let recv_packet = mock_receive_packet(send_channel, &class_id, &[("a", 4)], "bob");
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            send_msg("collection", "bob", &[("a", 4)])
+        );
+        let ack: Ics721Ack = from_binary(&res.acknowledgement).unwrap();
+        assert_eq!(ack, Ics721Ack::Result(b"1".into()));
+        assert_eq!(
+            outstanding(deps.as_ref(), send_channel),
+            vec![
+                ("collection".to_string(), "a".to_string(), 6),
+                ("collection".to_string(), "b".to_string(), 5)
+            ]
+        );
+
+        // more than what is escrowed for the channel can't be redeemed
+        let recv_packet = mock_receive_packet(send_channel, &class_id, &[("a", 7)], "bob");
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet),
+        )
+        .unwrap();
+        assert!(res.messages.is_empty());
+        let ack: Ics721Ack = from_binary(&res.acknowledgement).unwrap();
+        let no_funds = Ics721Ack::Error(ContractError::InsufficientFunds {}.to_string());
+        assert_eq!(ack, no_funds);
+
+        // neither through another channel
+        let class_id = format!("{}/channel-15/collection", REMOTE_PORT);
+        let recv_packet = mock_receive_packet("channel-1", &class_id, &[("a", 1)], "bob");
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet),
+        )
+        .unwrap();
+        let ack: Ics721Ack = from_binary(&res.acknowledgement).unwrap();
+        assert_eq!(ack, no_funds);
+    }
+
+    #[test]
+    fn refund_local_tokens_on_failure() {
+        let send_channel = "channel-9";
+        let mut deps = setup(&[send_channel], &["collection"]);
+
+        let res = transfer(deps.as_mut(), "collection", send_channel, &[("a", 10)]).unwrap();
+        let (_, packet) = sent_packet(&res, send_channel);
+
+        // a remote class named like the escrowed one doesn't turn the refund into a voucher mint
+        let recv_packet = mock_receive_packet(send_channel, "collection", &[("a", 1)], "bob");
+        ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet),
+        )
+        .unwrap();
+
+        // an error ack sends the tokens back to the sender
+        let res = ibc_packet_ack(deps.as_mut(), mock_env(), ack_error(packet)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            send_msg("collection", "alice", &[("a", 10)])
+        );
+        assert_eq!(
+            outstanding(deps.as_ref(), send_channel),
+            vec![("collection".to_string(), "a".to_string(), 0)]
+        );
+
+        // so does a timeout
+        let res = transfer(deps.as_mut(), "collection", send_channel, &[("a", 3)]).unwrap();
+        let (_, packet) = sent_packet(&res, send_channel);
+        let res = ibc_packet_timeout(deps.as_mut(), mock_env(), IbcPacketTimeoutMsg::new(packet))
+            .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            send_msg("collection", "alice", &[("a", 3)])
+        );
+
+        // a success ack only emits events
+        let res = transfer(deps.as_mut(), "collection", send_channel, &[("a", 2)]).unwrap();
+        let (_, packet) = sent_packet(&res, send_channel);
+        let ack = IbcAcknowledgement::new(ack_success());
+        let res =
+            ibc_packet_ack(deps.as_mut(), mock_env(), IbcPacketAckMsg::new(ack, packet)).unwrap();
+        assert!(res.messages.is_empty());
+        assert_eq!(
+            outstanding(deps.as_ref(), send_channel),
+            vec![("collection".to_string(), "a".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn remote_tokens_become_vouchers() {
+        let recv_channel = "channel-1";
+        let mut deps = setup(&[recv_channel, "channel-2"], &[]);
+
+        // the first transfer of a remote token mints its voucher with an open supply
+        let recv_packet =
+            mock_receive_packet(recv_channel, "remote-collection", &[("x", 8)], "bob");
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet.clone()),
+        )
+        .unwrap();
+        let class_id = format!("{}/{}/remote-collection", CONTRACT_PORT, recv_channel);
+        let voucher = voucher_token_id(&class_id, "x");
+        assert!(voucher.starts_with("ibc/") && voucher.ends_with("/x"));
+        assert_eq!(
+            res.messages[0].msg,
+            mint_msg("bob", &voucher, 8, "remote:x", true)
+        );
+        assert_eq!(
+            VOUCHERS.load(&deps.storage, &voucher).unwrap(),
+            VoucherInfo {
+                class_id: class_id.clone(),
+                token_id: "x".to_string(),
+            }
+        );
+
+        // later ones mint more of it
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            mint_msg("bob", &voucher, 8, "remote:x", false)
+        );
+
+        // only vouchers minted by this contract can be sent
+        let err = transfer(deps.as_mut(), VOUCHER_CONTRACT, recv_channel, &[("y", 1)]).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::UnknownVoucher {
+                token_id: "y".to_string()
+            }
+        );
+
+        // vouchers going back are burned, and sent with their class and the remote token id
+        let res = transfer(
+            deps.as_mut(),
+            VOUCHER_CONTRACT,
+            recv_channel,
+            &[(&voucher, 5)],
+        )
+        .unwrap();
+        assert_eq!(2, res.messages.len());
+        let burn = Cw1155ExecuteMsg::BatchBurn {
+            from: MOCK_CONTRACT_ADDR.to_string(),
+            batch: vec![(voucher.clone(), Uint128::new(5), String::new())],
+        };
+        assert_eq!(
+            res.messages[0].msg,
+            cw1155_msg(&Addr::unchecked(VOUCHER_CONTRACT), &burn).unwrap()
+        );
+        let (sent, packet) = sent_packet(&res, recv_channel);
+        assert_eq!(sent.class_id, class_id);
+        assert_eq!(sent.token_ids, vec!["x".to_string()]);
+        assert_eq!(sent.token_uris, vec![format!("uri:{}", voucher)]);
+        assert!(outstanding(deps.as_ref(), recv_channel).is_empty());
+
+        // and minted again if the transfer fails
+        let res = ibc_packet_timeout(deps.as_mut(), mock_env(), IbcPacketTimeoutMsg::new(packet))
+            .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            mint_msg("alice", &voucher, 5, &format!("uri:{}", voucher), false)
+        );
+
+        // vouchers going through another channel are escrowed, with the full class
+        let res = transfer(
+            deps.as_mut(),
+            VOUCHER_CONTRACT,
+            "channel-2",
+            &[(&voucher, 3)],
+        )
+        .unwrap();
+        assert_eq!(1, res.messages.len());
+        let (sent, packet) = sent_packet(&res, "channel-2");
+        assert_eq!(sent.class_id, class_id);
+        assert_eq!(sent.token_ids, vec!["x".to_string()]);
+        assert_eq!(
+            outstanding(deps.as_ref(), "channel-2"),
+            vec![(class_id.clone(), "x".to_string(), 3)]
+        );
+
+        // and released from escrow if the transfer fails
+        let res = ibc_packet_ack(deps.as_mut(), mock_env(), ack_error(packet)).unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            send_msg(VOUCHER_CONTRACT, "alice", &[(&voucher, 3)])
+        );
+
+        // or when they come back
+        transfer(
+            deps.as_mut(),
+            VOUCHER_CONTRACT,
+            "channel-2",
+            &[(&voucher, 3)],
+        )
+        .unwrap();
+        let returning = format!("{}/channel-25/{}", REMOTE_PORT, class_id);
+        let recv_packet = mock_receive_packet("channel-2", &returning, &[("x", 3)], "carl");
+        let res = ibc_packet_receive(
+            deps.as_mut(),
+            mock_env(),
+            IbcPacketReceiveMsg::new(recv_packet),
+        )
+        .unwrap();
+        assert_eq!(
+            res.messages[0].msg,
+            send_msg(VOUCHER_CONTRACT, "carl", &[(&voucher, 3)])
+        );
+    }
+    #[test]
+    fn vouchers_return_to_their_origin() {
+        let home_end = IbcEndpoint {
+            port_id: CONTRACT_PORT.to_string(),
+            channel_id: "channel-9".to_string(),
+        };
+        let away_end = IbcEndpoint {
+            port_id: REMOTE_PORT.to_string(),
+            channel_id: "channel-1".to_string(),
+        };
+        let mut home = setup(&[&home_end.channel_id], &["collection"]);
+        let mut away = setup(&[&away_end.channel_id], &[]);
+        let deliver = |res: &Response, src: &IbcEndpoint, dest: &IbcEndpoint| {
+            let (_, packet) = sent_packet(res, &src.channel_id);
+            IbcPacketReceiveMsg::new(IbcPacket::new(
+                packet.data,
+                src.clone(),
+                dest.clone(),
+                3,
+                Timestamp::from_seconds(1665321069).into(),
+            ))
+        };
+
+        // the tokens leave their origin and become vouchers away
+        let res = transfer(home.as_mut(), "collection", "channel-9", &[("a", 10)]).unwrap();
+        let res = ibc_packet_receive(
+            away.as_mut(),
+            mock_env(),
+            deliver(&res, &home_end, &away_end),
+        )
+        .unwrap();
+        let class_id = format!("{}/channel-1/collection", REMOTE_PORT);
+        let voucher = voucher_token_id(&class_id, "a");
+        assert_eq!(
+            res.messages[0].msg,
+            mint_msg("remote-rcpt", &voucher, 10, "uri:a", true)
+        );
+
+        // the burned vouchers release the escrowed tokens at home
+        let res = transfer(
+            away.as_mut(),
+            VOUCHER_CONTRACT,
+            "channel-1",
+            &[(&voucher, 4)],
+        )
+        .unwrap();
+        let res = ibc_packet_receive(
+            home.as_mut(),
+            mock_env(),
+            deliver(&res, &away_end, &home_end),
+        )
+        .unwrap();
+        let ack: Ics721Ack = from_binary(&res.acknowledgement).unwrap();
+        assert_eq!(ack, Ics721Ack::Result(b"1".into()));
+        assert_eq!(
+            res.messages[0].msg,
+            send_msg("collection", "remote-rcpt", &[("a", 4)])
+        );
+        assert_eq!(
+            outstanding(home.as_ref(), "channel-9"),
+            vec![("collection".to_string(), "a".to_string(), 6)]
+        );
+    }
+}
@@ -0,0 +1,19 @@
+/*!
+This is an *IBC Enabled* contract that sends CW1155 tokens from one chain to another, in the spirit of the
+ICS721 protocol for non-fungible tokens, but with an amount for every token id.
+
+Tokens of the local CW1155 contracts are escrowed here while they are away. Tokens coming from a remote chain
+are minted as vouchers on a CW1155 contract this contract is a minter of, and burned again when they go back.
+
+For more information on this contract, please check out the
+[README](https://github.com/CosmWasm/cw-plus/blob/main/contracts/cw1155-ics721/README.md).
+*/
+
+pub mod contract;
+mod error;
+pub mod ibc;
+pub mod msg;
+pub mod state;
+mod test_helpers;
+
+pub use crate::error::ContractError;
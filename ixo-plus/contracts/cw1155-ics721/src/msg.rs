@@ -0,0 +1,112 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::Uint128;
+use cw1155::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
+
+use crate::state::{ChannelInfo, VoucherInfo};
+
+#[cw_serde]
+pub struct InitMsg {
+    /// Default timeout for ics721 packets, specified in seconds
+    pub default_timeout: u64,
+    /// who can allow more contracts
+    pub gov_contract: String,
+    /// The cw1155 contract minting vouchers for the tokens of remote chains,
+    /// this contract must be granted the minter role on it
+    pub voucher_contract: String,
+    /// initial allowlist - all cw1155 tokens we will send must be previously allowed by governance
+    pub allowlist: Vec<String>,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    /// This accepts a properly-encoded ReceiveMsg from a cw1155 contract
+    Receive(Cw1155ReceiveMsg),
+    /// This accepts a properly-encoded BatchReceiveMsg from a cw1155 contract
+    BatchReceive(Cw1155BatchReceiveMsg),
+    /// This must be called by gov_contract, will allow a new cw1155 contract to be sent
+    Allow { contract: String },
+    /// Change the admin (must be called by current admin)
+    UpdateAdmin { admin: String },
+}
+
+/// This is the message we accept via Receive and BatchReceive
+#[cw_serde]
+pub struct TransferMsg {
+    /// The local channel to send the packets on
+    pub channel: String,
+    /// The remote address to send to.
+    /// Don't use HumanAddress as this will likely have a different Bech32 prefix than we use
+    /// and cannot be validated locally
+    pub remote_address: String,
+    /// How long the packet lives in seconds. If not specified, use default_timeout
+    pub timeout: Option<u64>,
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    /// Return the port ID bound by this contract.
+    #[returns(PortResponse)]
+    Port {},
+    /// Show all channels we have connected to.
+    #[returns(ListChannelsResponse)]
+    ListChannels {},
+    /// Returns the details of the name channel, error if not created.
+    #[returns(ChannelResponse)]
+    Channel { id: String },
+    /// Show the Config.
+    #[returns(ConfigResponse)]
+    Config {},
+    #[returns(cw_controllers::AdminResponse)]
+    Admin {},
+    /// Query if a given cw1155 contract is allowed.
+    #[returns(AllowedResponse)]
+    Allowed { contract: String },
+    /// Returns the remote class and token id of a voucher, error if it isn't one.
+    #[returns(VoucherInfo)]
+    Voucher { token_id: String },
+}
+
+#[cw_serde]
+pub struct ListChannelsResponse {
+    pub channels: Vec<ChannelInfo>,
+}
+
+#[cw_serde]
+pub struct ChannelBalance {
+    pub class_id: String,
+    pub token_id: String,
+    /// How many tokens we currently have pending over this channel
+    pub outstanding: Uint128,
+    /// The total number of tokens that have been sent over this channel
+    /// (even if many have been returned, so balance is low)
+    pub total_sent: Uint128,
+}
+
+#[cw_serde]
+pub struct ChannelResponse {
+    /// Information on the channel's connection
+    pub info: ChannelInfo,
+    /// The balance of every token sent over this channel
+    pub balances: Vec<ChannelBalance>,
+}
+
+#[cw_serde]
+pub struct PortResponse {
+    pub port_id: String,
+}
+
+#[cw_serde]
+pub struct ConfigResponse {
+    pub default_timeout: u64,
+    pub voucher_contract: String,
+    pub gov_contract: String,
+}
+
+#[cw_serde]
+pub struct AllowedResponse {
+    pub is_allowed: bool,
+}
@@ -0,0 +1,97 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Empty, IbcEndpoint, StdResult, Storage, Uint128};
+use cw_controllers::Admin;
+use cw_storage_plus::{Item, Map};
+
+use crate::ContractError;
+
+pub const ADMIN: Admin = Admin::new("admin");
+
+pub const CONFIG: Item<Config> = Item::new("ics721_config");
+
+/// static info on one channel that doesn't change
+pub const CHANNEL_INFO: Map<&str, ChannelInfo> = Map::new("channel_info");
+
+/// indexed by (channel_id, class_id, token_id) maintaining the balance of the channel in that token
+pub const CHANNEL_STATE: Map<(&str, &str, &str), ChannelState> = Map::new("channel_state");
+
+/// Every cw1155 contract we allow to be sent is stored here
+pub const ALLOW_LIST: Map<&Addr, Empty> = Map::new("allow_list");
+
+/// Every voucher minted on the voucher contract, by voucher token id
+pub const VOUCHERS: Map<&str, VoucherInfo> = Map::new("vouchers");
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ChannelState {
+    pub outstanding: Uint128,
+    pub total_sent: Uint128,
+}
+
+#[cw_serde]
+pub struct Config {
+    pub default_timeout: u64,
+    /// The cw1155 contract minting vouchers for the tokens of remote chains
+    pub voucher_contract: Addr,
+}
+
+#[cw_serde]
+pub struct ChannelInfo {
+    /// id of this channel
+    pub id: String,
+    /// the remote channel/port we connect to
+    pub counterparty_endpoint: IbcEndpoint,
+    /// the connection this exists on (you can use to query client/consensus info)
+    pub connection_id: String,
+}
+
+#[cw_serde]
+pub struct VoucherInfo {
+    /// The class of the remote tokens, prefixed with the port and channel they arrived on
+    pub class_id: String,
+    /// The token id on the remote chain
+    pub token_id: String,
+}
+
+pub fn increase_channel_balance(
+    storage: &mut dyn Storage,
+    channel: &str,
+    class_id: &str,
+    token_id: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    CHANNEL_STATE.update(
+        storage,
+        (channel, class_id, token_id),
+        |orig| -> StdResult<_> {
+            let mut state = orig.unwrap_or_default();
+            state.outstanding += amount;
+            state.total_sent += amount;
+            Ok(state)
+        },
+    )?;
+    Ok(())
+}
+
+pub fn reduce_channel_balance(
+    storage: &mut dyn Storage,
+    channel: &str,
+    class_id: &str,
+    token_id: &str,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    CHANNEL_STATE.update(
+        storage,
+        (channel, class_id, token_id),
+        |orig| -> Result<_, ContractError> {
+            // this will return error if we don't have the tokens there to cover the request (or no token registered)
+            let mut cur = orig.ok_or(ContractError::InsufficientFunds {})?;
+            cur.outstanding = cur
+                .outstanding
+                .checked_sub(amount)
+                .or(Err(ContractError::InsufficientFunds {}))?;
+            Ok(cur)
+        },
+    )?;
+    Ok(())
+}
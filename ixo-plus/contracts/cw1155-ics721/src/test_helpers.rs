@@ -0,0 +1,83 @@
+#![cfg(test)]
+
+use crate::contract::instantiate;
+use crate::ibc::{ibc_channel_connect, ibc_channel_open, ICS721_ORDERING, ICS721_VERSION};
+
+use cosmwasm_std::testing::{
+    mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+};
+use cosmwasm_std::{
+    from_binary, to_binary, ContractResult, DepsMut, Empty, IbcChannel, IbcChannelConnectMsg,
+    IbcChannelOpenMsg, IbcEndpoint, OwnedDeps, SystemResult, WasmQuery,
+};
+use cw1155::{Cw1155QueryMsg, TokenInfoResponse};
+
+use crate::msg::InitMsg;
+
+pub const DEFAULT_TIMEOUT: u64 = 3600; // 1 hour,
+pub const CONTRACT_PORT: &str = "wasm.cosmos2contract";
+pub const REMOTE_PORT: &str = "wasm.remote";
+pub const CONNECTION_ID: &str = "connection-2";
+pub const VOUCHER_CONTRACT: &str = "vouchers";
+
+pub fn mock_channel(channel_id: &str) -> IbcChannel {
+    IbcChannel::new(
+        IbcEndpoint {
+            port_id: CONTRACT_PORT.into(),
+            channel_id: channel_id.into(),
+        },
+        IbcEndpoint {
+            port_id: REMOTE_PORT.into(),
+            channel_id: format!("{}5", channel_id),
+        },
+        ICS721_ORDERING,
+        ICS721_VERSION,
+        CONNECTION_ID,
+    )
+}
+
+// we simulate instantiate and ack here
+pub fn add_channel(mut deps: DepsMut, channel_id: &str) {
+    let channel = mock_channel(channel_id);
+    let open_msg = IbcChannelOpenMsg::new_init(channel.clone());
+    ibc_channel_open(deps.branch(), mock_env(), open_msg).unwrap();
+    let connect_msg = IbcChannelConnectMsg::new_ack(channel, ICS721_VERSION);
+    ibc_channel_connect(deps.branch(), mock_env(), connect_msg).unwrap();
+}
+
+pub fn setup(channels: &[&str], allow: &[&str]) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+
+    // every cw1155 contract answers token info queries with "uri:{token_id}"
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => {
+            let token_id = match from_binary(msg).unwrap() {
+                Cw1155QueryMsg::TokenInfo { token_id } => token_id,
+                _ => unimplemented!(),
+            };
+            let info = TokenInfoResponse::<Option<Empty>> {
+                url: format!("uri:{}", token_id),
+                extension: None,
+                frozen: false,
+            };
+            SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+        }
+        _ => unimplemented!(),
+    });
+
+    // instantiate an empty contract
+    let instantiate_msg = InitMsg {
+        default_timeout: DEFAULT_TIMEOUT,
+        gov_contract: "gov".to_string(),
+        voucher_contract: VOUCHER_CONTRACT.to_string(),
+        allowlist: allow.iter().map(|contract| contract.to_string()).collect(),
+    };
+    let info = mock_info(&String::from("anyone"), &[]);
+    let res = instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    for channel in channels {
+        add_channel(deps.as_mut(), channel);
+    }
+    deps
+}
@@ -1,11 +0,0 @@
-use cosmwasm_schema::write_api;
-
-use cw20_base_lp::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
-
-fn main() {
-    write_api! {
-        instantiate: InstantiateMsg,
-        execute: ExecuteMsg,
-        query: QueryMsg,
-    }
-}
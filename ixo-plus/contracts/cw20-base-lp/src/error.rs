@@ -1,41 +0,0 @@
-use cosmwasm_std::StdError;
-use thiserror::Error;
-
-#[derive(Error, Debug, PartialEq)]
-pub enum ContractError {
-    #[error("{0}")]
-    Std(#[from] StdError),
-
-    #[error("Unauthorized")]
-    Unauthorized {},
-
-    #[error("Cannot set to own account")]
-    CannotSetOwnAccount {},
-
-    #[error("Invalid zero amount")]
-    InvalidZeroAmount {},
-
-    #[error("Allowance is expired")]
-    Expired {},
-
-    #[error("No allowance for this account")]
-    NoAllowance {},
-
-    #[error("Minting cannot exceed the cap")]
-    CannotExceedCap {},
-
-    #[error("Logo binary data exceeds 5KB limit")]
-    LogoTooBig {},
-
-    #[error("Invalid xml preamble for SVG")]
-    InvalidXmlPreamble {},
-
-    #[error("Invalid png header")]
-    InvalidPngHeader {},
-
-    #[error("Invalid expiration value")]
-    InvalidExpiration {},
-
-    #[error("Duplicate initial balance addresses")]
-    DuplicateInitialBalanceAddresses {},
-}
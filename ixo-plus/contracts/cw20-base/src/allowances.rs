@@ -1,11 +1,18 @@
 use cosmwasm_std::{
-    attr, Addr, Binary, BlockInfo, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
-    Storage, Uint128,
+    attr, to_vec, Addr, Api, Binary, BlockInfo, CanonicalAddr, Deps, DepsMut, Env, MessageInfo,
+    Response, StdError, StdResult, Storage, Uint128,
 };
-use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration};
+use cw20::{AllowanceResponse, Cw20ReceiveMsg, Expiration, NonceResponse, PermitSignDoc};
+use ripemd::Ripemd160;
+use sha2::{Digest, Sha256};
 
 use crate::error::ContractError;
-use crate::state::{ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, TOKEN_INFO};
+use crate::hooks::{balance_changed_hooks, received, sent};
+use crate::state::{
+    snapshot_total_supply, update_balance, ALLOWANCES, ALLOWANCES_SPENDER, CAPABILITIES, NONCES,
+    TOKEN_INFO,
+};
+use crate::vesting::assert_unlocked;
 
 pub fn execute_increase_allowance(
     deps: DepsMut,
@@ -43,6 +50,92 @@ pub fn execute_increase_allowance(
     Ok(res)
 }
 
+#[allow(clippy::too_many_arguments)]
+pub fn execute_permit(
+    deps: DepsMut,
+    env: Env,
+    owner_pubkey: Binary,
+    spender: String,
+    amount: Uint128,
+    expires: Option<Expiration>,
+    nonce: u64,
+    signature: Binary,
+) -> Result<Response, ContractError> {
+    let owner_addr = pubkey_to_address(deps.api, &owner_pubkey)?;
+    let spender_addr = deps.api.addr_validate(&spender)?;
+    if spender_addr == owner_addr {
+        return Err(ContractError::CannotSetOwnAccount {});
+    }
+
+    let expected = NONCES
+        .may_load(deps.storage, &owner_addr)?
+        .unwrap_or_default();
+    if nonce != expected {
+        return Err(ContractError::InvalidNonce { expected });
+    }
+    let sign_doc = PermitSignDoc {
+        chain_id: env.block.chain_id.clone(),
+        contract: env.contract.address.to_string(),
+        spender: spender.clone(),
+        amount,
+        expires,
+        nonce,
+    };
+    let hash = Sha256::digest(adr036_sign_bytes(&owner_addr, &to_vec(&sign_doc)?));
+    if !deps
+        .api
+        .secp256k1_verify(&hash, &signature, &owner_pubkey)?
+    {
+        return Err(ContractError::InvalidSignature {});
+    }
+    NONCES.save(deps.storage, &owner_addr, &(nonce + 1))?;
+
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::InvalidExpiration {});
+    }
+    if amount.is_zero() {
+        ALLOWANCES.remove(deps.storage, (&owner_addr, &spender_addr));
+        ALLOWANCES_SPENDER.remove(deps.storage, (&spender_addr, &owner_addr));
+    } else {
+        let allowance = AllowanceResponse {
+            allowance: amount,
+            expires,
+        };
+        ALLOWANCES.save(deps.storage, (&owner_addr, &spender_addr), &allowance)?;
+        ALLOWANCES_SPENDER.save(deps.storage, (&spender_addr, &owner_addr), &allowance)?;
+    }
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "permit"),
+        attr("owner", owner_addr),
+        attr("spender", spender),
+        attr("amount", amount),
+        attr("nonce", nonce.to_string()),
+    ]);
+    Ok(res)
+}
+
+/// The bytes a wallet signs for `signArbitrary(chain_id, signer, data)`, an ADR-036 amino sign doc
+/// wrapping `data` in a single `sign/MsgSignData` message, with the keys sorted and no spaces.
+fn adr036_sign_bytes(signer: &Addr, data: &[u8]) -> Vec<u8> {
+    format!(
+        r#"{{"account_number":"0","chain_id":"","fee":{{"amount":[],"gas":"0"}},"memo":"","msgs":[{{"type":"sign/MsgSignData","value":{{"data":"{}","signer":"{}"}}}}],"sequence":"0"}}"#,
+        Binary::from(data).to_base64(),
+        signer
+    )
+    .into_bytes()
+}
+
+/// Derives the account address of a compressed secp256k1 public key, as the Cosmos SDK does
+fn pubkey_to_address(api: &dyn Api, pubkey: &[u8]) -> Result<Addr, ContractError> {
+    if pubkey.len() != 33 {
+        return Err(ContractError::InvalidPubkey {});
+    }
+    let hash = Ripemd160::digest(Sha256::digest(pubkey));
+    Ok(api.addr_humanize(&CanonicalAddr::from(hash.as_slice()))?)
+}
+
 pub fn execute_decrease_allowance(
     deps: DepsMut,
     env: Env,
@@ -135,26 +228,38 @@ pub fn execute_transfer_from(
     // deduct allowance before doing anything else have enough allowance
     deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
 
-    BALANCES.update(
+    assert_unlocked(deps.storage, &owner_addr, amount, &env.block)?;
+    let owner_balance = update_balance(
         deps.storage,
         &owner_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let rcpt_balance = update_balance(
         deps.storage,
         &rcpt_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
+    let hooks = balance_changed_hooks(
+        deps.storage,
+        vec![
+            sent(&owner_addr, owner_balance, amount),
+            received(&rcpt_addr, rcpt_balance, amount),
+        ],
+    )?;
 
-    let res = Response::new().add_attributes(vec![
-        attr("action", "transfer_from"),
-        attr("from", owner),
-        attr("to", recipient),
-        attr("by", info.sender),
-        attr("amount", amount),
-    ]);
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "transfer_from"),
+            attr("from", owner),
+            attr("to", recipient),
+            attr("by", info.sender),
+            attr("amount", amount),
+        ])
+        .add_submessages(hooks);
     Ok(res)
 }
 
@@ -168,29 +273,42 @@ pub fn execute_burn_from(
 ) -> Result<Response, ContractError> {
     let owner_addr = deps.api.addr_validate(&owner)?;
 
+    if CAPABILITIES.load(deps.storage)?.minter_only_burn_from {
+        let config = TOKEN_INFO.load(deps.storage)?;
+        if config.mint.map(|m| m.minter) != Some(info.sender.clone()) {
+            return Err(ContractError::Unauthorized {});
+        }
+    }
+
     // deduct allowance before doing anything else have enough allowance
     deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
 
+    assert_unlocked(deps.storage, &owner_addr, amount, &env.block)?;
     // lower balance
-    BALANCES.update(
+    let balance = update_balance(
         deps.storage,
         &owner_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
+    let meta = TOKEN_INFO.update(deps.storage, |mut meta| -> StdResult<_> {
         meta.total_supply = meta.total_supply.checked_sub(amount)?;
         Ok(meta)
     })?;
-
-    let res = Response::new().add_attributes(vec![
-        attr("action", "burn_from"),
-        attr("from", owner),
-        attr("by", info.sender),
-        attr("amount", amount),
-    ]);
+    snapshot_total_supply(deps.storage, meta.total_supply, env.block.height)?;
+    let hooks = balance_changed_hooks(deps.storage, vec![sent(&owner_addr, balance, amount)])?;
+
+    let res = Response::new()
+        .add_attributes(vec![
+            attr("action", "burn_from"),
+            attr("from", owner),
+            attr("by", info.sender),
+            attr("amount", amount),
+        ])
+        .add_submessages(hooks);
     Ok(res)
 }
 
@@ -209,19 +327,29 @@ pub fn execute_send_from(
     // deduct allowance before doing anything else have enough allowance
     deduct_allowance(deps.storage, &owner_addr, &info.sender, &env.block, amount)?;
 
+    assert_unlocked(deps.storage, &owner_addr, amount, &env.block)?;
     // move the tokens to the contract
-    BALANCES.update(
+    let owner_balance = update_balance(
         deps.storage,
         &owner_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let rcpt_balance = update_balance(
         deps.storage,
         &rcpt_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
+    let hooks = balance_changed_hooks(
+        deps.storage,
+        vec![
+            sent(&owner_addr, owner_balance, amount),
+            received(&rcpt_addr, rcpt_balance, amount),
+        ],
+    )?;
 
     let attrs = vec![
         attr("action", "send_from"),
@@ -239,7 +367,10 @@ pub fn execute_send_from(
     }
     .into_cosmos_msg(contract)?;
 
-    let res = Response::new().add_message(msg).add_attributes(attrs);
+    let res = Response::new()
+        .add_message(msg)
+        .add_attributes(attrs)
+        .add_submessages(hooks);
     Ok(res)
 }
 
@@ -252,16 +383,34 @@ pub fn query_allowance(deps: Deps, owner: String, spender: String) -> StdResult<
     Ok(allowance)
 }
 
+pub fn query_nonce(deps: Deps, owner: String) -> StdResult<NonceResponse> {
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    let nonce = NONCES
+        .may_load(deps.storage, &owner_addr)?
+        .unwrap_or_default();
+    Ok(NonceResponse { nonce })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
-    use cosmwasm_std::{coins, CosmosMsg, SubMsg, Timestamp, WasmMsg};
-    use cw20::{Cw20Coin, TokenInfoResponse};
+    use std::marker::PhantomData;
+
+    use bech32::{ToBase32, Variant};
+    use cosmwasm_std::testing::{
+        mock_dependencies_with_balance, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coins, CosmosMsg, Empty, OwnedDeps, RecoverPubkeyError, SubMsg, Timestamp,
+        VerificationError, WasmMsg,
+    };
+    use cw20::{MinterResponse, TokenInfoResponse};
+    use k256::ecdsa::signature::Signer;
+    use k256::ecdsa::{Signature, SigningKey};
 
     use crate::contract::{execute, instantiate, query_balance, query_token_info};
-    use crate::msg::{ExecuteMsg, InstantiateMsg};
+    use crate::msg::{Capabilities, ExecuteMsg, InitialBalance, InstantiateMsg};
 
     fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
         query_balance(deps, address.into()).unwrap().balance
@@ -277,12 +426,16 @@ mod tests {
             name: "Auto Gen".to_string(),
             symbol: "AUTO".to_string(),
             decimals: 3,
-            initial_balances: vec![Cw20Coin {
+            initial_balances: vec![InitialBalance {
                 address: addr.into(),
                 amount,
+                vesting: None,
             }],
             mint: None,
             marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -655,6 +808,84 @@ mod tests {
         assert_eq!(err, ContractError::Expired {});
     }
 
+    #[test]
+    fn minter_burn_from_respects_limits() {
+        let mut deps = mock_dependencies_with_balance(&[]);
+        let owner = String::from("addr0001");
+        let minter = String::from("pool");
+
+        let instantiate_msg = InstantiateMsg {
+            name: "Liquidity Token".to_string(),
+            symbol: "LPT".to_string(),
+            decimals: 6,
+            initial_balances: vec![InitialBalance {
+                address: owner.clone(),
+                amount: Uint128::new(999999),
+                vesting: None,
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.clone(),
+                cap: None,
+            }),
+            marketing: None,
+            snapshots: None,
+            capabilities: Some(Capabilities {
+                holder_burn: false,
+                minter_only_burn_from: true,
+            }),
+            admin: None,
+        };
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("creator", &[]),
+            instantiate_msg,
+        )
+        .unwrap();
+
+        // the minter needs an allowance as well
+        let msg = ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::NoAllowance {});
+
+        // provide an allowance expiring in the next block
+        let mut env = mock_env();
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: minter.clone(),
+            amount: Uint128::new(77777),
+            expires: Some(Expiration::AtHeight(env.block.height + 1)),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&owner, &[]), msg).unwrap();
+
+        // cannot burn more than the allowance
+        let msg = ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(77778),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert!(matches!(err, ContractError::Std(StdError::Overflow { .. })));
+
+        // valid burn of part of the allowance
+        let msg = ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: Uint128::new(44444),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), &owner), Uint128::new(955555));
+
+        // the rest expires with the allowance
+        env.block.height += 1;
+        let msg = ExecuteMsg::BurnFrom {
+            owner,
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), env, mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::Expired {});
+    }
+
     #[test]
     fn send_from_respects_limits() {
         let mut deps = mock_dependencies_with_balance(&[]);
@@ -876,4 +1107,245 @@ mod tests {
             }
         );
     }
+
+    /// MockApi only humanizes its own canonical addresses, this one also humanizes the 20 byte
+    /// addresses of public keys, as ixo bech32 addresses.
+    struct PermitApi(MockApi);
+
+    impl Api for PermitApi {
+        fn addr_validate(&self, human: &str) -> StdResult<Addr> {
+            self.0.addr_validate(human)
+        }
+
+        fn addr_canonicalize(&self, human: &str) -> StdResult<CanonicalAddr> {
+            self.0.addr_canonicalize(human)
+        }
+
+        fn addr_humanize(&self, canonical: &CanonicalAddr) -> StdResult<Addr> {
+            if canonical.len() != 20 {
+                return self.0.addr_humanize(canonical);
+            }
+            let addr =
+                bech32::encode("ixo", canonical.as_slice().to_base32(), Variant::Bech32).unwrap();
+            Ok(Addr::unchecked(addr))
+        }
+
+        fn secp256k1_verify(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.secp256k1_verify(message_hash, signature, public_key)
+        }
+
+        fn secp256k1_recover_pubkey(
+            &self,
+            message_hash: &[u8],
+            signature: &[u8],
+            recovery_param: u8,
+        ) -> Result<Vec<u8>, RecoverPubkeyError> {
+            self.0
+                .secp256k1_recover_pubkey(message_hash, signature, recovery_param)
+        }
+
+        fn ed25519_verify(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+            public_key: &[u8],
+        ) -> Result<bool, VerificationError> {
+            self.0.ed25519_verify(message, signature, public_key)
+        }
+
+        fn ed25519_batch_verify(
+            &self,
+            messages: &[&[u8]],
+            signatures: &[&[u8]],
+            public_keys: &[&[u8]],
+        ) -> Result<bool, VerificationError> {
+            self.0
+                .ed25519_batch_verify(messages, signatures, public_keys)
+        }
+
+        fn debug(&self, message: &str) {
+            self.0.debug(message)
+        }
+    }
+
+    fn sign_permit(key: &SigningKey, env: &Env, msg: &ExecuteMsg) -> ExecuteMsg {
+        match msg.clone() {
+            ExecuteMsg::Permit {
+                owner_pubkey,
+                spender,
+                amount,
+                expires,
+                nonce,
+                ..
+            } => {
+                let sign_doc = PermitSignDoc {
+                    chain_id: env.block.chain_id.clone(),
+                    contract: env.contract.address.to_string(),
+                    spender: spender.clone(),
+                    amount,
+                    expires,
+                    nonce,
+                };
+                let owner = pubkey_to_address(&PermitApi(MockApi::default()), &owner_pubkey)
+                    .unwrap_or_else(|_| Addr::unchecked(""));
+                let sign_bytes = adr036_sign_bytes(&owner, &to_vec(&sign_doc).unwrap());
+                let signature: Signature = key.sign(&sign_bytes);
+                ExecuteMsg::Permit {
+                    owner_pubkey,
+                    spender,
+                    amount,
+                    expires,
+                    nonce,
+                    signature: signature.to_bytes().to_vec().into(),
+                }
+            }
+            _ => panic!("not a permit"),
+        }
+    }
+
+    #[test]
+    fn permit() {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: PermitApi(MockApi::default()),
+            querier: MockQuerier::default(),
+            custom_query_type: PhantomData::<Empty>,
+        };
+        let key = SigningKey::from_bytes(&[7u8; 32].into()).unwrap();
+        let owner_pubkey = Binary::from(key.verifying_key().to_encoded_point(true).as_bytes());
+        let owner = pubkey_to_address(&deps.api, &owner_pubkey)
+            .unwrap()
+            .to_string();
+        let spender = String::from("addr0002");
+        let relayer = mock_info("relayer", &[]);
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), &owner, Uint128::new(12340000));
+
+        assert_eq!(query_nonce(deps.as_ref(), owner.clone()).unwrap().nonce, 0);
+
+        // anyone can submit a permit signed by the owner
+        let allow1 = Uint128::new(7777);
+        let expires = Expiration::AtHeight(123_456);
+        let unsigned = ExecuteMsg::Permit {
+            owner_pubkey: owner_pubkey.clone(),
+            spender: spender.clone(),
+            amount: allow1,
+            expires: Some(expires),
+            nonce: 0,
+            signature: Binary::default(),
+        };
+        let msg = sign_permit(&key, &env, &unsigned);
+        execute(deps.as_mut(), env.clone(), relayer.clone(), msg.clone()).unwrap();
+        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: allow1,
+                expires
+            }
+        );
+        assert_eq!(query_nonce(deps.as_ref(), owner.clone()).unwrap().nonce, 1);
+
+        // the spender can use it right away
+        let info = mock_info(spender.as_ref(), &[]);
+        let msg2 = ExecuteMsg::TransferFrom {
+            owner: owner.clone(),
+            recipient: spender.clone(),
+            amount: Uint128::new(777),
+        };
+        execute(deps.as_mut(), env.clone(), info, msg2).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), &spender), Uint128::new(777));
+
+        // a permit can't be replayed
+        let err = execute(deps.as_mut(), env.clone(), relayer.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidNonce { expected: 1 });
+
+        // the signature must match the message and the chain
+        let lower = Uint128::new(1000);
+        let unsigned = ExecuteMsg::Permit {
+            owner_pubkey: owner_pubkey.clone(),
+            spender: spender.clone(),
+            amount: lower,
+            expires: None,
+            nonce: 1,
+            signature: Binary::default(),
+        };
+        let mut other_chain = env.clone();
+        other_chain.block.chain_id = "other-chain".to_string();
+        let msg = sign_permit(&key, &other_chain, &unsigned);
+        let err = execute(deps.as_mut(), env.clone(), relayer.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+        let other_key = SigningKey::from_bytes(&[8u8; 32].into()).unwrap();
+        let msg = sign_permit(&other_key, &env, &unsigned);
+        let err = execute(deps.as_mut(), env.clone(), relayer.clone(), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidSignature {});
+
+        // a permit replaces the previous allowance
+        let msg = sign_permit(&key, &env, &unsigned);
+        execute(deps.as_mut(), env.clone(), relayer.clone(), msg).unwrap();
+        let allowance = query_allowance(deps.as_ref(), owner.clone(), spender.clone()).unwrap();
+        assert_eq!(
+            allowance,
+            AllowanceResponse {
+                allowance: lower,
+                expires: Expiration::Never {}
+            }
+        );
+
+        // the owner key must be compressed
+        let unsigned = ExecuteMsg::Permit {
+            owner_pubkey: key
+                .verifying_key()
+                .to_encoded_point(false)
+                .as_bytes()
+                .into(),
+            spender,
+            amount: lower,
+            expires: None,
+            nonce: 2,
+            signature: Binary::default(),
+        };
+        let msg = sign_permit(&key, &env, &unsigned);
+        let err = execute(deps.as_mut(), env, relayer, msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidPubkey {});
+    }
+
+    #[test]
+    fn permit_signed_with_sign_arbitrary() {
+        let mut deps = OwnedDeps {
+            storage: MockStorage::default(),
+            api: PermitApi(MockApi::default()),
+            querier: MockQuerier::default(),
+            custom_query_type: PhantomData::<Empty>,
+        };
+        let owner = "ixo150rtrmj2f8vl9tem8qpfw36ylw5jg9j2vk5ma2";
+        let env = mock_env();
+        do_instantiate(deps.as_mut(), owner, Uint128::new(12340000));
+
+        // signArbitrary(chain_id, owner, json) of
+        // {"chain_id":"cosmos-testnet-14002","contract":"cosmos2contract","spender":"addr0002",
+        //  "amount":"7777","expires":{"at_height":123456},"nonce":0}
+        // with the secp256k1 key [7u8; 32], made outside of this crate
+        let msg = ExecuteMsg::Permit {
+            owner_pubkey: Binary::from_base64("ApicC3bLVjlx/cm+8x7AbDVg8ySdbunl2DxXYlWW4F9v")
+                .unwrap(),
+            spender: "addr0002".to_string(),
+            amount: Uint128::new(7777),
+            expires: Some(Expiration::AtHeight(123_456)),
+            nonce: 0,
+            signature: Binary::from_base64(
+                "+3OhMI9zS2OqRypWbtxVsZWGognMpYEL2Yofi6BY6jVMubbSNdfpgL1PUfqt7Ljt0xMy6hSwCCpzPVTM1e/RvQ==",
+            )
+            .unwrap(),
+        };
+        execute(deps.as_mut(), env, mock_info("relayer", &[]), msg).unwrap();
+        let allowance =
+            query_allowance(deps.as_ref(), owner.to_string(), "addr0002".to_string()).unwrap();
+        assert_eq!(allowance.allowance, Uint128::new(7777));
+    }
 }
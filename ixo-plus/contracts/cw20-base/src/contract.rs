@@ -2,26 +2,37 @@
 use cosmwasm_std::entry_point;
 use cosmwasm_std::Order::Ascending;
 use cosmwasm_std::{
-    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdError, StdResult,
+    Uint128,
 };
 
 use cw2::set_contract_version;
 use cw20::{
-    BalanceResponse, Cw20Coin, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
-    MarketingInfoResponse, MinterResponse, TokenInfoResponse,
+    BalanceResponse, Cw20ReceiveMsg, DownloadLogoResponse, EmbeddedLogo, Logo, LogoInfo,
+    MarketingInfoResponse, MinterResponse, TokenInfoResponse, VestingSchedule,
 };
-use cw_utils::ensure_from_older_version;
+use cw_utils::{ensure_from_older_version, maybe_addr};
 
 use crate::allowances::{
-    execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_send_from,
-    execute_transfer_from, query_allowance,
+    execute_burn_from, execute_decrease_allowance, execute_increase_allowance, execute_permit,
+    execute_send_from, execute_transfer_from, query_allowance, query_nonce,
 };
 use crate::enumerable::{query_all_accounts, query_owner_allowances, query_spender_allowances};
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::hooks::{
+    balance_changed_hooks, execute_add_hook, execute_remove_hook, execute_update_admin,
+    query_hooks, received, reply_hook_failed, sent,
+};
+use crate::msg::{
+    ExecuteMsg, InitialBalance, InstantiateMsg, MigrateMsg, QueryMsg, TotalSupplyAtHeightResponse,
+};
 use crate::state::{
-    MinterData, TokenInfo, ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, LOGO, MARKETING_INFO,
-    TOKEN_INFO,
+    snapshot_total_supply, snapshots_enabled, update_balance, MinterData, TokenInfo, ADMIN,
+    ALLOWANCES, ALLOWANCES_SPENDER, BALANCES, BALANCE_SNAPSHOTS, CAPABILITIES, LOGO,
+    MARKETING_INFO, SNAPSHOTS, TOKEN_INFO, TOTAL_SUPPLY_SNAPSHOTS,
+};
+use crate::vesting::{
+    add_vesting, assert_unlocked, query_unlocked_balance, query_vesting_schedule,
 };
 
 // version info for migration info
@@ -94,15 +105,19 @@ fn verify_logo(logo: &Logo) -> Result<(), ContractError> {
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     // check valid token info
     msg.validate()?;
+    SNAPSHOTS.save(deps.storage, &msg.snapshots.unwrap_or_default())?;
+    CAPABILITIES.save(deps.storage, &msg.capabilities.clone().unwrap_or_default())?;
+    let admin = maybe_addr(deps.api, msg.admin.clone())?;
+    ADMIN.set(deps.branch(), admin)?;
     // create initial accounts
-    let total_supply = create_accounts(&mut deps, &msg.initial_balances)?;
+    let total_supply = create_accounts(&mut deps, &env, &msg.initial_balances)?;
 
     if let Some(limit) = msg.get_cap() {
         if total_supply > limit {
@@ -127,6 +142,7 @@ pub fn instantiate(
         mint,
     };
     TOKEN_INFO.save(deps.storage, &data)?;
+    snapshot_total_supply(deps.storage, total_supply, env.block.height)?;
 
     if let Some(marketing) = msg.marketing {
         let logo = if let Some(logo) = marketing.logo {
@@ -158,21 +174,25 @@ pub fn instantiate(
 
 pub fn create_accounts(
     deps: &mut DepsMut,
-    accounts: &[Cw20Coin],
+    env: &Env,
+    accounts: &[InitialBalance],
 ) -> Result<Uint128, ContractError> {
     validate_accounts(accounts)?;
 
     let mut total_supply = Uint128::zero();
     for row in accounts {
         let address = deps.api.addr_validate(&row.address)?;
-        BALANCES.save(deps.storage, &address, &row.amount)?;
+        update_balance(deps.storage, &address, env.block.height, |_| Ok(row.amount))?;
+        if let Some(schedule) = row.vesting.clone() {
+            add_vesting(deps.storage, &address, row.amount, schedule, &env.block)?;
+        }
         total_supply += row.amount;
     }
 
     Ok(total_supply)
 }
 
-pub fn validate_accounts(accounts: &[Cw20Coin]) -> Result<(), ContractError> {
+pub fn validate_accounts(accounts: &[InitialBalance]) -> Result<(), ContractError> {
     let mut addresses = accounts.iter().map(|c| &c.address).collect::<Vec<_>>();
     addresses.sort();
     addresses.dedup();
@@ -201,7 +221,11 @@ pub fn execute(
             amount,
             msg,
         } => execute_send(deps, env, info, contract, amount, msg),
-        ExecuteMsg::Mint { recipient, amount } => execute_mint(deps, env, info, recipient, amount),
+        ExecuteMsg::Mint {
+            recipient,
+            amount,
+            vesting,
+        } => execute_mint(deps, env, info, recipient, amount, vesting),
         ExecuteMsg::IncreaseAllowance {
             spender,
             amount,
@@ -212,6 +236,23 @@ pub fn execute(
             amount,
             expires,
         } => execute_decrease_allowance(deps, env, info, spender, amount, expires),
+        ExecuteMsg::Permit {
+            owner_pubkey,
+            spender,
+            amount,
+            expires,
+            nonce,
+            signature,
+        } => execute_permit(
+            deps,
+            env,
+            owner_pubkey,
+            spender,
+            amount,
+            expires,
+            nonce,
+            signature,
+        ),
         ExecuteMsg::TransferFrom {
             owner,
             recipient,
@@ -233,12 +274,15 @@ pub fn execute(
         ExecuteMsg::UpdateMinter { new_minter } => {
             execute_update_minter(deps, env, info, new_minter)
         }
+        ExecuteMsg::UpdateAdmin { admin } => execute_update_admin(deps, info, admin),
+        ExecuteMsg::AddHook { addr, gas_limit } => execute_add_hook(deps, info, addr, gas_limit),
+        ExecuteMsg::RemoveHook { addr } => execute_remove_hook(deps, info, addr),
     }
 }
 
 pub fn execute_transfer(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
@@ -249,64 +293,84 @@ pub fn execute_transfer(
 
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
 
-    BALANCES.update(
+    assert_unlocked(deps.storage, &info.sender, amount, &env.block)?;
+    let sender_balance = update_balance(
         deps.storage,
         &info.sender,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let rcpt_balance = update_balance(
         deps.storage,
         &rcpt_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
+    let hooks = balance_changed_hooks(
+        deps.storage,
+        vec![
+            sent(&info.sender, sender_balance, amount),
+            received(&rcpt_addr, rcpt_balance, amount),
+        ],
+    )?;
 
     let res = Response::new()
         .add_attribute("action", "transfer")
         .add_attribute("from", info.sender)
         .add_attribute("to", recipient)
-        .add_attribute("amount", amount);
+        .add_attribute("amount", amount)
+        .add_submessages(hooks);
     Ok(res)
 }
 
 pub fn execute_burn(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     amount: Uint128,
 ) -> Result<Response, ContractError> {
     if amount == Uint128::zero() {
         return Err(ContractError::InvalidZeroAmount {});
     }
+    if !CAPABILITIES.load(deps.storage)?.holder_burn {
+        return Err(ContractError::HolderBurnDisabled {});
+    }
 
+    assert_unlocked(deps.storage, &info.sender, amount, &env.block)?;
     // lower balance
-    BALANCES.update(
+    let balance = update_balance(
         deps.storage,
         &info.sender,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
     // reduce total_supply
-    TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
+    let token_info = TOKEN_INFO.update(deps.storage, |mut info| -> StdResult<_> {
         info.total_supply = info.total_supply.checked_sub(amount)?;
         Ok(info)
     })?;
+    snapshot_total_supply(deps.storage, token_info.total_supply, env.block.height)?;
+    let hooks = balance_changed_hooks(deps.storage, vec![sent(&info.sender, balance, amount)])?;
 
     let res = Response::new()
         .add_attribute("action", "burn")
         .add_attribute("from", info.sender)
-        .add_attribute("amount", amount);
+        .add_attribute("amount", amount)
+        .add_submessages(hooks);
     Ok(res)
 }
 
 pub fn execute_mint(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
+    vesting: Option<VestingSchedule>,
 ) -> Result<Response, ContractError> {
     if amount == Uint128::zero() {
         return Err(ContractError::InvalidZeroAmount {});
@@ -334,25 +398,32 @@ pub fn execute_mint(
         }
     }
     TOKEN_INFO.save(deps.storage, &config)?;
+    snapshot_total_supply(deps.storage, config.total_supply, env.block.height)?;
 
     // add amount to recipient balance
     let rcpt_addr = deps.api.addr_validate(&recipient)?;
-    BALANCES.update(
+    if let Some(schedule) = vesting {
+        add_vesting(deps.storage, &rcpt_addr, amount, schedule, &env.block)?;
+    }
+    let balance = update_balance(
         deps.storage,
         &rcpt_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
+    let hooks = balance_changed_hooks(deps.storage, vec![received(&rcpt_addr, balance, amount)])?;
 
     let res = Response::new()
         .add_attribute("action", "mint")
         .add_attribute("to", recipient)
-        .add_attribute("amount", amount);
+        .add_attribute("amount", amount)
+        .add_submessages(hooks);
     Ok(res)
 }
 
 pub fn execute_send(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     contract: String,
     amount: Uint128,
@@ -364,19 +435,29 @@ pub fn execute_send(
 
     let rcpt_addr = deps.api.addr_validate(&contract)?;
 
+    assert_unlocked(deps.storage, &info.sender, amount, &env.block)?;
     // move the tokens to the contract
-    BALANCES.update(
+    let sender_balance = update_balance(
         deps.storage,
         &info.sender,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> {
             Ok(balance.unwrap_or_default().checked_sub(amount)?)
         },
     )?;
-    BALANCES.update(
+    let rcpt_balance = update_balance(
         deps.storage,
         &rcpt_addr,
+        env.block.height,
         |balance: Option<Uint128>| -> StdResult<_> { Ok(balance.unwrap_or_default() + amount) },
     )?;
+    let hooks = balance_changed_hooks(
+        deps.storage,
+        vec![
+            sent(&info.sender, sender_balance, amount),
+            received(&rcpt_addr, rcpt_balance, amount),
+        ],
+    )?;
 
     let res = Response::new()
         .add_attribute("action", "send")
@@ -390,7 +471,8 @@ pub fn execute_send(
                 msg,
             }
             .into_cosmos_msg(contract)?,
-        );
+        )
+        .add_submessages(hooks);
     Ok(res)
 }
 
@@ -521,14 +603,30 @@ pub fn execute_upload_logo(
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Balance { address } => to_binary(&query_balance(deps, address)?),
+        QueryMsg::BalanceAtHeight { address, height } => {
+            to_binary(&query_balance_at_height(deps, address, height)?)
+        }
+        QueryMsg::VestingSchedule { address } => {
+            to_binary(&query_vesting_schedule(deps, env, address)?)
+        }
+        QueryMsg::UnlockedBalance { address } => {
+            to_binary(&query_unlocked_balance(deps, env, address)?)
+        }
         QueryMsg::TokenInfo {} => to_binary(&query_token_info(deps)?),
+        QueryMsg::TotalSupplyAtHeight { height } => {
+            to_binary(&query_total_supply_at_height(deps, height)?)
+        }
+        QueryMsg::Capabilities {} => to_binary(&CAPABILITIES.load(deps.storage)?),
+        QueryMsg::Admin {} => to_binary(&ADMIN.query_admin(deps)?),
+        QueryMsg::Hooks {} => to_binary(&query_hooks(deps)?),
         QueryMsg::Minter {} => to_binary(&query_minter(deps)?),
         QueryMsg::Allowance { owner, spender } => {
             to_binary(&query_allowance(deps, owner, spender)?)
         }
+        QueryMsg::Nonce { owner } => to_binary(&query_nonce(deps, owner)?),
         QueryMsg::AllAllowances {
             owner,
             start_after,
@@ -560,6 +658,52 @@ pub fn query_balance(deps: Deps, address: String) -> StdResult<BalanceResponse>
     Ok(BalanceResponse { balance })
 }
 
+/// Returns the balance at the beginning of block `height`, so that tokens moved within the same
+/// block, e.g. with a flash loan, aren't counted. Errors if snapshots are disabled.
+pub fn query_balance_at_height(
+    deps: Deps,
+    address: String,
+    height: Option<u64>,
+) -> StdResult<BalanceResponse> {
+    let height = match height {
+        Some(height) => height,
+        None => return query_balance(deps, address),
+    };
+    guard_snapshots_enabled(deps)?;
+    let address = deps.api.addr_validate(&address)?;
+    let balance = BALANCE_SNAPSHOTS
+        .may_load_at_height(deps.storage, &address, height)?
+        .unwrap_or_default();
+    Ok(BalanceResponse { balance })
+}
+
+/// Returns the total supply at the beginning of block `height`. Errors if snapshots are disabled.
+pub fn query_total_supply_at_height(
+    deps: Deps,
+    height: Option<u64>,
+) -> StdResult<TotalSupplyAtHeightResponse> {
+    let total_supply = match height {
+        Some(height) => {
+            guard_snapshots_enabled(deps)?;
+            TOTAL_SUPPLY_SNAPSHOTS
+                .may_load_at_height(deps.storage, height)?
+                .unwrap_or_default()
+        }
+        None => TOKEN_INFO.load(deps.storage)?.total_supply,
+    };
+    Ok(TotalSupplyAtHeightResponse { total_supply })
+}
+
+fn guard_snapshots_enabled(deps: Deps) -> StdResult<()> {
+    if snapshots_enabled(deps.storage)? {
+        Ok(())
+    } else {
+        Err(StdError::generic_err(
+            "Snapshots are not enabled for this token",
+        ))
+    }
+}
+
 pub fn query_token_info(deps: Deps) -> StdResult<TokenInfoResponse> {
     let info = TOKEN_INFO.load(deps.storage)?;
     let res = TokenInfoResponse {
@@ -603,7 +747,7 @@ pub fn query_download_logo(deps: Deps) -> StdResult<DownloadLogoResponse> {
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(mut deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
     let original_version =
         ensure_from_older_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
@@ -616,18 +760,37 @@ pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, C
             ALLOWANCES_SPENDER.save(deps.storage, (&spender, &owner), &allowance)?;
         }
     }
+    let capabilities = match msg.capabilities {
+        Some(capabilities) => capabilities,
+        None => CAPABILITIES.may_load(deps.storage)?.unwrap_or_default(),
+    };
+    CAPABILITIES.save(deps.storage, &capabilities)?;
+    if let Some(admin) = msg.admin {
+        let admin = deps.api.addr_validate(&admin)?;
+        ADMIN.set(deps.branch(), Some(admin))?;
+    }
     Ok(Response::default())
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    reply_hook_failed(deps, env, msg)
+}
+
 #[cfg(test)]
 mod tests {
     use cosmwasm_std::testing::{
         mock_dependencies, mock_dependencies_with_balance, mock_env, mock_info,
     };
-    use cosmwasm_std::{coins, from_binary, Addr, CosmosMsg, StdError, SubMsg, WasmMsg};
+    use cosmwasm_std::{
+        attr, coins, from_binary, Addr, CosmosMsg, StdError, SubMsg, SubMsgResult, WasmMsg,
+    };
+    use cw20::{BalanceChangedHookMsg, BalanceDiff};
+    use cw_controllers::{AdminError, AdminResponse, HookError, HooksResponse};
 
     use super::*;
-    use crate::msg::InstantiateMarketingInfo;
+    use crate::hooks::DEFAULT_HOOK_GAS_LIMIT;
+    use crate::msg::{Capabilities, InstantiateMarketingInfo};
 
     fn get_balance<T: Into<String>>(deps: Deps, address: T) -> Uint128 {
         query_balance(deps, address.into()).unwrap().balance
@@ -668,12 +831,16 @@ mod tests {
             name: "Auto Gen".to_string(),
             symbol: "AUTO".to_string(),
             decimals: 3,
-            initial_balances: vec![Cw20Coin {
+            initial_balances: vec![InitialBalance {
                 address: addr.to_string(),
                 amount,
+                vesting: None,
             }],
             mint: mint.clone(),
             marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -708,12 +875,16 @@ mod tests {
                 name: "Cash Token".to_string(),
                 symbol: "CASH".to_string(),
                 decimals: 9,
-                initial_balances: vec![Cw20Coin {
+                initial_balances: vec![InitialBalance {
                     address: String::from("addr0000"),
                     amount,
+                    vesting: None,
                 }],
                 mint: None,
                 marketing: None,
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
             let info = mock_info("creator", &[]);
             let env = mock_env();
@@ -745,15 +916,19 @@ mod tests {
                 name: "Cash Token".to_string(),
                 symbol: "CASH".to_string(),
                 decimals: 9,
-                initial_balances: vec![Cw20Coin {
+                initial_balances: vec![InitialBalance {
                     address: "addr0000".into(),
                     amount,
+                    vesting: None,
                 }],
                 mint: Some(MinterResponse {
                     minter: minter.clone(),
                     cap: Some(limit),
                 }),
                 marketing: None,
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
             let info = mock_info("creator", &[]);
             let env = mock_env();
@@ -792,15 +967,19 @@ mod tests {
                 name: "Cash Token".to_string(),
                 symbol: "CASH".to_string(),
                 decimals: 9,
-                initial_balances: vec![Cw20Coin {
+                initial_balances: vec![InitialBalance {
                     address: String::from("addr0000"),
                     amount,
+                    vesting: None,
                 }],
                 mint: Some(MinterResponse {
                     minter,
                     cap: Some(limit),
                 }),
                 marketing: None,
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
             let info = mock_info("creator", &[]);
             let env = mock_env();
@@ -829,6 +1008,9 @@ mod tests {
                         marketing: Some("marketing".to_owned()),
                         logo: Some(Logo::Url("url".to_owned())),
                     }),
+                    snapshots: None,
+                    capabilities: None,
+                    admin: None,
                 };
 
                 let info = mock_info("creator", &[]);
@@ -869,6 +1051,9 @@ mod tests {
                         marketing: Some("m".to_owned()),
                         logo: Some(Logo::Url("url".to_owned())),
                     }),
+                    snapshots: None,
+                    capabilities: None,
+                    admin: None,
                 };
 
                 let info = mock_info("creator", &[]);
@@ -901,6 +1086,7 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             recipient: winner.clone(),
             amount: prize,
+            vesting: None,
         };
 
         let info = mock_info(minter.as_ref(), &[]);
@@ -914,6 +1100,7 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             recipient: winner.clone(),
             amount: Uint128::zero(),
+            vesting: None,
         };
         let info = mock_info(minter.as_ref(), &[]);
         let env = mock_env();
@@ -925,6 +1112,7 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             recipient: winner,
             amount: Uint128::new(333_222_222),
+            vesting: None,
         };
         let info = mock_info(minter.as_ref(), &[]);
         let env = mock_env();
@@ -946,6 +1134,7 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             recipient: String::from("lucky"),
             amount: Uint128::new(222),
+            vesting: None,
         };
         let info = mock_info("anyone else", &[]);
         let env = mock_env();
@@ -1036,6 +1225,7 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             recipient: String::from("lucky"),
             amount: Uint128::new(222),
+            vesting: None,
         };
         let info = mock_info("minter", &[]);
         let env = mock_env();
@@ -1051,6 +1241,7 @@ mod tests {
         let msg = ExecuteMsg::Mint {
             recipient: String::from("lucky"),
             amount: Uint128::new(222),
+            vesting: None,
         };
         let info = mock_info("genesis", &[]);
         let env = mock_env();
@@ -1074,17 +1265,22 @@ mod tests {
             symbol: "BASH".to_string(),
             decimals: 6,
             initial_balances: vec![
-                Cw20Coin {
+                InitialBalance {
                     address: addr1.clone(),
                     amount: amount1,
+                    vesting: None,
                 },
-                Cw20Coin {
+                InitialBalance {
                     address: addr1.clone(),
                     amount: amount2,
+                    vesting: None,
                 },
             ],
             mint: None,
             marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: None,
         };
         let err =
             instantiate(deps.as_mut(), env.clone(), info.clone(), instantiate_msg).unwrap_err();
@@ -1096,17 +1292,22 @@ mod tests {
             symbol: "BASH".to_string(),
             decimals: 6,
             initial_balances: vec![
-                Cw20Coin {
+                InitialBalance {
                     address: addr1.clone(),
                     amount: amount1,
+                    vesting: None,
                 },
-                Cw20Coin {
+                InitialBalance {
                     address: addr2.clone(),
                     amount: amount2,
+                    vesting: None,
                 },
             ],
             mint: None,
             marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: None,
         };
         let res = instantiate(deps.as_mut(), env, info, instantiate_msg).unwrap();
         assert_eq!(0, res.messages.len());
@@ -1343,6 +1544,542 @@ mod tests {
         );
     }
 
+    #[test]
+    fn snapshots() {
+        let mut deps = mock_dependencies();
+        let addr1 = String::from("addr0001");
+        let addr2 = String::from("addr0002");
+        let minter = String::from("minter");
+        let amount1 = Uint128::from(1000u128);
+
+        let mut env = mock_env();
+        let start = env.block.height;
+        let instantiate_msg = InstantiateMsg {
+            name: "Voting Coin".to_string(),
+            symbol: "VOTE".to_string(),
+            decimals: 6,
+            initial_balances: vec![InitialBalance {
+                address: addr1.clone(),
+                amount: amount1,
+                vesting: None,
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.clone(),
+                cap: None,
+            }),
+            marketing: None,
+            snapshots: Some(true),
+            capabilities: None,
+            admin: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info, instantiate_msg).unwrap();
+
+        // several changes within the same block
+        env.block.height = start + 1;
+        let msg = ExecuteMsg::Transfer {
+            recipient: addr2.clone(),
+            amount: Uint128::new(300),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&addr1, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Mint {
+            recipient: addr2.clone(),
+            amount: Uint128::new(500),
+            vesting: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap();
+
+        env.block.height = start + 5;
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+        };
+        execute(deps.as_mut(), env, mock_info(&addr2, &[]), msg).unwrap();
+
+        let balance_at = |deps: Deps, address: &str, height: Option<u64>| {
+            query_balance_at_height(deps, address.to_string(), height)
+                .unwrap()
+                .balance
+                .u128()
+        };
+        let supply_at = |deps: Deps, height: Option<u64>| {
+            query_total_supply_at_height(deps, height)
+                .unwrap()
+                .total_supply
+                .u128()
+        };
+
+        // the balances at a height don't count the changes of that block
+        assert_eq!(balance_at(deps.as_ref(), &addr1, Some(start)), 0);
+        assert_eq!(balance_at(deps.as_ref(), &addr1, Some(start + 1)), 1000);
+        assert_eq!(balance_at(deps.as_ref(), &addr1, Some(start + 2)), 700);
+        assert_eq!(balance_at(deps.as_ref(), &addr2, Some(start + 1)), 0);
+        assert_eq!(balance_at(deps.as_ref(), &addr2, Some(start + 5)), 800);
+        assert_eq!(balance_at(deps.as_ref(), &addr2, Some(start + 6)), 700);
+        assert_eq!(balance_at(deps.as_ref(), &addr2, None), 700);
+
+        assert_eq!(supply_at(deps.as_ref(), Some(start)), 0);
+        assert_eq!(supply_at(deps.as_ref(), Some(start + 1)), 1000);
+        assert_eq!(supply_at(deps.as_ref(), Some(start + 3)), 1500);
+        assert_eq!(supply_at(deps.as_ref(), Some(start + 6)), 1400);
+        assert_eq!(supply_at(deps.as_ref(), None), 1400);
+
+        // without snapshots, only the current values can be queried
+        let mut deps = mock_dependencies();
+        do_instantiate(deps.as_mut(), &addr1, amount1);
+        assert_eq!(balance_at(deps.as_ref(), &addr1, None), 1000);
+        assert_eq!(supply_at(deps.as_ref(), None), 1000);
+        let err = query_balance_at_height(deps.as_ref(), addr1, Some(start + 1)).unwrap_err();
+        assert_eq!(
+            err,
+            StdError::generic_err("Snapshots are not enabled for this token")
+        );
+        query_total_supply_at_height(deps.as_ref(), Some(start + 1)).unwrap_err();
+    }
+
+    #[test]
+    fn vesting() {
+        let mut deps = mock_dependencies();
+        let team = String::from("team");
+        let investor = String::from("investor");
+        let minter = String::from("minter");
+
+        let mut env = mock_env();
+        let start = env.block.time;
+        let instantiate_msg = InstantiateMsg {
+            name: "Dao Coin".to_string(),
+            symbol: "DAO".to_string(),
+            decimals: 6,
+            initial_balances: vec![InitialBalance {
+                address: team.clone(),
+                amount: Uint128::new(1000),
+                vesting: Some(VestingSchedule::Linear {
+                    start,
+                    end: start.plus_seconds(1000),
+                    cliff: Some(start.plus_seconds(100)),
+                }),
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.clone(),
+                cap: None,
+            }),
+            marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), env.clone(), info, instantiate_msg).unwrap();
+
+        // locked tokens count in the balance, but can't move before the cliff
+        assert_eq!(get_balance(deps.as_ref(), &team), Uint128::new(1000));
+        let unlocked = query_unlocked_balance(deps.as_ref(), env.clone(), team.clone()).unwrap();
+        assert_eq!(unlocked.balance, Uint128::zero());
+        let msg = ExecuteMsg::Transfer {
+            recipient: investor.clone(),
+            amount: Uint128::new(1),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&team, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+
+        // a quarter is released a quarter of the way
+        env.block.time = start.plus_seconds(250);
+        let vesting = query_vesting_schedule(deps.as_ref(), env.clone(), team.clone()).unwrap();
+        assert_eq!(vesting.amount, Uint128::new(1000));
+        assert_eq!(vesting.locked, Uint128::new(750));
+        let msg = ExecuteMsg::Transfer {
+            recipient: investor.clone(),
+            amount: Uint128::new(251),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&team, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(250),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&team, &[]), msg).unwrap();
+        let unlocked = query_unlocked_balance(deps.as_ref(), env.clone(), team.clone()).unwrap();
+        assert_eq!(unlocked.balance, Uint128::zero());
+
+        // minted tokens can be locked as well, on top of free ones
+        let msg = ExecuteMsg::Mint {
+            recipient: investor.clone(),
+            amount: Uint128::new(100),
+            vesting: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap();
+        let cliff = VestingSchedule::Cliff {
+            release_at: start.plus_seconds(500),
+        };
+        let msg = ExecuteMsg::Mint {
+            recipient: investor.clone(),
+            amount: Uint128::new(400),
+            vesting: Some(cliff.clone()),
+        };
+        execute(
+            deps.as_mut(),
+            env.clone(),
+            mock_info(&minter, &[]),
+            msg.clone(),
+        )
+        .unwrap();
+        assert_eq!(get_balance(deps.as_ref(), &investor), Uint128::new(500));
+        let vesting = query_vesting_schedule(deps.as_ref(), env.clone(), investor.clone()).unwrap();
+        assert_eq!(vesting.schedule, Some(cliff));
+        assert_eq!(vesting.locked, Uint128::new(400));
+
+        // only one schedule can lock the tokens of an address
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::VestingScheduleExists {});
+
+        // the free tokens move as usual, including with allowances
+        let msg = ExecuteMsg::Transfer {
+            recipient: team.clone(),
+            amount: Uint128::new(60),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&investor, &[]), msg).unwrap();
+        let msg = ExecuteMsg::IncreaseAllowance {
+            spender: team.clone(),
+            amount: Uint128::new(100),
+            expires: None,
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&investor, &[]), msg).unwrap();
+        let msg = ExecuteMsg::TransferFrom {
+            owner: investor.clone(),
+            recipient: team.clone(),
+            amount: Uint128::new(41),
+        };
+        let err = execute(deps.as_mut(), env.clone(), mock_info(&team, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::LockedTokens {});
+
+        // everything moves once released
+        env.block.time = start.plus_seconds(1000);
+        let msg = ExecuteMsg::TransferFrom {
+            owner: investor.clone(),
+            recipient: team.clone(),
+            amount: Uint128::new(40),
+        };
+        execute(deps.as_mut(), env.clone(), mock_info(&team, &[]), msg).unwrap();
+        let unlocked = query_unlocked_balance(deps.as_ref(), env.clone(), team.clone()).unwrap();
+        assert_eq!(unlocked.balance, Uint128::new(850));
+        let vesting = query_vesting_schedule(deps.as_ref(), env, team).unwrap();
+        assert_eq!(vesting.locked, Uint128::zero());
+
+        // schedules must release the locked amount
+        let msg = ExecuteMsg::Mint {
+            recipient: minter.clone(),
+            amount: Uint128::new(100),
+            vesting: Some(VestingSchedule::Steps { steps: vec![] }),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::InvalidVestingSchedule {});
+    }
+
+    #[test]
+    fn lp_capabilities() {
+        let mut deps = mock_dependencies();
+        let holder = String::from("holder");
+        let minter = String::from("pool");
+        let spender = String::from("spender");
+        let lp = Capabilities {
+            holder_burn: false,
+            minter_only_burn_from: true,
+        };
+        let instantiate_msg = InstantiateMsg {
+            name: "Liquidity Token".to_string(),
+            symbol: "LPT".to_string(),
+            decimals: 6,
+            initial_balances: vec![InitialBalance {
+                address: holder.clone(),
+                amount: Uint128::new(1000),
+                vesting: None,
+            }],
+            mint: Some(MinterResponse {
+                minter: minter.clone(),
+                cap: None,
+            }),
+            marketing: None,
+            snapshots: None,
+            capabilities: Some(lp.clone()),
+            admin: None,
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+        let capabilities: Capabilities =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Capabilities {}).unwrap())
+                .unwrap();
+        assert_eq!(capabilities, lp);
+
+        // holders can't burn
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&holder, &[]), msg).unwrap_err();
+        assert_eq!(err, ContractError::HolderBurnDisabled {});
+
+        // only the minter burns from an allowance
+        for spender in [&spender, &minter] {
+            let msg = ExecuteMsg::IncreaseAllowance {
+                spender: spender.clone(),
+                amount: Uint128::new(100),
+                expires: None,
+            };
+            execute(deps.as_mut(), mock_env(), mock_info(&holder, &[]), msg).unwrap();
+        }
+        let msg = ExecuteMsg::BurnFrom {
+            owner: holder.clone(),
+            amount: Uint128::new(100),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&spender, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(deps.as_mut(), mock_env(), mock_info(&minter, &[]), msg).unwrap();
+        assert_eq!(get_balance(deps.as_ref(), &holder), Uint128::new(900));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(900)
+        );
+    }
+
+    #[test]
+    fn capabilities_are_independent() {
+        let holder = String::from("holder");
+        let minter = String::from("pool");
+        let spender = String::from("spender");
+        let instantiate_with = |capabilities| {
+            let mut deps = mock_dependencies();
+            let instantiate_msg = InstantiateMsg {
+                name: "Some Token".to_string(),
+                symbol: "SOME".to_string(),
+                decimals: 6,
+                initial_balances: vec![InitialBalance {
+                    address: holder.clone(),
+                    amount: Uint128::new(1000),
+                    vesting: None,
+                }],
+                mint: Some(MinterResponse {
+                    minter: minter.clone(),
+                    cap: None,
+                }),
+                marketing: None,
+                snapshots: None,
+                capabilities: Some(capabilities),
+                admin: None,
+            };
+            let info = mock_info("creator", &[]);
+            instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+            for spender in [&spender, &minter] {
+                let msg = ExecuteMsg::IncreaseAllowance {
+                    spender: spender.clone(),
+                    amount: Uint128::new(100),
+                    expires: None,
+                };
+                execute(deps.as_mut(), mock_env(), mock_info(&holder, &[]), msg).unwrap();
+            }
+            deps
+        };
+        let burn = ExecuteMsg::Burn {
+            amount: Uint128::new(10),
+        };
+        let burn_from = ExecuteMsg::BurnFrom {
+            owner: holder.clone(),
+            amount: Uint128::new(10),
+        };
+
+        // holders can't burn, but anyone burns from an allowance
+        let mut deps = instantiate_with(Capabilities {
+            holder_burn: false,
+            minter_only_burn_from: false,
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&holder, &[]),
+            burn.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::HolderBurnDisabled {});
+        for sender in [&spender, &minter] {
+            let info = mock_info(sender, &[]);
+            execute(deps.as_mut(), mock_env(), info, burn_from.clone()).unwrap();
+        }
+        assert_eq!(get_balance(deps.as_ref(), &holder), Uint128::new(980));
+
+        // holders burn, but only the minter burns from an allowance
+        let mut deps = instantiate_with(Capabilities {
+            holder_burn: true,
+            minter_only_burn_from: true,
+        });
+        execute(deps.as_mut(), mock_env(), mock_info(&holder, &[]), burn).unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&spender, &[]),
+            burn_from.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&minter, &[]),
+            burn_from,
+        )
+        .unwrap();
+        assert_eq!(get_balance(deps.as_ref(), &holder), Uint128::new(980));
+        assert_eq!(
+            query_token_info(deps.as_ref()).unwrap().total_supply,
+            Uint128::new(980)
+        );
+    }
+
+    #[test]
+    fn hooks() {
+        let mut deps = mock_dependencies();
+        let admin = String::from("admin");
+        let holder = String::from("holder");
+        let rcpt = String::from("rcpt");
+        let hook = String::from("staking");
+        let instantiate_msg = InstantiateMsg {
+            name: "Hooked Token".to_string(),
+            symbol: "HOOK".to_string(),
+            decimals: 6,
+            initial_balances: vec![InitialBalance {
+                address: holder.clone(),
+                amount: Uint128::new(1000),
+                vesting: None,
+            }],
+            mint: Some(MinterResponse {
+                minter: admin.clone(),
+                cap: None,
+            }),
+            marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: Some(admin.clone()),
+        };
+        let info = mock_info("creator", &[]);
+        instantiate(deps.as_mut(), mock_env(), info, instantiate_msg).unwrap();
+
+        // only the admin adds hooks
+        let msg = ExecuteMsg::AddHook {
+            addr: hook.clone(),
+            gas_limit: Some(100_000),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info(&holder, &[]),
+            msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, HookError::Admin(AdminError::NotAdmin {}).into());
+        execute(deps.as_mut(), mock_env(), mock_info(&admin, &[]), msg).unwrap();
+        let hooks: HooksResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap()).unwrap();
+        assert_eq!(hooks.hooks, vec![hook.clone()]);
+
+        // transfers call the hook with the balance diffs
+        let msg = ExecuteMsg::Transfer {
+            recipient: rcpt.clone(),
+            amount: Uint128::new(300),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&holder, &[]), msg).unwrap();
+        let hook_msg = BalanceChangedHookMsg::new(vec![
+            BalanceDiff::new(&holder, Uint128::new(1000), Uint128::new(700)),
+            BalanceDiff::new(&rcpt, Uint128::zero(), Uint128::new(300)),
+        ]);
+        let mut expected = SubMsg::reply_on_error(hook_msg.into_cosmos_msg(&hook).unwrap(), 0);
+        expected.gas_limit = Some(100_000);
+        assert_eq!(res.messages, vec![expected]);
+
+        // so do mints and burns
+        let msg = ExecuteMsg::Mint {
+            recipient: rcpt.clone(),
+            amount: Uint128::new(50),
+            vesting: None,
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&admin, &[]), msg).unwrap();
+        let hook_msg = BalanceChangedHookMsg::new(vec![BalanceDiff::new(
+            &rcpt,
+            Uint128::new(300),
+            Uint128::new(350),
+        )]);
+        assert_eq!(
+            res.messages[0].msg,
+            hook_msg.into_cosmos_msg(&hook).unwrap()
+        );
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(100),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&rcpt, &[]), msg).unwrap();
+        let hook_msg = BalanceChangedHookMsg::new(vec![BalanceDiff::new(
+            &rcpt,
+            Uint128::new(350),
+            Uint128::new(250),
+        )]);
+        assert_eq!(
+            res.messages[0].msg,
+            hook_msg.into_cosmos_msg(&hook).unwrap()
+        );
+
+        // a failing hook is removed
+        let reply_msg = Reply {
+            id: 0,
+            result: SubMsgResult::Err("out of gas".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert_eq!(res.attributes[0], attr("action", "remove_hook"));
+        let hooks: HooksResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Hooks {}).unwrap()).unwrap();
+        assert!(hooks.hooks.is_empty());
+        let msg = ExecuteMsg::Transfer {
+            recipient: holder.clone(),
+            amount: Uint128::new(100),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&rcpt, &[]), msg).unwrap();
+        assert!(res.messages.is_empty());
+
+        // hooks added without a gas limit get the default one, and a new reply id
+        let msg = ExecuteMsg::AddHook {
+            addr: hook.clone(),
+            gas_limit: None,
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(&admin, &[]), msg).unwrap();
+        let msg = ExecuteMsg::Burn {
+            amount: Uint128::new(50),
+        };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(&rcpt, &[]), msg).unwrap();
+        assert_eq!(res.messages[0].id, 1);
+        assert_eq!(res.messages[0].gas_limit, Some(DEFAULT_HOOK_GAS_LIMIT));
+
+        // the failure of a removed hook is ignored
+        let msg = ExecuteMsg::RemoveHook { addr: hook.clone() };
+        execute(deps.as_mut(), mock_env(), mock_info(&admin, &[]), msg).unwrap();
+        let reply_msg = Reply {
+            id: 1,
+            result: SubMsgResult::Err("out of gas".to_string()),
+        };
+        let res = reply(deps.as_mut(), mock_env(), reply_msg).unwrap();
+        assert!(res.attributes.is_empty());
+
+        // the admin can give up managing hooks
+        let msg = ExecuteMsg::UpdateAdmin { admin: None };
+        execute(deps.as_mut(), mock_env(), mock_info(&admin, &[]), msg).unwrap();
+        let res: AdminResponse =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Admin {}).unwrap()).unwrap();
+        assert_eq!(res.admin, None);
+        let msg = ExecuteMsg::AddHook {
+            addr: hook,
+            gas_limit: None,
+        };
+        let err = execute(deps.as_mut(), mock_env(), mock_info(&admin, &[]), msg).unwrap_err();
+        assert_eq!(err, HookError::Admin(AdminError::NotAdmin {}).into());
+    }
+
     mod migration {
         use super::*;
 
@@ -1363,7 +2100,7 @@ mod tests {
 
         #[test]
         fn test_migrate() {
-            let mut app = App::default();
+            let app = App::default();
 
             let cw20_id = app.store_code(cw20_contract());
             let cw20_addr = app
@@ -1374,12 +2111,16 @@ mod tests {
                         name: "Token".to_string(),
                         symbol: "TOKEN".to_string(),
                         decimals: 6,
-                        initial_balances: vec![Cw20Coin {
+                        initial_balances: vec![InitialBalance {
                             address: "sender".to_string(),
                             amount: Uint128::new(100),
+                            vesting: None,
                         }],
                         mint: None,
                         marketing: None,
+                        snapshots: None,
+                        capabilities: None,
+                        admin: None,
                     },
                     &[],
                     "TOKEN",
@@ -1422,7 +2163,11 @@ mod tests {
                 CosmosMsg::Wasm(WasmMsg::Migrate {
                     contract_addr: cw20_addr.to_string(),
                     new_code_id: cw20_id,
-                    msg: to_binary(&MigrateMsg {}).unwrap(),
+                    msg: to_binary(&MigrateMsg {
+                        capabilities: None,
+                        admin: None,
+                    })
+                    .unwrap(),
                 }),
             )
             .unwrap();
@@ -1461,6 +2206,38 @@ mod tests {
                 }]
             );
         }
+
+        #[test]
+        fn migrate_lp_token() {
+            let mut deps = mock_dependencies();
+            do_instantiate(deps.as_mut(), "holder", Uint128::new(1000));
+            // cw20-base-lp stored no capabilities
+            CAPABILITIES.remove(deps.as_mut().storage);
+
+            let lp = Capabilities {
+                holder_burn: false,
+                minter_only_burn_from: true,
+            };
+            let msg = MigrateMsg {
+                capabilities: Some(lp.clone()),
+                admin: None,
+            };
+            migrate(deps.as_mut(), mock_env(), msg).unwrap();
+            assert_eq!(CAPABILITIES.load(&deps.storage).unwrap(), lp);
+
+            // later migrations keep them
+            migrate(
+                deps.as_mut(),
+                mock_env(),
+                MigrateMsg {
+                    capabilities: None,
+                    admin: None,
+                },
+            )
+            .unwrap();
+            assert_eq!(CAPABILITIES.load(&deps.storage).unwrap(), lp);
+            assert_eq!(get_balance(deps.as_ref(), "holder"), Uint128::new(1000));
+        }
     }
 
     mod marketing {
@@ -1481,6 +2258,9 @@ mod tests {
                     marketing: Some("marketing".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1535,6 +2315,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1588,6 +2371,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1641,6 +2427,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1694,6 +2483,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1747,6 +2539,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1800,6 +2595,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1857,6 +2655,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1910,6 +2711,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -1959,6 +2763,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -2009,6 +2816,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -2060,6 +2870,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -2110,6 +2923,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -2167,6 +2983,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -2217,6 +3036,9 @@ mod tests {
                     marketing: Some("creator".to_owned()),
                     logo: Some(Logo::Url("url".to_owned())),
                 }),
+                snapshots: None,
+                capabilities: None,
+                admin: None,
             };
 
             let info = mock_info("creator", &[]);
@@ -84,10 +84,10 @@ mod tests {
 
     use cosmwasm_std::testing::{mock_dependencies_with_balance, mock_env, mock_info};
     use cosmwasm_std::{coins, from_binary, DepsMut, Uint128};
-    use cw20::{Cw20Coin, Expiration, TokenInfoResponse};
+    use cw20::{Expiration, TokenInfoResponse};
 
     use crate::contract::{execute, instantiate, query, query_token_info};
-    use crate::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+    use crate::msg::{ExecuteMsg, InitialBalance, InstantiateMsg, QueryMsg};
 
     // this will set up the instantiation for other tests
     fn do_instantiate(mut deps: DepsMut, addr: &str, amount: Uint128) -> TokenInfoResponse {
@@ -95,12 +95,16 @@ mod tests {
             name: "Auto Gen".to_string(),
             symbol: "AUTO".to_string(),
             decimals: 3,
-            initial_balances: vec![Cw20Coin {
+            initial_balances: vec![InitialBalance {
                 address: addr.into(),
                 amount,
+                vesting: None,
             }],
             mint: None,
             marketing: None,
+            snapshots: None,
+            capabilities: None,
+            admin: None,
         };
         let info = mock_info("creator", &[]);
         let env = mock_env();
@@ -1,4 +1,5 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{StdError, VerificationError};
+use cw_controllers::{AdminError, HookError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -6,9 +7,21 @@ pub enum ContractError {
     #[error("{0}")]
     Std(#[from] StdError),
 
+    #[error("{0}")]
+    Verification(#[from] VerificationError),
+
+    #[error("{0}")]
+    Admin(#[from] AdminError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Holders cannot burn this token")]
+    HolderBurnDisabled {},
+
     #[error("Cannot set to own account")]
     CannotSetOwnAccount {},
 
@@ -33,6 +46,24 @@ pub enum ContractError {
     #[error("Invalid png header")]
     InvalidPngHeader {},
 
+    #[error("Permit owner key must be a compressed secp256k1 public key")]
+    InvalidPubkey {},
+
+    #[error("Invalid permit signature")]
+    InvalidSignature {},
+
+    #[error("Invalid permit nonce, expected {expected}")]
+    InvalidNonce { expected: u64 },
+
+    #[error("Cannot move locked tokens")]
+    LockedTokens {},
+
+    #[error("Invalid vesting schedule")]
+    InvalidVestingSchedule {},
+
+    #[error("Address already has locked tokens")]
+    VestingScheduleExists {},
+
     #[error("Invalid expiration value")]
     InvalidExpiration {},
 
@@ -0,0 +1,110 @@
+use cosmwasm_std::{
+    attr, Addr, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, Storage, SubMsg,
+    SubMsgResult, Uint128,
+};
+use cw20::{BalanceChangedHookMsg, BalanceDiff};
+use cw_controllers::HooksResponse;
+use cw_utils::maybe_addr;
+
+use crate::error::ContractError;
+use crate::state::{HookConfig, ADMIN, HOOKS, HOOK_CONFIGS, HOOK_IDS, NEXT_HOOK_ID};
+
+/// The gas limit of hooks added without one. A hook running out of gas is removed, but the
+/// transaction only has so much gas, an unlimited hook could use it all and abort the transfer.
+pub const DEFAULT_HOOK_GAS_LIMIT: u64 = 500_000;
+
+/// Prepares the `BalanceChangedHook` submessages of all hooks. A failing hook doesn't revert
+/// the balance change, it is removed by `reply` instead.
+pub fn balance_changed_hooks(
+    storage: &dyn Storage,
+    diffs: Vec<BalanceDiff>,
+) -> StdResult<Vec<SubMsg>> {
+    let msg = BalanceChangedHookMsg::new(diffs);
+    HOOKS.prepare_hooks(storage, |hook| {
+        let config = HOOK_CONFIGS.load(storage, &hook)?;
+        let mut submsg = SubMsg::reply_on_error(msg.clone().into_cosmos_msg(hook)?, config.id);
+        submsg.gas_limit = Some(config.gas_limit);
+        Ok(submsg)
+    })
+}
+
+pub fn execute_update_admin(
+    deps: DepsMut,
+    info: MessageInfo,
+    admin: Option<String>,
+) -> Result<Response, ContractError> {
+    let admin = maybe_addr(deps.api, admin)?;
+    Ok(ADMIN.execute_update_admin(deps, info, admin)?)
+}
+
+pub fn execute_add_hook(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+    gas_limit: Option<u64>,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let res = HOOKS.execute_add_hook(&ADMIN, deps.branch(), info, addr.clone())?;
+
+    let id = NEXT_HOOK_ID.may_load(deps.storage)?.unwrap_or_default();
+    NEXT_HOOK_ID.save(deps.storage, &(id + 1))?;
+    let config = HookConfig {
+        id,
+        gas_limit: gas_limit.unwrap_or(DEFAULT_HOOK_GAS_LIMIT),
+    };
+    HOOK_CONFIGS.save(deps.storage, &addr, &config)?;
+    HOOK_IDS.save(deps.storage, id, &addr)?;
+    Ok(res)
+}
+
+pub fn execute_remove_hook(
+    mut deps: DepsMut,
+    info: MessageInfo,
+    addr: String,
+) -> Result<Response, ContractError> {
+    let addr = deps.api.addr_validate(&addr)?;
+    let res = HOOKS.execute_remove_hook(&ADMIN, deps.branch(), info, addr.clone())?;
+    let config = HOOK_CONFIGS.load(deps.storage, &addr)?;
+    HOOK_CONFIGS.remove(deps.storage, &addr);
+    HOOK_IDS.remove(deps.storage, config.id);
+    Ok(res)
+}
+
+/// Removes the hook whose submessage failed, so that it can't block the token
+pub fn reply_hook_failed(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    let id = msg.id;
+    let error = match msg.result {
+        SubMsgResult::Err(error) => error,
+        SubMsgResult::Ok(_) => return Ok(Response::new()),
+    };
+
+    // the hook may have been removed in the meantime
+    let hook = match HOOK_IDS.may_load(deps.storage, id)? {
+        Some(hook) => hook,
+        None => return Ok(Response::new()),
+    };
+    HOOKS.remove_hook(deps.storage, hook.clone())?;
+    HOOK_CONFIGS.remove(deps.storage, &hook);
+    HOOK_IDS.remove(deps.storage, id);
+
+    let res = Response::new().add_attributes(vec![
+        attr("action", "remove_hook"),
+        attr("hook", hook),
+        attr("error", error),
+    ]);
+    Ok(res)
+}
+
+pub fn query_hooks(deps: Deps) -> StdResult<HooksResponse> {
+    HOOKS.query_hooks(deps)
+}
+
+/// The balance diff of `address` after receiving `amount`, from its new balance
+pub fn received(address: &Addr, new: Uint128, amount: Uint128) -> BalanceDiff {
+    BalanceDiff::new(address, new - amount, new)
+}
+
+/// The balance diff of `address` after sending or burning `amount`, from its new balance
+pub fn sent(address: &Addr, new: Uint128, amount: Uint128) -> BalanceDiff {
+    BalanceDiff::new(address, new + amount, new)
+}
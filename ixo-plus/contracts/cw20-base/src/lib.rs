@@ -9,6 +9,8 @@ Implements:
 - [x] CW20 Base
 - [x] Mintable extension
 - [x] Allowances extension
+- [x] Vesting extension
+- [x] Hooks extension
 
 For more information on this contract, please check out the
 [README](https://github.com/CosmWasm/cw-plus/blob/main/contracts/cw20-base/README.md).
@@ -18,7 +20,9 @@ pub mod allowances;
 pub mod contract;
 pub mod enumerable;
 mod error;
+pub mod hooks;
 pub mod msg;
 pub mod state;
+pub mod vesting;
 
 pub use crate::error::ContractError;
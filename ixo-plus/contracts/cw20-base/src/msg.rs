@@ -1,6 +1,6 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{StdError, StdResult, Uint128};
-use cw20::{Cw20Coin, Logo, MinterResponse};
+use cw20::{Logo, MinterResponse, VestingSchedule};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -14,15 +14,49 @@ pub struct InstantiateMarketingInfo {
     pub logo: Option<Logo>,
 }
 
+#[cw_serde]
+pub struct InitialBalance {
+    pub address: String,
+    pub amount: Uint128,
+    /// Locks the tokens until released by the schedule
+    pub vesting: Option<VestingSchedule>,
+}
+
+/// What holders and spenders can do with the token, besides the cw20 base messages
+#[cw_serde]
+pub struct Capabilities {
+    /// Holders can burn their own tokens
+    pub holder_burn: bool,
+    /// Only the minter can burn tokens from an allowance, e.g. the pool of a liquidity token
+    pub minter_only_burn_from: bool,
+}
+
+impl Default for Capabilities {
+    /// The capabilities of a plain cw20 token
+    fn default() -> Self {
+        Capabilities {
+            holder_burn: true,
+            minter_only_burn_from: false,
+        }
+    }
+}
+
 #[cw_serde]
 #[cfg_attr(test, derive(Default))]
 pub struct InstantiateMsg {
     pub name: String,
     pub symbol: String,
     pub decimals: u8,
-    pub initial_balances: Vec<Cw20Coin>,
+    pub initial_balances: Vec<InitialBalance>,
     pub mint: Option<MinterResponse>,
     pub marketing: Option<InstantiateMarketingInfo>,
+    /// Keep the history of balances and total supply, for the `*AtHeight` queries.
+    /// Unset or false disables it, it can't be changed later.
+    pub snapshots: Option<bool>,
+    /// Unset means the capabilities of a plain cw20 token, see `Capabilities::default`
+    pub capabilities: Option<Capabilities>,
+    /// Manages the hooks called whenever balances change, none if unset
+    pub admin: Option<String>,
 }
 
 impl InstantiateMsg {
@@ -76,9 +110,41 @@ pub enum QueryMsg {
     /// Returns the current balance of the given address, 0 if unset.
     #[returns(cw20::BalanceResponse)]
     Balance { address: String },
+    /// Only with snapshots enabled.
+    /// Returns the balance of the given address at the beginning of block `height`, 0 if unset.
+    /// Unset `height` returns the current balance.
+    #[returns(cw20::BalanceResponse)]
+    BalanceAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Only with "vesting" extension.
+    /// Returns the vesting schedule of address, if any, and how many of its tokens are locked.
+    #[returns(cw20::VestingScheduleResponse)]
+    VestingSchedule { address: String },
+    /// Only with "vesting" extension.
+    /// Returns the balance address can transfer, i.e. without its locked tokens.
+    #[returns(cw20::BalanceResponse)]
+    UnlockedBalance { address: String },
     /// Returns metadata on the contract - name, decimals, supply, etc.
     #[returns(cw20::TokenInfoResponse)]
     TokenInfo {},
+    /// Only with snapshots enabled.
+    /// Returns the total supply at the beginning of block `height`.
+    /// Unset `height` returns the current total supply.
+    #[returns(TotalSupplyAtHeightResponse)]
+    TotalSupplyAtHeight { height: Option<u64> },
+    /// Returns what holders and spenders can do with the token.
+    #[returns(Capabilities)]
+    Capabilities {},
+    /// Only with "hooks" extension.
+    /// Returns who manages the hooks, if anyone.
+    #[returns(cw_controllers::AdminResponse)]
+    Admin {},
+    /// Only with "hooks" extension.
+    /// Returns the contracts called whenever balances change.
+    #[returns(cw_controllers::HooksResponse)]
+    Hooks {},
     /// Only with "mintable" extension.
     /// Returns who can mint and the hard cap on maximum tokens after minting.
     #[returns(cw20::MinterResponse)]
@@ -87,6 +153,10 @@ pub enum QueryMsg {
     /// Returns how much spender can use from owner account, 0 if unset.
     #[returns(cw20::AllowanceResponse)]
     Allowance { owner: String, spender: String },
+    /// Only with "allowance" extension.
+    /// Returns the nonce the next `Permit` of owner must have, 0 if unset.
+    #[returns(cw20::NonceResponse)]
+    Nonce { owner: String },
     /// Only with "enumerable" extension (and "allowances")
     /// Returns all allowances this owner has approved. Supports pagination.
     #[returns(cw20::AllAllowancesResponse)]
@@ -122,8 +192,20 @@ pub enum QueryMsg {
     DownloadLogo {},
 }
 
+#[cw_serde]
+pub struct TotalSupplyAtHeightResponse {
+    pub total_supply: Uint128,
+}
+
 #[derive(Serialize, Deserialize, JsonSchema)]
-pub struct MigrateMsg {}
+pub struct MigrateMsg {
+    /// Replaces the capabilities of the token. Tokens migrated from a version without
+    /// capabilities get the default ones if unset, so `cw20-base-lp` tokens must set them.
+    pub capabilities: Option<Capabilities>,
+    /// Sets who manages the hooks, e.g. for tokens instantiated without an admin.
+    /// Unset keeps the current admin.
+    pub admin: Option<String>,
+}
 
 #[cfg(test)]
 mod tests {
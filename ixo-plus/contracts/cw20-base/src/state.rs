@@ -1,8 +1,11 @@
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Uint128};
-use cw_storage_plus::{Item, Map};
+use cosmwasm_std::{Addr, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, SnapshotItem, SnapshotMap, Strategy};
 
-use cw20::{AllowanceResponse, Logo, MarketingInfoResponse};
+use cw20::{AllowanceResponse, Logo, MarketingInfoResponse, VestingSchedule};
+use cw_controllers::{Admin, Hooks};
+
+use crate::msg::Capabilities;
 
 #[cw_serde]
 pub struct TokenInfo {
@@ -30,7 +33,86 @@ pub const TOKEN_INFO: Item<TokenInfo> = Item::new("token_info");
 pub const MARKETING_INFO: Item<MarketingInfoResponse> = Item::new("marketing_info");
 pub const LOGO: Item<Logo> = Item::new("logo");
 pub const BALANCES: Map<&Addr, Uint128> = Map::new("balance");
+/// What holders and spenders can do with the token, set at instantiation or migration
+pub const CAPABILITIES: Item<Capabilities> = Item::new("capabilities");
+/// Whether the history of balances and total supply is kept, set at instantiation
+pub const SNAPSHOTS: Item<bool> = Item::new("snapshots");
+/// The same storage as `BALANCES`, along with its history if snapshots are enabled
+pub const BALANCE_SNAPSHOTS: SnapshotMap<&Addr, Uint128> = SnapshotMap::new(
+    "balance",
+    "balance__checkpoints",
+    "balance__changelog",
+    Strategy::EveryBlock,
+);
+/// The history of the total supply if snapshots are enabled, `TOKEN_INFO` holds the current one
+pub const TOTAL_SUPPLY_SNAPSHOTS: SnapshotItem<Uint128> = SnapshotItem::new(
+    "total_supply",
+    "total_supply__checkpoints",
+    "total_supply__changelog",
+    Strategy::EveryBlock,
+);
 pub const ALLOWANCES: Map<(&Addr, &Addr), AllowanceResponse> = Map::new("allowance");
 // TODO: After https://github.com/CosmWasm/cw-plus/issues/670 is implemented, replace this with a `MultiIndex` over `ALLOWANCES`
 pub const ALLOWANCES_SPENDER: Map<(&Addr, &Addr), AllowanceResponse> =
     Map::new("allowance_spender");
+#[cw_serde]
+pub struct Vesting {
+    pub schedule: VestingSchedule,
+    /// The amount of tokens locked by the schedule at first
+    pub amount: Uint128,
+}
+
+pub const VESTING: Map<&Addr, Vesting> = Map::new("vesting");
+
+/// The nonce the next permit of each owner must have
+pub const NONCES: Map<&Addr, u64> = Map::new("nonces");
+
+/// Manages the hooks
+pub const ADMIN: Admin = Admin::new("admin");
+/// Contracts called with `BalanceChangedHook` whenever balances change
+pub const HOOKS: Hooks = Hooks::new("hooks");
+
+#[cw_serde]
+pub struct HookConfig {
+    /// The reply id of the hook submessages, to remove the hook when they fail
+    pub id: u64,
+    pub gas_limit: u64,
+}
+
+pub const HOOK_CONFIGS: Map<&Addr, HookConfig> = Map::new("hook_configs");
+/// The hook of each reply id
+pub const HOOK_IDS: Map<u64, Addr> = Map::new("hook_ids");
+pub const NEXT_HOOK_ID: Item<u64> = Item::new("next_hook_id");
+
+pub fn snapshots_enabled(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(SNAPSHOTS.may_load(storage)?.unwrap_or_default())
+}
+
+/// Updates the balance of `address`, recording the change at `height` if snapshots are enabled
+pub fn update_balance<A>(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    height: u64,
+    action: A,
+) -> StdResult<Uint128>
+where
+    A: FnOnce(Option<Uint128>) -> StdResult<Uint128>,
+{
+    if snapshots_enabled(storage)? {
+        BALANCE_SNAPSHOTS.update(storage, address, height, action)
+    } else {
+        BALANCES.update(storage, address, action)
+    }
+}
+
+/// Records the new total supply at `height` if snapshots are enabled
+pub fn snapshot_total_supply(
+    storage: &mut dyn Storage,
+    total_supply: Uint128,
+    height: u64,
+) -> StdResult<()> {
+    if snapshots_enabled(storage)? {
+        TOTAL_SUPPLY_SNAPSHOTS.save(storage, &total_supply, height)?;
+    }
+    Ok(())
+}
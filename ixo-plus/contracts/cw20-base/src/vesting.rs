@@ -0,0 +1,85 @@
+use cosmwasm_std::{Addr, BlockInfo, Deps, Env, StdResult, Storage, Timestamp, Uint128};
+use cw20::{BalanceResponse, VestingSchedule, VestingScheduleResponse};
+
+use crate::error::ContractError;
+use crate::state::{Vesting, BALANCES, VESTING};
+
+/// Returns how many tokens of `address` are still locked by its vesting schedule at `time`
+pub fn locked_amount(storage: &dyn Storage, address: &Addr, time: Timestamp) -> StdResult<Uint128> {
+    let locked = VESTING
+        .may_load(storage, address)?
+        .map(|vesting| vesting.amount - vesting.schedule.released(vesting.amount, time))
+        .unwrap_or_default();
+    Ok(locked)
+}
+
+/// Fails if spending `amount` would take some locked tokens of `address`
+pub fn assert_unlocked(
+    storage: &dyn Storage,
+    address: &Addr,
+    amount: Uint128,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    let locked = locked_amount(storage, address, block.time)?;
+    if locked.is_zero() {
+        return Ok(());
+    }
+    let balance = BALANCES.may_load(storage, address)?.unwrap_or_default();
+    // an insufficient balance fails later on, with the usual overflow error
+    if balance >= amount && balance - amount < locked {
+        return Err(ContractError::LockedTokens {});
+    }
+    Ok(())
+}
+
+/// Locks `amount` of the tokens of `address` until released by `schedule`.
+/// An address has at most one vesting schedule at a time, a new one can only start once all
+/// tokens of the previous one are released.
+pub fn add_vesting(
+    storage: &mut dyn Storage,
+    address: &Addr,
+    amount: Uint128,
+    schedule: VestingSchedule,
+    block: &BlockInfo,
+) -> Result<(), ContractError> {
+    if !schedule.is_valid(amount) {
+        return Err(ContractError::InvalidVestingSchedule {});
+    }
+    if !locked_amount(storage, address, block.time)?.is_zero() {
+        return Err(ContractError::VestingScheduleExists {});
+    }
+    VESTING.save(storage, address, &Vesting { schedule, amount })?;
+    Ok(())
+}
+
+pub fn query_vesting_schedule(
+    deps: Deps,
+    env: Env,
+    address: String,
+) -> StdResult<VestingScheduleResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let res = match VESTING.may_load(deps.storage, &address)? {
+        Some(Vesting { schedule, amount }) => VestingScheduleResponse {
+            locked: amount - schedule.released(amount, env.block.time),
+            schedule: Some(schedule),
+            amount,
+        },
+        None => VestingScheduleResponse {
+            schedule: None,
+            amount: Uint128::zero(),
+            locked: Uint128::zero(),
+        },
+    };
+    Ok(res)
+}
+
+pub fn query_unlocked_balance(deps: Deps, env: Env, address: String) -> StdResult<BalanceResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let balance = BALANCES
+        .may_load(deps.storage, &address)?
+        .unwrap_or_default();
+    let locked = locked_amount(deps.storage, &address, env.block.time)?;
+    Ok(BalanceResponse {
+        balance: balance.saturating_sub(locked),
+    })
+}
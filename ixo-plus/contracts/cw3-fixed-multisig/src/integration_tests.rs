@@ -31,7 +31,7 @@ pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
 #[test]
 // cw3 multisig account can control cw20 admin actions
 fn cw3_controls_cw20() {
-    let mut router = mock_app();
+    let router = mock_app();
 
     // setup cw3 multisig with 3 accounts
     let cw3_id = router.store_code(contract_cw3_fixed_multisig());
@@ -82,6 +82,9 @@ fn cw3_controls_cw20() {
             cap: None,
         }),
         marketing: None,
+        snapshots: None,
+        capabilities: None,
+        admin: None,
     };
     let cw20_addr = router
         .instantiate_contract(
@@ -100,6 +103,7 @@ fn cw3_controls_cw20() {
     let cw20_mint_msg = cw20_base::msg::ExecuteMsg::Mint {
         recipient: mint_recipient.to_string(),
         amount: mint_amount,
+        vesting: None,
     };
 
     let execute_mint_msg = WasmMsg::Execute {
@@ -2,6 +2,7 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
+use cosmwasm_std::Empty;
 
 fn main() {
     let mut out_dir = current_dir().unwrap();
@@ -9,14 +10,37 @@ fn main() {
     create_dir_all(&out_dir).unwrap();
     remove_schemas(&out_dir).unwrap();
 
-    export_schema(&schema_for!(cw1155::Cw1155ExecuteMsg), &out_dir);
+    export_schema(
+        &schema_for!(cw1155::Cw1155ExecuteMsg<Option<Empty>>),
+        &out_dir,
+    );
     export_schema(&schema_for!(cw1155::Cw1155QueryMsg), &out_dir);
     export_schema(&schema_for!(cw1155::Cw1155ReceiveMsg), &out_dir);
     export_schema(&schema_for!(cw1155::Cw1155BatchReceiveMsg), &out_dir);
+    export_schema(&schema_for!(cw1155::ContractInfoResponse), &out_dir);
     export_schema(&schema_for!(cw1155::BalanceResponse), &out_dir);
     export_schema(&schema_for!(cw1155::BatchBalanceResponse), &out_dir);
     export_schema(&schema_for!(cw1155::ApprovedForAllResponse), &out_dir);
     export_schema(&schema_for!(cw1155::IsApprovedForAllResponse), &out_dir);
-    export_schema(&schema_for!(cw1155::TokenInfoResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::AllowanceResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::AllAllowancesResponse), &out_dir);
+    export_schema(
+        &schema_for!(cw1155::TokenInfoResponse<Option<Empty>>),
+        &out_dir,
+    );
     export_schema(&schema_for!(cw1155::TokensResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::OwnersResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::RoyaltiesInfoResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::CheckRoyaltiesResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::SupplyResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::AllSuppliesResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::AdminResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::HasRoleResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::RoleGrantsResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::PausedResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::TransferRestrictions), &out_dir);
+    export_schema(&schema_for!(cw1155::HolderListResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::Retirement), &out_dir);
+    export_schema(&schema_for!(cw1155::RetirementsResponse), &out_dir);
+    export_schema(&schema_for!(cw1155::RetiredSupplyResponse), &out_dir);
 }
@@ -23,6 +23,27 @@ impl<'a> Event for TransferEvent<'a> {
     }
 }
 
+/// Tracks retirements, next to the transfer event of the burn
+pub struct RetireEvent<'a> {
+    pub id: u64,
+    pub owner: &'a str,
+    pub token_id: &'a str,
+    pub amount: Uint128,
+    pub beneficiary: &'a str,
+}
+
+impl<'a> Event for RetireEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "retire"));
+        rsp.attributes
+            .push(attr("retirement_id", self.id.to_string()));
+        rsp.attributes.push(attr("owner", self.owner));
+        rsp.attributes.push(attr("token_id", self.token_id));
+        rsp.attributes.push(attr("amount", self.amount));
+        rsp.attributes.push(attr("beneficiary", self.beneficiary));
+    }
+}
+
 /// Tracks token metadata changes
 pub struct MetadataEvent<'a> {
     pub url: &'a str,
@@ -37,6 +58,24 @@ impl<'a> Event for MetadataEvent<'a> {
     }
 }
 
+/// Tracks allowance changes of a single token
+pub struct ApproveEvent<'a> {
+    pub owner: &'a str,
+    pub spender: &'a str,
+    pub token_id: &'a str,
+    pub amount: Uint128,
+}
+
+impl<'a> Event for ApproveEvent<'a> {
+    fn add_attributes(&self, rsp: &mut Response) {
+        rsp.attributes.push(attr("action", "approve"));
+        rsp.attributes.push(attr("owner", self.owner));
+        rsp.attributes.push(attr("spender", self.spender));
+        rsp.attributes.push(attr("token_id", self.token_id));
+        rsp.attributes.push(attr("amount", self.amount));
+    }
+}
+
 /// Tracks approve_all status changes
 pub struct ApproveAllEvent<'a> {
     pub sender: &'a str,
@@ -16,15 +16,24 @@ For more information on this specification, please check out the
 
 pub use cw_utils::Expiration;
 
-pub use crate::event::{ApproveAllEvent, MetadataEvent, TransferEvent};
-pub use crate::msg::{Cw1155ExecuteMsg, TokenId};
+pub use crate::event::{ApproveAllEvent, ApproveEvent, MetadataEvent, RetireEvent, TransferEvent};
+pub use crate::metadata::{Metadata, Trait};
+pub use crate::msg::{
+    Cw1155ExecuteMsg, HolderList, Role, SupplyPolicy, TokenId, TransferRestrictions,
+};
 pub use crate::query::{
-    Approval, ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse, Cw1155QueryMsg,
-    IsApprovedForAllResponse, TokenInfoResponse, TokensResponse,
+    AdminResponse, AllAllowancesResponse, AllSuppliesResponse, AllowanceInfo, AllowanceResponse,
+    Approval, ApprovedForAllResponse, BalanceResponse, BatchBalanceResponse,
+    CheckRoyaltiesResponse, ContractInfoResponse, Cw1155QueryMsg, HasRoleResponse,
+    HolderListResponse, IsApprovedForAllResponse, OwnersResponse, PausedResponse,
+    RetiredSupplyResponse, Retirement, RetirementsResponse, RoleGrant, RoleGrantsResponse,
+    RoyaltiesInfoResponse, SupplyResponse, TokenInfoResponse, TokenOwner, TokenSupply,
+    TokensResponse,
 };
 pub use crate::receiver::{Cw1155BatchReceiveMsg, Cw1155ReceiveMsg};
 
 mod event;
+mod metadata;
 mod msg;
 mod query;
 mod receiver;
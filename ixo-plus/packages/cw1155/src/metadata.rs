@@ -0,0 +1,31 @@
+use cosmwasm_schema::cw_serde;
+
+#[cw_serde]
+#[derive(Default)]
+pub struct Trait {
+    pub display_type: Option<String>,
+    pub trait_type: String,
+    pub value: String,
+}
+
+/// On-chain metadata of a token, for contracts using `Option<Metadata>` as extension.
+/// The fields follow common token metadata, with the ones impact credits need on top.
+#[cw_serde]
+#[derive(Default)]
+pub struct Metadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image: Option<String>,
+    /// Number of decimals used to display amounts of a fungible token
+    pub decimals: Option<u8>,
+    /// Year or period the credits were issued for
+    pub vintage: Option<String>,
+    /// Id of the project that issued the credits
+    pub project_id: Option<String>,
+    pub attributes: Option<Vec<Trait>>,
+    /// This is how much the minter takes as a cut when sold,
+    /// royalties are owed on this token if it is Some
+    pub royalty_percentage: Option<u64>,
+    /// The payment address, may be different to or the same as the minter addr
+    pub royalty_payment_address: Option<String>,
+}
@@ -4,9 +4,51 @@ use cw_utils::Expiration;
 
 pub type TokenId = String;
 
+/// Whether more of a token can be minted after its first mint
 #[cw_serde]
+#[derive(Default)]
+pub enum SupplyPolicy {
+    /// The whole supply is minted at once, like a non-fungible token. Minting the token id
+    /// again fails.
+    #[default]
+    Fixed,
+    /// The minter can mint more of the token at any time, up to its `max_supply` if set.
+    Open,
+}
+
+/// Permissions granted by the admin of a contract with the "roles" extension.
+/// A role can be limited to the token ids starting with a given prefix.
+#[cw_serde]
+pub enum Role {
+    /// Can mint tokens
+    Minter,
+    /// Can update the metadata of tokens
+    MetadataEditor,
+    /// Can pause and unpause transfers
+    Pauser,
+}
+
+/// Rules limiting who can hold and move a token
+#[cw_serde]
+#[derive(Default)]
+pub struct TransferRestrictions {
+    /// The token can't be moved between accounts once minted, only burned or retired
+    pub soulbound: bool,
+    /// Only accounts on the allow list of the token can receive it
+    pub allow_list_only: bool,
+}
 
-pub enum Cw1155ExecuteMsg {
+/// The per token lists of holders. Accounts on the deny list can't send nor receive the token.
+#[cw_serde]
+pub enum HolderList {
+    Allow,
+    Deny,
+}
+
+/// `T` is the type of the on-chain metadata extension of tokens, `Option<Empty>` if unused.
+#[cw_serde]
+
+pub enum Cw1155ExecuteMsg<T> {
     /// SendFrom is a base message to move tokens,
     /// if `env.sender` is the owner or has sufficient pre-approval.
     SendFrom {
@@ -30,16 +72,28 @@ pub enum Cw1155ExecuteMsg {
         msg: Option<Binary>,
     },
     /// Mint is a base message to mint tokens.
+    /// Minting an existing `token_id` is only allowed if its supply policy is `Open`,
+    /// in which case `uri` is ignored.
     Mint {
         /// If `to` is not contract, `msg` should be `None`
         to: String,
         token_id: TokenId,
         value: Uint128,
         uri: String,
+        /// Caps the total supply of a new `token_id`, unset means no cap.
+        /// It can only be set by the first mint of a token.
+        max_supply: Option<Uint128>,
+        /// Supply policy of a new `token_id`, unset means `Fixed`.
+        /// It can only be set by the first mint of a token.
+        supply_policy: Option<SupplyPolicy>,
+        /// On-chain metadata of a new `token_id`, ignored for an existing one
+        extension: T,
         /// `None` means don't call the receiver interface
         msg: Option<Binary>,
     },
     /// BatchMint is a base message to mint multiple types of tokens in batch.
+    /// New token ids get a `Fixed` supply without cap and the default extension,
+    /// existing ones follow their supply policy.
     BatchMint {
         /// If `to` is not contract, `msg` should be `None`
         to: String,
@@ -68,4 +122,79 @@ pub enum Cw1155ExecuteMsg {
     },
     /// Remove previously granted ApproveAll permission
     RevokeAll { operator: String },
+    /// Only with the "retirement" extension.
+    /// Burns `amount` of `token_id` from the sender's account for good, e.g. to offset emissions
+    /// with carbon credits, and records a retirement certificate.
+    Retire {
+        token_id: TokenId,
+        amount: Uint128,
+        /// Who the tokens are retired on behalf of, the sender if unset
+        beneficiary: Option<String>,
+        reason: Option<String>,
+    },
+    /// Only with the "metadata" extension.
+    /// Updates the url and / or extension of `token_id`, only allowed to its metadata editors.
+    /// Setting `freeze` makes the metadata of the token immutable for good.
+    UpdateMetadata {
+        token_id: TokenId,
+        url: Option<String>,
+        extension: Option<T>,
+        freeze: Option<bool>,
+    },
+    /// Allows spender to transfer / send / burn up to `amount` of `token_id` from the owner's
+    /// account. It replaces any previous allowance for the token, and zero removes it.
+    /// If expiration is set, then this allowance has a time/height limit
+    Approve {
+        spender: String,
+        token_id: TokenId,
+        amount: Uint128,
+        expires: Option<Expiration>,
+    },
+    /// Only with the "roles" extension.
+    /// Grants `role` to `address`, limited to the token ids starting with `token_id_prefix` if
+    /// set, or for all token ids otherwise. Only allowed to the admin.
+    GrantRole {
+        role: Role,
+        address: String,
+        token_id_prefix: Option<String>,
+    },
+    /// Only with the "roles" extension.
+    /// Revokes the grant of `role` to `address` with the same `token_id_prefix`.
+    /// Only allowed to the admin.
+    RevokeRole {
+        role: Role,
+        address: String,
+        token_id_prefix: Option<String>,
+    },
+    /// Only with the "roles" extension.
+    /// Proposes `new_admin` as the admin, who has to accept it with `AcceptAdmin`.
+    /// Only allowed to the admin, a new proposal replaces the pending one.
+    TransferAdmin { new_admin: String },
+    /// Only with the "roles" extension.
+    /// Makes the sender the admin, if it is the pending admin.
+    AcceptAdmin {},
+    /// Only with the "restrictions" extension.
+    /// Sets the transfer restrictions of `token_id`, only allowed to its minters.
+    SetTransferRestrictions {
+        token_id: TokenId,
+        restrictions: TransferRestrictions,
+    },
+    /// Only with the "restrictions" extension.
+    /// Adds and removes accounts from a holder list of `token_id`, only allowed to its minters.
+    UpdateHolderList {
+        token_id: TokenId,
+        list: HolderList,
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Only with the "restrictions" extension.
+    /// Blocks all transfers between accounts, only allowed to pausers of all token ids.
+    Pause {},
+    /// Only with the "restrictions" extension.
+    /// Lifts a pause, only allowed to pausers of all token ids.
+    Unpause {},
+    /// Continues rebuilding the holder index and total supplies from up to `limit` balances,
+    /// after migrating a collection from a version without them. Anyone can call it, and all
+    /// other messages are rejected until the rebuild is done.
+    MigrateBalances { limit: Option<u32> },
 }
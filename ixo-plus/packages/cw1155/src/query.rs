@@ -1,8 +1,8 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Empty, Timestamp, Uint128};
 use cw_utils::Expiration;
 
-use crate::msg::TokenId;
+use crate::msg::{HolderList, Role, SupplyPolicy, TokenId, TransferRestrictions};
 
 #[cw_serde]
 #[derive(QueryResponses)]
@@ -28,10 +28,31 @@ pub enum Cw1155QueryMsg {
     /// Query approved status `owner` granted to `operator`.
     #[returns(IsApprovedForAllResponse)]
     IsApprovedForAll { owner: String, operator: String },
+    /// Returns how much of `token_id` spender can use from owner account, 0 if unset.
+    #[returns(AllowanceResponse)]
+    Allowance {
+        owner: String,
+        spender: String,
+        token_id: TokenId,
+    },
+    /// List all allowances the owner granted, ordered by spender and token_id.
+    #[returns(AllAllowancesResponse)]
+    AllAllowances {
+        owner: String,
+        /// unset or false will filter out expired allowances, you must set to true to see them
+        include_expired: Option<bool>,
+        /// the last `(spender, token_id)` of the previous page
+        start_after: Option<(String, TokenId)>,
+        limit: Option<u32>,
+    },
 
+    /// With MetaData Extension.
+    /// Returns the name and symbol of the collection.
+    #[returns(ContractInfoResponse)]
+    ContractInfo {},
     /// With MetaData Extension.
     /// Query metadata of token
-    #[returns(TokenInfoResponse)]
+    #[returns(TokenInfoResponse<Option<Empty>>)]
     TokenInfo { token_id: TokenId },
 
     /// With Enumerable extension.
@@ -49,6 +70,102 @@ pub enum Cw1155QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    /// With Enumerable extension.
+    /// Requires pagination. Lists the accounts holding `token_id` and their balance,
+    /// ordered by address.
+    #[returns(OwnersResponse)]
+    Owners {
+        token_id: TokenId,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// With Roles extension.
+    /// Returns the admin managing roles, and the proposed next admin if any.
+    #[returns(AdminResponse)]
+    Admin {},
+    /// With Roles extension.
+    /// Returns whether `address` has `role` for `token_id`, or for all token ids if unset.
+    #[returns(HasRoleResponse)]
+    HasRole {
+        role: Role,
+        address: String,
+        token_id: Option<TokenId>,
+    },
+    /// With Roles extension.
+    /// Lists the grants of `role`, ordered by address and token id prefix.
+    /// `start_after` is the address and token id prefix of the last grant, the prefix is empty
+    /// for grants of all token ids.
+    #[returns(RoleGrantsResponse)]
+    RoleGrants {
+        role: Role,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+
+    /// With Restrictions extension.
+    /// Returns whether transfers are paused.
+    #[returns(PausedResponse)]
+    Paused {},
+    /// With Restrictions extension.
+    /// Returns the transfer restrictions of `token_id`, none if unset.
+    #[returns(TransferRestrictions)]
+    TransferRestrictions { token_id: TokenId },
+    /// With Restrictions extension.
+    /// Requires pagination. Lists the accounts on a holder list of `token_id`.
+    #[returns(HolderListResponse)]
+    HolderList {
+        token_id: TokenId,
+        list: HolderList,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+
+    /// With Retirement extension.
+    /// Returns the retirement certificate with the given id.
+    #[returns(Retirement)]
+    Retirement { id: u64 },
+    /// With Retirement extension.
+    /// Lists retirement certificates ordered by id, optionally only the ones of `owner` and / or
+    /// `token_id`.
+    #[returns(RetirementsResponse)]
+    Retirements {
+        owner: Option<String>,
+        token_id: Option<TokenId>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// With Retirement extension.
+    /// Returns the total amount of `token_id` retired so far.
+    #[returns(RetiredSupplyResponse)]
+    RetiredSupply { token_id: TokenId },
+
+    /// With Royalties extension.
+    /// Should be called on sale to see if royalties are owed by the marketplace selling the
+    /// token, if CheckRoyalties returns true.
+    /// See https://eips.ethereum.org/EIPS/eip-2981
+    #[returns(RoyaltiesInfoResponse)]
+    RoyaltyInfo {
+        token_id: TokenId,
+        /// The denom of this sale must also be the denom of the returned royalty amount
+        sale_price: Uint128,
+    },
+    /// With Royalties extension.
+    /// Returns whether marketplaces should check the royalties of tokens on sale.
+    #[returns(CheckRoyaltiesResponse)]
+    CheckRoyalties {},
+
+    /// With Supply extension.
+    /// Returns the amount of `token_id` in circulation, and its max supply if capped.
+    #[returns(SupplyResponse)]
+    Supply { token_id: TokenId },
+    /// With Supply extension.
+    /// Requires pagination. Lists the supply of all token_ids controlled by the contract.
+    #[returns(AllSuppliesResponse)]
+    AllSupplies {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -80,9 +197,150 @@ pub struct IsApprovedForAllResponse {
 }
 
 #[cw_serde]
-pub struct TokenInfoResponse {
+#[derive(Default)]
+pub struct AllowanceResponse {
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct AllowanceInfo {
+    pub spender: String,
+    pub token_id: TokenId,
+    pub allowance: Uint128,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct AllAllowancesResponse {
+    pub allowances: Vec<AllowanceInfo>,
+}
+
+#[cw_serde]
+#[derive(Default)]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct TokenInfoResponse<T> {
     /// Should be a url point to a json file
     pub url: String,
+    /// On-chain metadata, the type depends on the contract
+    pub extension: T,
+    /// Frozen metadata can't be updated anymore
+    pub frozen: bool,
+}
+
+#[cw_serde]
+pub struct SupplyResponse {
+    /// Amount minted minus amount burned
+    pub total_supply: Uint128,
+    /// The total supply can never exceed this amount, `None` if uncapped
+    pub max_supply: Option<Uint128>,
+    /// Whether more of the token can be minted
+    pub supply_policy: SupplyPolicy,
+}
+
+#[cw_serde]
+pub struct TokenSupply {
+    pub token_id: TokenId,
+    pub total_supply: Uint128,
+    pub max_supply: Option<Uint128>,
+    pub supply_policy: SupplyPolicy,
+}
+
+#[cw_serde]
+pub struct AllSuppliesResponse {
+    /// Contains the supplies in lexicographical ordering of token_id
+    pub supplies: Vec<TokenSupply>,
+}
+
+#[cw_serde]
+pub struct AdminResponse {
+    pub admin: String,
+    /// Proposed admin, until it accepts
+    pub pending_admin: Option<String>,
+}
+
+#[cw_serde]
+pub struct HasRoleResponse {
+    pub has_role: bool,
+}
+
+#[cw_serde]
+pub struct RoleGrant {
+    pub address: String,
+    /// The role only applies to the token ids starting with it, all token ids if `None`
+    pub token_id_prefix: Option<String>,
+}
+
+#[cw_serde]
+pub struct RoleGrantsResponse {
+    pub grants: Vec<RoleGrant>,
+}
+
+#[cw_serde]
+pub struct PausedResponse {
+    pub paused: bool,
+}
+
+#[cw_serde]
+pub struct HolderListResponse {
+    pub holders: Vec<String>,
+}
+
+#[cw_serde]
+pub struct RoyaltiesInfoResponse {
+    pub address: String,
+    /// Note that this must be the same denom as that passed in to RoyaltyInfo,
+    /// rounded down
+    pub royalty_amount: Uint128,
+}
+
+/// Shows if the contract implements royalties,
+/// if royalty_payments is true, marketplaces should pay them
+#[cw_serde]
+pub struct CheckRoyaltiesResponse {
+    pub royalty_payments: bool,
+}
+
+/// A retirement certificate
+#[cw_serde]
+pub struct Retirement {
+    pub id: u64,
+    /// The account the tokens were burned from
+    pub owner: String,
+    pub token_id: TokenId,
+    pub amount: Uint128,
+    pub beneficiary: String,
+    pub reason: Option<String>,
+    /// Block time of the retirement
+    pub timestamp: Timestamp,
+}
+
+#[cw_serde]
+pub struct RetirementsResponse {
+    pub retirements: Vec<Retirement>,
+}
+
+#[cw_serde]
+pub struct RetiredSupplyResponse {
+    pub retired_supply: Uint128,
+}
+
+#[cw_serde]
+pub struct TokenOwner {
+    pub owner: String,
+    pub balance: Uint128,
+}
+
+#[cw_serde]
+pub struct OwnersResponse {
+    /// Contains the holders in lexicographical ordering of address, without the ones whose
+    /// balance went back to 0
+    pub owners: Vec<TokenOwner>,
 }
 
 #[cw_serde]
@@ -4,9 +4,10 @@ use std::fs::create_dir_all;
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 use cw20::{
-    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceResponse, Cw20ExecuteMsg,
-    Cw20QueryMsg, Cw20ReceiveMsg, DownloadLogoResponse, MarketingInfoResponse, MinterResponse,
-    TokenInfoResponse,
+    AllAccountsResponse, AllAllowancesResponse, AllowanceResponse, BalanceChangedHookMsg,
+    BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, DownloadLogoResponse,
+    MarketingInfoResponse, MinterResponse, NonceResponse, PermitSignDoc, TokenInfoResponse,
+    VestingScheduleResponse,
 };
 
 fn main() {
@@ -18,6 +19,7 @@ fn main() {
     export_schema(&schema_for!(Cw20ExecuteMsg), &out_dir);
     export_schema(&schema_for!(Cw20QueryMsg), &out_dir);
     export_schema(&schema_for!(Cw20ReceiveMsg), &out_dir);
+    export_schema(&schema_for!(BalanceChangedHookMsg), &out_dir);
     export_schema(&schema_for!(AllowanceResponse), &out_dir);
     export_schema(&schema_for!(BalanceResponse), &out_dir);
     export_schema(&schema_for!(TokenInfoResponse), &out_dir);
@@ -26,4 +28,7 @@ fn main() {
     export_schema(&schema_for!(MarketingInfoResponse), &out_dir);
     export_schema(&schema_for!(AllAllowancesResponse), &out_dir);
     export_schema(&schema_for!(AllAccountsResponse), &out_dir);
+    export_schema(&schema_for!(NonceResponse), &out_dir);
+    export_schema(&schema_for!(PermitSignDoc), &out_dir);
+    export_schema(&schema_for!(VestingScheduleResponse), &out_dir);
 }
@@ -15,23 +15,28 @@ pub use crate::balance::Balance;
 pub use crate::coin::{Cw20Coin, Cw20CoinVerified};
 pub use crate::denom::Denom;
 pub use crate::helpers::Cw20Contract;
+pub use crate::hook::{BalanceChangedHookMsg, BalanceDiff};
 pub use crate::logo::{EmbeddedLogo, Logo, LogoInfo};
-pub use crate::msg::Cw20ExecuteMsg;
+pub use crate::msg::{Cw20ExecuteMsg, PermitSignDoc};
 pub use crate::query::{
     AllAccountsResponse, AllAllowancesResponse, AllSpenderAllowancesResponse, AllowanceInfo,
     AllowanceResponse, BalanceResponse, Cw20QueryMsg, DownloadLogoResponse, MarketingInfoResponse,
-    MinterResponse, SpenderAllowanceInfo, TokenInfoResponse,
+    MinterResponse, NonceResponse, SpenderAllowanceInfo, TokenInfoResponse,
+    VestingScheduleResponse,
 };
 pub use crate::receiver::Cw20ReceiveMsg;
+pub use crate::vesting::{VestingSchedule, VestingStep};
 
 mod balance;
 mod coin;
 mod denom;
 mod helpers;
+mod hook;
 mod logo;
 mod msg;
 mod query;
 mod receiver;
+mod vesting;
 
 #[cfg(test)]
 mod tests {
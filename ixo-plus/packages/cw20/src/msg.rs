@@ -1,4 +1,5 @@
 use crate::logo::Logo;
+use crate::vesting::VestingSchedule;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Binary, Uint128};
 use cw_utils::Expiration;
@@ -33,6 +34,19 @@ pub enum Cw20ExecuteMsg {
         amount: Uint128,
         expires: Option<Expiration>,
     },
+    /// Only with "approval" extension. Sets the spender's access of tokens from the owner's
+    /// account to amount, as IncreaseAllowance from an empty allowance would. The owner
+    /// authorizes it by signing the `PermitSignDoc` of the message as ADR-036 arbitrary data with
+    /// the secp256k1 key of `owner_pubkey` rather than sending it, so anyone can relay it. `nonce` must be the
+    /// owner's current nonce, which every permit increases.
+    Permit {
+        owner_pubkey: Binary,
+        spender: String,
+        amount: Uint128,
+        expires: Option<Expiration>,
+        nonce: u64,
+        signature: Binary,
+    },
     /// Only with "approval" extension. Transfers amount tokens from owner -> recipient
     /// if `env.sender` has sufficient pre-approval.
     TransferFrom {
@@ -51,8 +65,13 @@ pub enum Cw20ExecuteMsg {
     /// Only with "approval" extension. Destroys tokens forever
     BurnFrom { owner: String, amount: Uint128 },
     /// Only with the "mintable" extension. If authorized, creates amount new tokens
-    /// and adds to the recipient balance.
-    Mint { recipient: String, amount: Uint128 },
+    /// and adds to the recipient balance. With the "vesting" extension, the tokens can be
+    /// locked until released by a vesting schedule.
+    Mint {
+        recipient: String,
+        amount: Uint128,
+        vesting: Option<VestingSchedule>,
+    },
     /// Only with the "mintable" extension. The current minter may set
     /// a new minter. Setting the minter to None will remove the
     /// token's minter forever.
@@ -70,4 +89,33 @@ pub enum Cw20ExecuteMsg {
     },
     /// If set as the "marketing" role on the contract, upload a new URL, SVG, or PNG for the token
     UploadLogo(Logo),
+    /// Only with the "hooks" extension. The admin may set a new admin, or remove it
+    /// for good when None.
+    UpdateAdmin { admin: Option<String> },
+    /// Only with the "hooks" extension. The admin registers a contract to be called with
+    /// `BalanceChangedHook` whenever balances change, with at most `gas_limit` gas, or a default
+    /// limit of the implementation if unset.
+    AddHook {
+        addr: String,
+        gas_limit: Option<u64>,
+    },
+    /// Only with the "hooks" extension. The admin unregisters a hook.
+    RemoveHook { addr: String },
+}
+
+/// The document signed by the owner of a `Permit`. The signature is the one a wallet returns for
+/// `signArbitrary(chain_id, owner, json)` (ADR-036), e.g. with Keplr or Leap, where `json` is the
+/// serialization of this document with the fields in declaration order and no spaces: the compact
+/// (r, s) secp256k1 signature of the sha256 hash of an amino sign doc with an empty chain id,
+/// holding a single `sign/MsgSignData` of the owner address and the base64 encoded `json`.
+#[cw_serde]
+pub struct PermitSignDoc {
+    /// Chain id of the token, so a permit can't be replayed on another chain.
+    pub chain_id: String,
+    /// Address of the token, so a permit can't be replayed on another token.
+    pub contract: String,
+    pub spender: String,
+    pub amount: Uint128,
+    pub expires: Option<Expiration>,
+    pub nonce: u64,
 }
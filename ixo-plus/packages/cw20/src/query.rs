@@ -5,6 +5,7 @@ use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Binary, Uint128};
 
 use crate::logo::LogoInfo;
+use crate::vesting::VestingSchedule;
 use cw_utils::Expiration;
 
 #[cw_serde]
@@ -20,6 +21,18 @@ pub enum Cw20QueryMsg {
     /// Returns how much spender can use from owner account, 0 if unset.
     /// Return type: AllowanceResponse.
     Allowance { owner: String, spender: String },
+    /// Only with "allowance" extension.
+    /// Returns the nonce the next `Permit` of owner must have, 0 if unset.
+    /// Return type: NonceResponse.
+    Nonce { owner: String },
+    /// Only with "vesting" extension.
+    /// Returns the vesting schedule of address, if any, and how many of its tokens are locked.
+    /// Return type: VestingScheduleResponse.
+    VestingSchedule { address: String },
+    /// Only with "vesting" extension.
+    /// Returns the balance address can transfer, i.e. without its locked tokens.
+    /// Return type: BalanceResponse.
+    UnlockedBalance { address: String },
     /// Only with "mintable" extension.
     /// Returns who can mint and the hard cap on maximum tokens after minting.
     /// Return type: MinterResponse.
@@ -70,6 +83,20 @@ pub struct AllowanceResponse {
     pub expires: Expiration,
 }
 
+#[cw_serde]
+pub struct NonceResponse {
+    pub nonce: u64,
+}
+
+#[cw_serde]
+pub struct VestingScheduleResponse {
+    pub schedule: Option<VestingSchedule>,
+    /// The amount of tokens released by the schedule
+    pub amount: Uint128,
+    /// The amount of tokens not released yet
+    pub locked: Uint128,
+}
+
 #[cw_serde]
 pub struct MinterResponse {
     pub minter: String,
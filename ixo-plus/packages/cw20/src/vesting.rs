@@ -0,0 +1,129 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Timestamp, Uint128};
+
+/// How vesting tokens are released over time. Until they are released, they count in the
+/// balance of their owner but can't be transferred, sent nor burned.
+#[cw_serde]
+pub enum VestingSchedule {
+    /// All tokens are released at once at `release_at`.
+    Cliff { release_at: Timestamp },
+    /// Tokens are released linearly from `start` to `end`, and none before `cliff` if set.
+    Linear {
+        start: Timestamp,
+        end: Timestamp,
+        cliff: Option<Timestamp>,
+    },
+    /// Each step releases its amount at its time. Steps are ordered by time and add up to
+    /// all tokens.
+    Steps { steps: Vec<VestingStep> },
+}
+
+#[cw_serde]
+pub struct VestingStep {
+    pub release_at: Timestamp,
+    pub amount: Uint128,
+}
+
+impl VestingSchedule {
+    /// Checks the schedule can release `amount` tokens
+    pub fn is_valid(&self, amount: Uint128) -> bool {
+        match self {
+            VestingSchedule::Cliff { .. } => true,
+            VestingSchedule::Linear { start, end, cliff } => {
+                start < end && cliff.iter().all(|cliff| start <= cliff && cliff <= end)
+            }
+            VestingSchedule::Steps { steps } => {
+                let ordered = steps
+                    .windows(2)
+                    .all(|pair| pair[0].release_at < pair[1].release_at);
+                let total = steps.iter().try_fold(Uint128::zero(), |total, step| {
+                    total.checked_add(step.amount)
+                });
+                ordered && steps.iter().all(|step| !step.amount.is_zero()) && total == Ok(amount)
+            }
+        }
+    }
+
+    /// Returns how many of `amount` tokens are released at `time`
+    pub fn released(&self, amount: Uint128, time: Timestamp) -> Uint128 {
+        match self {
+            VestingSchedule::Cliff { release_at } => {
+                if time >= *release_at {
+                    amount
+                } else {
+                    Uint128::zero()
+                }
+            }
+            VestingSchedule::Linear { start, end, cliff } => {
+                if time < cliff.unwrap_or(*start) || time <= *start {
+                    Uint128::zero()
+                } else if time >= *end {
+                    amount
+                } else {
+                    amount.multiply_ratio(time.nanos() - start.nanos(), end.nanos() - start.nanos())
+                }
+            }
+            VestingSchedule::Steps { steps } => steps
+                .iter()
+                .take_while(|step| step.release_at <= time)
+                .fold(Uint128::zero(), |released, step| released + step.amount)
+                .min(amount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_release() {
+        let schedule = VestingSchedule::Linear {
+            start: Timestamp::from_seconds(100),
+            end: Timestamp::from_seconds(200),
+            cliff: Some(Timestamp::from_seconds(150)),
+        };
+        let amount = Uint128::new(1000);
+        assert!(schedule.is_valid(amount));
+
+        let released = |seconds| schedule.released(amount, Timestamp::from_seconds(seconds));
+        assert_eq!(released(50), Uint128::zero());
+        assert_eq!(released(149), Uint128::zero());
+        assert_eq!(released(150), Uint128::new(500));
+        assert_eq!(released(175), Uint128::new(750));
+        assert_eq!(released(200), amount);
+        assert_eq!(released(300), amount);
+
+        let cliff_after_end = VestingSchedule::Linear {
+            start: Timestamp::from_seconds(100),
+            end: Timestamp::from_seconds(200),
+            cliff: Some(Timestamp::from_seconds(250)),
+        };
+        assert!(!cliff_after_end.is_valid(amount));
+    }
+
+    #[test]
+    fn step_release() {
+        let schedule = VestingSchedule::Steps {
+            steps: vec![
+                VestingStep {
+                    release_at: Timestamp::from_seconds(100),
+                    amount: Uint128::new(300),
+                },
+                VestingStep {
+                    release_at: Timestamp::from_seconds(200),
+                    amount: Uint128::new(700),
+                },
+            ],
+        };
+        assert!(schedule.is_valid(Uint128::new(1000)));
+        assert!(!schedule.is_valid(Uint128::new(900)));
+
+        let released =
+            |seconds| schedule.released(Uint128::new(1000), Timestamp::from_seconds(seconds));
+        assert_eq!(released(99), Uint128::zero());
+        assert_eq!(released(100), Uint128::new(300));
+        assert_eq!(released(199), Uint128::new(300));
+        assert_eq!(released(200), Uint128::new(1000));
+    }
+}
@@ -1,5 +1,7 @@
+use std::cell::RefCell;
 use std::fmt::{self, Debug};
 use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
 use anyhow::bail;
 use anyhow::Result as AnyResult;
@@ -9,6 +11,7 @@ use cosmwasm_std::{
     Empty, Querier, QuerierResult, QuerierWrapper, QueryRequest, Record, Storage, SystemError,
     SystemResult,
 };
+use cosmwasm_std::{GovMsg, IbcMsg, IbcQuery};
 use schemars::JsonSchema;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
@@ -17,9 +20,13 @@ use crate::bank::{Bank, BankKeeper, BankSudo};
 use crate::contracts::Contract;
 use crate::executor::{AppResponse, Executor};
 use crate::module::{FailingModule, Module};
-use crate::staking::{Distribution, DistributionKeeper, StakeKeeper, Staking, StakingSudo};
+use crate::staking::{DistributionKeeper, StakeKeeper, StakingSudo};
+use crate::stargate::StargateKeeper;
+#[cfg(feature = "stargate")]
+use crate::stargate::StargateMsg;
 use crate::transactions::transactional;
 use crate::wasm::{ContractData, Wasm, WasmKeeper, WasmSudo};
+use crate::{Distribution, Gov, Ibc, Staking, Stargate};
 
 pub fn next_block(block: &mut BlockInfo) {
     block.time = block.time.plus_seconds(5);
@@ -35,8 +42,15 @@ pub type BasicApp<ExecC = Empty, QueryC = Empty> = App<
     WasmKeeper<ExecC, QueryC>,
     StakeKeeper,
     DistributionKeeper,
+    FailingModule<IbcMsg, IbcQuery, Empty>,
+    FailingModule<GovMsg, Empty, Empty>,
+    StargateKeeper<ExecC, QueryC>,
 >;
 
+/// The router of an `App`, borrowed mutably only while modules are initialized
+type RouterCell<Bank, Custom, Wasm, Staking, Distr, Ibc, Gov, Stargate> =
+    RefCell<Router<Bank, Custom, Wasm, Staking, Distr, Ibc, Gov, Stargate>>;
+
 /// Router is a persisted state. You can query this.
 /// Execution generally happens on the RouterCache, which then can be atomically committed or rolled back.
 /// We offer .execute() as a wrapper around cache, execute, commit/rollback process.
@@ -48,21 +62,24 @@ pub struct App<
     Wasm = WasmKeeper<Empty, Empty>,
     Staking = StakeKeeper,
     Distr = DistributionKeeper,
+    Ibc = FailingModule<IbcMsg, IbcQuery, Empty>,
+    Gov = FailingModule<GovMsg, Empty, Empty>,
+    Stargate = StargateKeeper<Empty, Empty>,
 > {
-    router: Router<Bank, Custom, Wasm, Staking, Distr>,
+    pub router: RouterCell<Bank, Custom, Wasm, Staking, Distr, Ibc, Gov, Stargate>,
     api: Api,
-    storage: Storage,
-    block: BlockInfo,
+    storage: RefCell<Storage>,
+    block: RefCell<BlockInfo>,
 }
 
-fn no_init<BankT, CustomT, WasmT, StakingT, DistrT>(
-    _: &mut Router<BankT, CustomT, WasmT, StakingT, DistrT>,
+fn no_init<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>(
+    _: &mut Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
     _: &dyn Api,
     _: &mut dyn Storage,
 ) {
 }
 
-impl Default for BasicApp {
+impl Default for App {
     fn default() -> Self {
         Self::new(no_init)
     }
@@ -79,6 +96,9 @@ impl BasicApp {
                 WasmKeeper<Empty, Empty>,
                 StakeKeeper,
                 DistributionKeeper,
+                FailingModule<IbcMsg, IbcQuery, Empty>,
+                FailingModule<GovMsg, Empty, Empty>,
+                StargateKeeper<Empty, Empty>,
             >,
             &dyn Api,
             &mut dyn Storage,
@@ -101,6 +121,9 @@ where
             WasmKeeper<ExecC, QueryC>,
             StakeKeeper,
             DistributionKeeper,
+            FailingModule<IbcMsg, IbcQuery, Empty>,
+            FailingModule<GovMsg, Empty, Empty>,
+            StargateKeeper<ExecC, QueryC>,
         >,
         &dyn Api,
         &mut dyn Storage,
@@ -109,8 +132,8 @@ where
     AppBuilder::new_custom().build(init_fn)
 }
 
-impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT> Querier
-    for App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT> Querier
+    for App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     CustomT::ExecT: Clone + fmt::Debug + PartialEq + JsonSchema + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
@@ -121,16 +144,25 @@ where
     CustomT: Module,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
 {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
         self.router
-            .querier(&self.api, &self.storage, &self.block)
+            .borrow()
+            .querier(
+                &self.api,
+                self.storage.borrow().deref(),
+                &self.block.borrow(),
+            )
             .raw_query(bin_request)
     }
 }
 
-impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT> Executor<CustomT::ExecT>
-    for App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    Executor<CustomT::ExecT>
+    for App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     CustomT::ExecT: Clone + fmt::Debug + PartialEq + JsonSchema + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
@@ -141,9 +173,12 @@ where
     CustomT: Module,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
 {
     fn execute(
-        &mut self,
+        &self,
         sender: Addr,
         msg: cosmwasm_std::CosmosMsg<CustomT::ExecT>,
     ) -> AnyResult<AppResponse> {
@@ -163,10 +198,13 @@ pub type BasicAppBuilder<ExecC, QueryC> = AppBuilder<
     WasmKeeper<ExecC, QueryC>,
     StakeKeeper,
     DistributionKeeper,
+    FailingModule<IbcMsg, IbcQuery, Empty>,
+    FailingModule<GovMsg, Empty, Empty>,
+    StargateKeeper<ExecC, QueryC>,
 >;
 
 /// Utility to build App in stages. If particular items wont be set, defaults would be used
-pub struct AppBuilder<Bank, Api, Storage, Custom, Wasm, Staking, Distr> {
+pub struct AppBuilder<Bank, Api, Storage, Custom, Wasm, Staking, Distr, Ibc, Gov, Stargate> {
     api: Api,
     block: BlockInfo,
     storage: Storage,
@@ -175,6 +213,9 @@ pub struct AppBuilder<Bank, Api, Storage, Custom, Wasm, Staking, Distr> {
     custom: Custom,
     staking: Staking,
     distribution: Distr,
+    ibc: Ibc,
+    gov: Gov,
+    stargate: Stargate,
 }
 
 impl Default
@@ -186,6 +227,9 @@ impl Default
         WasmKeeper<Empty, Empty>,
         StakeKeeper,
         DistributionKeeper,
+        FailingModule<IbcMsg, IbcQuery, Empty>,
+        FailingModule<GovMsg, Empty, Empty>,
+        StargateKeeper<Empty, Empty>,
     >
 {
     fn default() -> Self {
@@ -202,6 +246,9 @@ impl
         WasmKeeper<Empty, Empty>,
         StakeKeeper,
         DistributionKeeper,
+        FailingModule<IbcMsg, IbcQuery, Empty>,
+        FailingModule<GovMsg, Empty, Empty>,
+        StargateKeeper<Empty, Empty>,
     >
 {
     /// Creates builder with default components working with empty exec and query messages.
@@ -215,6 +262,9 @@ impl
             custom: FailingModule::new(),
             staking: StakeKeeper::new(),
             distribution: DistributionKeeper::new(),
+            ibc: FailingModule::new(),
+            gov: FailingModule::new(),
+            stargate: StargateKeeper::new(),
         }
     }
 }
@@ -228,6 +278,9 @@ impl<ExecC, QueryC>
         WasmKeeper<ExecC, QueryC>,
         StakeKeeper,
         DistributionKeeper,
+        FailingModule<IbcMsg, IbcQuery, Empty>,
+        FailingModule<GovMsg, Empty, Empty>,
+        StargateKeeper<ExecC, QueryC>,
     >
 where
     ExecC: Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
@@ -245,12 +298,15 @@ where
             custom: FailingModule::new(),
             staking: StakeKeeper::new(),
             distribution: DistributionKeeper::new(),
+            ibc: FailingModule::new(),
+            gov: FailingModule::new(),
+            stargate: StargateKeeper::new(),
         }
     }
 }
 
-impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
-    AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 {
     /// Overwrites default wasm executor.
     ///
@@ -261,18 +317,56 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     /// Also it is possible to completely abandon trait bounding here which would not be bad idea,
     /// however it might make the message on build creepy in many cases, so as for properly build
     /// `App` we always want `Wasm` to be `Wasm`, some checks are done early.
-    pub fn with_wasm<C: Module, NewWasm: Wasm<C::ExecT, C::QueryT>>(
+    pub fn with_wasm<ExecC, QueryC: CustomQuery, NewWasm: Wasm<ExecC, QueryC>>(
         self,
         wasm: NewWasm,
-    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, NewWasm, StakingT, DistrT> {
+    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, NewWasm, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
+        let AppBuilder {
+            bank,
+            api,
+            storage,
+            custom,
+            block,
+            staking,
+            distribution,
+            ibc,
+            gov,
+            stargate,
+            ..
+        } = self;
+
+        AppBuilder {
+            api,
+            block,
+            storage,
+            bank,
+            wasm,
+            custom,
+            staking,
+            distribution,
+            ibc,
+            gov,
+            stargate,
+        }
+    }
+
+    pub fn with_stargate(
+        self,
+        stargate: StargateT,
+    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
         let AppBuilder {
             bank,
             api,
             storage,
             custom,
             block,
+            wasm,
             staking,
             distribution,
+            ibc,
+            gov,
             ..
         } = self;
 
@@ -285,6 +379,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
@@ -292,7 +389,8 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     pub fn with_bank<NewBank: Bank>(
         self,
         bank: NewBank,
-    ) -> AppBuilder<NewBank, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT> {
+    ) -> AppBuilder<NewBank, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
         let AppBuilder {
             wasm,
             api,
@@ -301,6 +399,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             block,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
             ..
         } = self;
 
@@ -313,6 +414,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
@@ -320,7 +424,8 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     pub fn with_api<NewApi: Api>(
         self,
         api: NewApi,
-    ) -> AppBuilder<BankT, NewApi, StorageT, CustomT, WasmT, StakingT, DistrT> {
+    ) -> AppBuilder<BankT, NewApi, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
         let AppBuilder {
             wasm,
             bank,
@@ -329,6 +434,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             block,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
             ..
         } = self;
 
@@ -341,6 +449,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
@@ -348,7 +459,8 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     pub fn with_storage<NewStorage: Storage>(
         self,
         storage: NewStorage,
-    ) -> AppBuilder<BankT, ApiT, NewStorage, CustomT, WasmT, StakingT, DistrT> {
+    ) -> AppBuilder<BankT, ApiT, NewStorage, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
         let AppBuilder {
             wasm,
             api,
@@ -357,6 +469,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             block,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
             ..
         } = self;
 
@@ -369,6 +484,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
@@ -384,7 +502,8 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     pub fn with_custom<NewCustom: Module>(
         self,
         custom: NewCustom,
-    ) -> AppBuilder<BankT, ApiT, StorageT, NewCustom, WasmT, StakingT, DistrT> {
+    ) -> AppBuilder<BankT, ApiT, StorageT, NewCustom, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    {
         let AppBuilder {
             wasm,
             bank,
@@ -393,6 +512,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             block,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
             ..
         } = self;
 
@@ -405,6 +527,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
@@ -412,7 +537,8 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     pub fn with_staking<NewStaking: Staking>(
         self,
         staking: NewStaking,
-    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, NewStaking, DistrT> {
+    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, NewStaking, DistrT, IbcT, GovT, StargateT>
+    {
         let AppBuilder {
             wasm,
             api,
@@ -421,6 +547,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             block,
             bank,
             distribution,
+            ibc,
+            gov,
+            stargate,
             ..
         } = self;
 
@@ -433,14 +562,98 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
-    /// Overwrites default bank interface
+    /// Overwrites default distribution interface
     pub fn with_distribution<NewDistribution: Distribution>(
         self,
         distribution: NewDistribution,
-    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, NewDistribution> {
+    ) -> AppBuilder<
+        BankT,
+        ApiT,
+        StorageT,
+        CustomT,
+        WasmT,
+        StakingT,
+        NewDistribution,
+        IbcT,
+        GovT,
+        StargateT,
+    > {
+        let AppBuilder {
+            wasm,
+            api,
+            storage,
+            custom,
+            block,
+            staking,
+            bank,
+            ibc,
+            gov,
+            stargate,
+            ..
+        } = self;
+
+        AppBuilder {
+            api,
+            block,
+            storage,
+            bank,
+            wasm,
+            custom,
+            staking,
+            distribution,
+            ibc,
+            gov,
+            stargate,
+        }
+    }
+
+    /// Overwrites default ibc interface
+    pub fn with_ibc<NewIbc: Ibc>(
+        self,
+        ibc: NewIbc,
+    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, NewIbc, GovT, StargateT>
+    {
+        let AppBuilder {
+            wasm,
+            api,
+            storage,
+            custom,
+            block,
+            staking,
+            bank,
+            distribution,
+            gov,
+            stargate,
+            ..
+        } = self;
+
+        AppBuilder {
+            api,
+            block,
+            storage,
+            bank,
+            wasm,
+            custom,
+            staking,
+            distribution,
+            ibc,
+            gov,
+            stargate,
+        }
+    }
+
+    /// Overwrites default gov interface
+    pub fn with_gov<NewGov: Gov>(
+        self,
+        gov: NewGov,
+    ) -> AppBuilder<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, NewGov, StargateT>
+    {
         let AppBuilder {
             wasm,
             api,
@@ -449,6 +662,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             block,
             staking,
             bank,
+            distribution,
+            ibc,
+            stargate,
             ..
         } = self;
 
@@ -461,6 +677,9 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom,
             staking,
             distribution,
+            ibc,
+            gov,
+            stargate,
         }
     }
 
@@ -476,7 +695,7 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
     pub fn build<F>(
         self,
         init_fn: F,
-    ) -> App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
+    ) -> App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
     where
         BankT: Bank,
         ApiT: Api,
@@ -485,7 +704,13 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
         WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
         StakingT: Staking,
         DistrT: Distribution,
-        F: FnOnce(&mut Router<BankT, CustomT, WasmT, StakingT, DistrT>, &dyn Api, &mut dyn Storage),
+        IbcT: Ibc,
+        GovT: Gov,
+        F: FnOnce(
+            &mut Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
+            &dyn Api,
+            &mut dyn Storage,
+        ),
     {
         let router = Router {
             wasm: self.wasm,
@@ -493,21 +718,24 @@ impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
             custom: self.custom,
             staking: self.staking,
             distribution: self.distribution,
+            ibc: self.ibc,
+            gov: self.gov,
+            stargate: self.stargate,
         };
 
-        let mut app = App {
-            router,
+        let app = App {
+            router: RefCell::new(router),
             api: self.api,
-            block: self.block,
-            storage: self.storage,
+            block: RefCell::new(self.block),
+            storage: RefCell::new(self.storage),
         };
         app.init_modules(init_fn);
         app
     }
 }
 
-impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
-    App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     WasmT: Wasm<CustomT::ExecT, CustomT::QueryT>,
     BankT: Bank,
@@ -516,29 +744,43 @@ where
     CustomT: Module,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
 {
-    pub fn init_modules<F, T>(&mut self, init_fn: F) -> T
+    pub fn init_modules<F, T>(&self, init_fn: F) -> T
     where
         F: FnOnce(
-            &mut Router<BankT, CustomT, WasmT, StakingT, DistrT>,
+            &mut Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
             &dyn Api,
             &mut dyn Storage,
         ) -> T,
     {
-        init_fn(&mut self.router, &self.api, &mut self.storage)
+        init_fn(
+            self.router.borrow_mut().deref_mut(),
+            &self.api,
+            self.storage.borrow_mut().deref_mut(),
+        )
     }
 
     pub fn read_module<F, T>(&self, query_fn: F) -> T
     where
-        F: FnOnce(&Router<BankT, CustomT, WasmT, StakingT, DistrT>, &dyn Api, &dyn Storage) -> T,
+        F: FnOnce(
+            &Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
+            &dyn Api,
+            &dyn Storage,
+        ) -> T,
     {
-        query_fn(&self.router, &self.api, &self.storage)
+        query_fn(
+            &self.router.borrow(),
+            &self.api,
+            self.storage.borrow().deref(),
+        )
     }
 }
 
 // Helper functions to call some custom WasmKeeper logic.
 // They show how we can easily add such calls to other custom keepers (CustomT, StakingT, etc)
-impl<BankT, ApiT, StorageT, CustomT, StakingT, DistrT>
+impl<BankT, ApiT, StorageT, CustomT, StakingT, DistrT, IbcT, GovT, StargateT>
     App<
         BankT,
         ApiT,
@@ -547,6 +789,9 @@ impl<BankT, ApiT, StorageT, CustomT, StakingT, DistrT>
         WasmKeeper<CustomT::ExecT, CustomT::QueryT>,
         StakingT,
         DistrT,
+        IbcT,
+        GovT,
+        StargateT,
     >
 where
     BankT: Bank,
@@ -555,12 +800,15 @@ where
     CustomT: Module,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
     CustomT::ExecT: Clone + fmt::Debug + PartialEq + JsonSchema + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
 {
     /// This registers contract code (like uploading wasm bytecode on a chain),
     /// so it can later be used to instantiate a contract.
-    pub fn store_code(&mut self, code: Box<dyn Contract<CustomT::ExecT, CustomT::QueryT>>) -> u64 {
+    pub fn store_code(&self, code: Box<dyn Contract<CustomT::ExecT, CustomT::QueryT>>) -> u64 {
         self.init_modules(|router, _, _| router.wasm.store_code(code) as u64)
     }
 
@@ -575,8 +823,8 @@ where
     }
 }
 
-impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
-    App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     CustomT::ExecT: std::fmt::Debug + PartialEq + Clone + JsonSchema + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
@@ -587,19 +835,23 @@ where
     CustomT: Module,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
 {
-    pub fn set_block(&mut self, block: BlockInfo) {
-        self.block = block;
+    pub fn set_block(&self, block: BlockInfo) {
+        self.block.replace(block);
     }
 
     // this let's use use "next block" steps that add eg. one height and 5 seconds
-    pub fn update_block<F: Fn(&mut BlockInfo)>(&mut self, action: F) {
-        action(&mut self.block);
+    pub fn update_block<F: Fn(&mut BlockInfo)>(&self, action: F) {
+        action(self.block.borrow_mut().deref_mut());
+        // self.block.replace_with(action);
     }
 
     /// Returns a copy of the current block_info
     pub fn block_info(&self) -> BlockInfo {
-        self.block.clone()
+        self.block.borrow().clone()
     }
 
     /// Simple helper so we get access to all the QuerierWrapper helpers,
@@ -612,7 +864,7 @@ where
     /// This will create a cache before the execution, so no state changes are persisted if any of them
     /// return an error. But all writes are persisted on success.
     pub fn execute_multi(
-        &mut self,
+        &self,
         sender: Addr,
         msgs: Vec<cosmwasm_std::CosmosMsg<CustomT::ExecT>>,
     ) -> AnyResult<Vec<AppResponse>> {
@@ -627,9 +879,17 @@ where
             storage,
         } = self;
 
-        transactional(&mut *storage, |write_cache, _| {
+        transactional(storage.borrow_mut().deref_mut(), |write_cache, _| {
             msgs.into_iter()
-                .map(|msg| router.execute(&*api, write_cache, block, sender.clone(), msg))
+                .map(|msg| {
+                    router.borrow().execute(
+                        api,
+                        write_cache,
+                        block.borrow().deref(),
+                        sender.clone(),
+                        msg,
+                    )
+                })
                 .collect()
         })
     }
@@ -638,7 +898,7 @@ where
     /// This will create a cache before the execution, so no state changes are persisted if this
     /// returns an error, but all are persisted on success.
     pub fn wasm_sudo<T: Serialize, U: Into<Addr>>(
-        &mut self,
+        &self,
         contract_addr: U,
         msg: &T,
     ) -> AnyResult<AppResponse> {
@@ -651,17 +911,22 @@ where
             storage,
         } = self;
 
-        transactional(&mut *storage, |write_cache, _| {
-            router
-                .wasm
-                .sudo(&*api, contract_addr.into(), write_cache, router, block, msg)
+        transactional(storage.borrow_mut().deref_mut(), |write_cache, _| {
+            router.borrow().wasm.sudo(
+                api,
+                contract_addr.into(),
+                write_cache,
+                router.borrow().deref(),
+                block.borrow().deref(),
+                msg,
+            )
         })
     }
 
     /// Runs arbitrary SudoMsg.
     /// This will create a cache before the execution, so no state changes are persisted if this
     /// returns an error, but all are persisted on success.
-    pub fn sudo(&mut self, msg: SudoMsg) -> AnyResult<AppResponse> {
+    pub fn sudo(&self, msg: SudoMsg) -> AnyResult<AppResponse> {
         // we need to do some caching of storage here, once in the entry point:
         // meaning, wrap current state, all writes go to a cache, only when execute
         // returns a success do we flush it (otherwise drop it)
@@ -672,24 +937,31 @@ where
             storage,
         } = self;
 
-        transactional(&mut *storage, |write_cache, _| {
-            router.sudo(&*api, write_cache, block, msg)
+        transactional(storage.borrow_mut().deref_mut(), |write_cache, _| {
+            router
+                .borrow()
+                .sudo(api, write_cache, block.borrow().deref(), msg)
         })
     }
 }
 
-pub struct Router<Bank, Custom, Wasm, Staking, Distr> {
+pub struct Router<Bank, Custom, Wasm, Staking, Distr, Ibc, Gov, Stargate> {
     // this can remain crate-only as all special functions are wired up to app currently
     // we need to figure out another format for wasm, as some like sudo need to be called after init
-    pub(crate) wasm: Wasm,
+    pub wasm: Wasm,
     // these must be pub so we can initialize them (super user) on build
     pub bank: Bank,
     pub custom: Custom,
     pub staking: Staking,
     pub distribution: Distr,
+    pub ibc: Ibc,
+    pub gov: Gov,
+    // TODO: make stargate generic
+    pub stargate: Stargate,
 }
 
-impl<BankT, CustomT, WasmT, StakingT, DistrT> Router<BankT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
+    Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     CustomT::ExecT: Clone + fmt::Debug + PartialEq + JsonSchema + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
@@ -698,6 +970,9 @@ where
     BankT: Bank,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
 {
     pub fn querier<'a>(
         &'a self,
@@ -771,8 +1046,8 @@ pub trait CosmosRouter {
     ) -> AnyResult<AppResponse>;
 }
 
-impl<BankT, CustomT, WasmT, StakingT, DistrT> CosmosRouter
-    for Router<BankT, CustomT, WasmT, StakingT, DistrT>
+impl<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT> CosmosRouter
+    for Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>
 where
     CustomT::ExecT: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
     CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
@@ -781,6 +1056,9 @@ where
     BankT: Bank,
     StakingT: Staking,
     DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
 {
     type ExecC = CustomT::ExecT;
     type QueryC = CustomT::QueryT;
@@ -797,10 +1075,25 @@ where
             CosmosMsg::Wasm(msg) => self.wasm.execute(api, storage, self, block, sender, msg),
             CosmosMsg::Bank(msg) => self.bank.execute(api, storage, self, block, sender, msg),
             CosmosMsg::Custom(msg) => self.custom.execute(api, storage, self, block, sender, msg),
+            #[cfg(feature = "staking")]
             CosmosMsg::Staking(msg) => self.staking.execute(api, storage, self, block, sender, msg),
+            #[cfg(feature = "staking")]
             CosmosMsg::Distribution(msg) => self
                 .distribution
                 .execute(api, storage, self, block, sender, msg),
+            #[cfg(feature = "ibc")]
+            CosmosMsg::Ibc(msg) => self.ibc.execute(api, storage, self, block, sender, msg),
+            #[cfg(feature = "gov")]
+            CosmosMsg::Gov(msg) => self.gov.execute(api, storage, self, block, sender, msg),
+            #[cfg(feature = "stargate")]
+            CosmosMsg::Stargate { type_url, value } => self.stargate.execute(
+                api,
+                storage,
+                self,
+                block,
+                sender,
+                StargateMsg { type_url, value },
+            ),
             _ => bail!("Cannot execute {:?}", msg),
         }
     }
@@ -820,7 +1113,21 @@ where
             QueryRequest::Wasm(req) => self.wasm.query(api, storage, &querier, block, req),
             QueryRequest::Bank(req) => self.bank.query(api, storage, &querier, block, req),
             QueryRequest::Custom(req) => self.custom.query(api, storage, &querier, block, req),
+            #[cfg(feature = "staking")]
             QueryRequest::Staking(req) => self.staking.query(api, storage, &querier, block, req),
+            #[cfg(feature = "ibc")]
+            QueryRequest::Ibc(req) => self.ibc.query(api, storage, &querier, block, req),
+            #[cfg(feature = "stargate")]
+            QueryRequest::Stargate { path, data } => self.stargate.query(
+                api,
+                storage,
+                &querier,
+                block,
+                StargateMsg {
+                    type_url: path,
+                    value: data,
+                },
+            ),
             _ => unimplemented!(),
         }
     }
@@ -838,8 +1145,11 @@ where
                     .sudo(api, msg.contract_addr, storage, self, block, msg.msg)
             }
             SudoMsg::Bank(msg) => self.bank.sudo(api, storage, self, block, msg),
+            #[cfg(feature = "staking")]
             SudoMsg::Staking(msg) => self.staking.sudo(api, storage, self, block, msg),
             SudoMsg::Custom(_) => unimplemented!(),
+            #[cfg(not(feature = "staking"))]
+            SudoMsg::Staking(_) => bail!("Staking is disabled, enable the `staking` feature"),
         }
     }
 }
@@ -960,8 +1270,22 @@ mod test {
     use crate::test_helpers::{CustomMsg, EmptyMsg};
     use crate::transactions::StorageTransaction;
 
-    fn get_balance<BankT, ApiT, StorageT, CustomT, WasmT>(
-        app: &App<BankT, ApiT, StorageT, CustomT, WasmT>,
+    type IbcModule = FailingModule<IbcMsg, IbcQuery, Empty>;
+    type GovModule = FailingModule<GovMsg, Empty, Empty>;
+
+    fn get_balance<BankT, ApiT, StorageT, CustomT, WasmT, StargateT>(
+        app: &App<
+            BankT,
+            ApiT,
+            StorageT,
+            CustomT,
+            WasmT,
+            StakeKeeper,
+            DistributionKeeper,
+            IbcModule,
+            GovModule,
+            StargateT,
+        >,
         addr: &Addr,
     ) -> Vec<Coin>
     where
@@ -972,19 +1296,22 @@ mod test {
         ApiT: Api,
         StorageT: Storage,
         CustomT: Module,
+        StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
     {
         app.wrap().query_all_balances(addr).unwrap()
     }
 
     #[test]
     fn update_block() {
-        let mut app = App::default();
+        let app = App::default();
+
+        let old_block = app.block_info();
+        let BlockInfo { time, height, .. } = old_block;
 
-        let BlockInfo { time, height, .. } = app.block;
         app.update_block(next_block);
 
-        assert_eq!(time.plus_seconds(5), app.block.time);
-        assert_eq!(height + 1, app.block.height);
+        assert_eq!(time.plus_seconds(5), app.block.borrow().time);
+        assert_eq!(height + 1, app.block.borrow().height);
     }
 
     #[test]
@@ -994,7 +1321,7 @@ mod test {
         let init_funds = vec![coin(20, "btc"), coin(100, "eth")];
         let rcpt_funds = vec![coin(5, "btc")];
 
-        let mut app = App::new(|router, _, storage| {
+        let app = App::new(|router, _, storage| {
             // initialization moved to App construction
             router
                 .bank
@@ -1040,7 +1367,7 @@ mod test {
         let owner = Addr::unchecked("owner");
         let init_funds = vec![coin(20, "btc"), coin(100, "eth")];
 
-        let mut app = App::new(|router, _, storage| {
+        let app = App::new(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1123,7 +1450,7 @@ mod test {
         let owner = Addr::unchecked("owner");
         let init_funds = vec![coin(20, "btc"), coin(100, "eth")];
 
-        let mut app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
+        let app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1224,7 +1551,7 @@ mod test {
         let owner = Addr::unchecked("owner");
         let init_funds = vec![coin(20, "btc"), coin(100, "eth")];
 
-        let mut app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
+        let app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1318,7 +1645,7 @@ mod test {
         let owner = Addr::unchecked("owner");
         let init_funds = vec![coin(100, "eth")];
 
-        let mut app = App::new(|router, _, storage| {
+        let app = App::new(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1483,7 +1810,7 @@ mod test {
             let lottery = coin(54321, denom);
             let bonus = coin(12321, denom);
 
-            let mut app = BasicAppBuilder::<CustomMsg, Empty>::new_custom()
+            let app = BasicAppBuilder::<CustomMsg, Empty>::new_custom()
                 .with_custom(CustomHandler {})
                 .build(|router, _, storage| {
                     router
@@ -1518,7 +1845,7 @@ mod test {
         let random = Addr::unchecked("random");
         let init_funds = vec![coin(20, "btc"), coin(100, "eth")];
 
-        let mut app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
+        let app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1601,8 +1928,8 @@ mod test {
         // TODO: check error?
     }
 
-    fn query_router<BankT, CustomT, WasmT, StakingT, DistrT>(
-        router: &Router<BankT, CustomT, WasmT, StakingT, DistrT>,
+    fn query_router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>(
+        router: &Router<BankT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
         api: &dyn Api,
         storage: &dyn Storage,
         rcpt: &Addr,
@@ -1629,8 +1956,8 @@ mod test {
         val.amount
     }
 
-    fn query_app<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>(
-        app: &App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT>,
+    fn query_app<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>(
+        app: &App<BankT, ApiT, StorageT, CustomT, WasmT, StakingT, DistrT, IbcT, GovT, StargateT>,
         rcpt: &Addr,
     ) -> Vec<Coin>
     where
@@ -1644,6 +1971,9 @@ mod test {
         CustomT: Module,
         StakingT: Staking,
         DistrT: Distribution,
+        IbcT: Ibc,
+        GovT: Gov,
+        StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
     {
         let query = BankQuery::AllBalances {
             address: rcpt.into(),
@@ -1660,7 +1990,7 @@ mod test {
         let rcpt = Addr::unchecked("recipient");
         let init_funds = vec![coin(20, "btc"), coin(100, "eth")];
 
-        let mut app = App::new(|router, _, storage| {
+        let app = App::new(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1668,17 +1998,25 @@ mod test {
         });
 
         // cache 1 - send some tokens
-        let mut cache = StorageTransaction::new(&app.storage);
+        let storage = app.storage.take();
+        let mut cache = StorageTransaction::new(&storage);
         let msg = BankMsg::Send {
             to_address: rcpt.clone().into(),
             amount: coins(25, "eth"),
         };
         app.router
-            .execute(&app.api, &mut cache, &app.block, owner.clone(), msg.into())
+            .borrow()
+            .execute(
+                &app.api,
+                &mut cache,
+                &app.block.borrow(),
+                owner.clone(),
+                msg.into(),
+            )
             .unwrap();
 
         // shows up in cache
-        let cached_rcpt = query_router(&app.router, &app.api, &cache, &rcpt);
+        let cached_rcpt = query_router(&app.router.borrow(), &app.api, &cache, &rcpt);
         assert_eq!(coins(25, "eth"), cached_rcpt);
         let router_rcpt = query_app(&app, &rcpt);
         assert_eq!(router_rcpt, vec![]);
@@ -1690,20 +2028,21 @@ mod test {
                 amount: coins(12, "eth"),
             };
             app.router
-                .execute(&app.api, cache2, &app.block, owner, msg.into())
+                .borrow()
+                .execute(&app.api, cache2, &app.block.borrow(), owner, msg.into())
                 .unwrap();
 
             // shows up in 2nd cache
-            let cached_rcpt = query_router(&app.router, &app.api, read, &rcpt);
+            let cached_rcpt = query_router(&app.router.borrow(), &app.api, read, &rcpt);
             assert_eq!(coins(25, "eth"), cached_rcpt);
-            let cached2_rcpt = query_router(&app.router, &app.api, cache2, &rcpt);
+            let cached2_rcpt = query_router(&app.router.borrow(), &app.api, cache2, &rcpt);
             assert_eq!(coins(37, "eth"), cached2_rcpt);
             Ok(())
         })
         .unwrap();
 
         // apply first to router
-        cache.prepare().commit(&mut app.storage);
+        cache.prepare().commit(app.storage.borrow_mut().deref_mut());
 
         let committed = query_app(&app, &rcpt);
         assert_eq!(coins(37, "eth"), committed);
@@ -1720,7 +2059,7 @@ mod test {
         let beneficiary = Addr::unchecked("beneficiary");
         let init_funds = coins(30, "btc");
 
-        let mut app = App::new(|router, _, storage| {
+        let app = App::new(|router, _, storage| {
             router
                 .bank
                 .init_balance(storage, &owner, init_funds)
@@ -1842,7 +2181,7 @@ mod test {
         let owner2 = Addr::unchecked("owner2");
         let beneficiary = Addr::unchecked("beneficiary");
 
-        let mut app = App::default();
+        let app = App::default();
 
         // create a hackatom contract with some funds
         let contract_id = app.store_code(hackatom::contract());
@@ -1939,7 +2278,7 @@ mod test {
 
         #[test]
         fn no_submsg() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -1965,7 +2304,7 @@ mod test {
 
         #[test]
         fn single_submsg() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -1997,7 +2336,7 @@ mod test {
 
         #[test]
         fn single_submsg_no_reply() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2024,7 +2363,7 @@ mod test {
 
         #[test]
         fn single_no_submsg_data() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2051,7 +2390,7 @@ mod test {
 
         #[test]
         fn single_no_top_level_data() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2086,7 +2425,7 @@ mod test {
             let owner = Addr::unchecked("owner");
             let init_funds = coins(100, "tgd");
 
-            let mut app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
+            let app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
                 router
                     .bank
                     .init_balance(storage, &owner, init_funds)
@@ -2143,7 +2482,7 @@ mod test {
 
         #[test]
         fn multiple_submsg() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2190,7 +2529,7 @@ mod test {
 
         #[test]
         fn multiple_submsg_no_reply() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2222,7 +2561,7 @@ mod test {
 
         #[test]
         fn multiple_submsg_mixed() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2269,7 +2608,7 @@ mod test {
 
         #[test]
         fn nested_submsg() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2320,7 +2659,7 @@ mod test {
 
         #[test]
         fn empty_attribute_key() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2350,7 +2689,7 @@ mod test {
 
         #[test]
         fn empty_attribute_value() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2380,7 +2719,7 @@ mod test {
 
         #[test]
         fn empty_event_attribute_key() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2409,7 +2748,7 @@ mod test {
 
         #[test]
         fn empty_event_attribute_value() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2438,7 +2777,7 @@ mod test {
 
         #[test]
         fn too_short_event_type() {
-            let mut app = App::default();
+            let app = App::default();
 
             let owner = Addr::unchecked("owner");
 
@@ -2477,7 +2816,7 @@ mod test {
             let custom_handler = CachingCustomHandler::<CustomMsg, Empty>::new();
             let custom_handler_state = custom_handler.state();
 
-            let mut app = AppBuilder::new_custom()
+            let app = AppBuilder::new_custom()
                 .with_api(api)
                 .with_custom(custom_handler)
                 .build(no_init);
@@ -2520,7 +2859,7 @@ mod test {
             let owner = Addr::unchecked("owner");
             let init_funds = vec![coin(20, "btc")];
 
-            let mut app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
+            let app = custom_app::<CustomMsg, Empty, _>(|router, _, storage| {
                 router
                     .bank
                     .init_balance(storage, &owner, init_funds)
@@ -2554,7 +2893,7 @@ mod test {
         #[test]
         fn instantiate_with_data_works() {
             let owner = Addr::unchecked("owner");
-            let mut app = BasicApp::new(|_, _, _| {});
+            let app = BasicApp::new(|_, _, _| {});
 
             // set up echo contract
             let code_id = app.store_code(echo::contract());
@@ -2582,7 +2921,7 @@ mod test {
         #[test]
         fn instantiate_with_reply_works() {
             let owner = Addr::unchecked("owner");
-            let mut app = BasicApp::new(|_, _, _| {});
+            let app = BasicApp::new(|_, _, _| {});
 
             // set up echo contract
             let code_id = app.store_code(echo::contract());
@@ -2633,7 +2972,7 @@ mod test {
         #[test]
         fn execute_wrapped_properly() {
             let owner = Addr::unchecked("owner");
-            let mut app = BasicApp::new(|_, _, _| {});
+            let app = BasicApp::new(|_, _, _| {});
 
             // set up reflect contract
             let code_id = app.store_code(echo::contract());
@@ -2658,7 +2997,7 @@ mod test {
         #[test]
         fn simple_instantiation() {
             let owner = Addr::unchecked("owner");
-            let mut app = App::default();
+            let app = App::default();
 
             // set up contract
             let code_id = app.store_code(error::contract(false));
@@ -2683,7 +3022,7 @@ mod test {
         #[test]
         fn simple_call() {
             let owner = Addr::unchecked("owner");
-            let mut app = App::default();
+            let app = App::default();
 
             // set up contract
             let code_id = app.store_code(error::contract(true));
@@ -2713,7 +3052,7 @@ mod test {
         #[test]
         fn nested_call() {
             let owner = Addr::unchecked("owner");
-            let mut app = App::default();
+            let app = App::default();
 
             let error_code_id = app.store_code(error::contract(true));
             let caller_code_id = app.store_code(caller::contract());
@@ -2753,7 +3092,7 @@ mod test {
         #[test]
         fn double_nested_call() {
             let owner = Addr::unchecked("owner");
-            let mut app = App::default();
+            let app = App::default();
 
             let error_code_id = app.store_code(error::contract(true));
             let caller_code_id = app.store_code(caller::contract());
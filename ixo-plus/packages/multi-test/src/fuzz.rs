@@ -0,0 +1,371 @@
+//! Property-based testing of contract message sequences.
+//!
+//! A [`Harness`] draws random sequences of [`Action`]s from a user supplied proptest strategy,
+//! replays every sequence against a freshly built app and checks each registered invariant
+//! after setup and after every step. When an invariant breaks, proptest shrinks the sequence
+//! and the harness reports the smallest one that still fails.
+//!
+//! Actions rejected by the contract are not failures: the transaction is rolled back as it
+//! would be on chain, and the invariants are checked against the unchanged state.
+
+use std::fmt::{self, Debug, Display};
+
+use anyhow::Result as AnyResult;
+use cosmwasm_std::{Addr, Coin};
+use proptest::collection::vec;
+use proptest::strategy::Strategy;
+use proptest::test_runner::{Config, TestCaseError, TestError, TestRunner};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::Executor;
+
+pub use proptest;
+
+/// A single `ExecuteMsg` sent to the contract under test.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Action<M> {
+    pub sender: Addr,
+    pub msg: M,
+    pub funds: Vec<Coin>,
+}
+
+impl<M> Action<M> {
+    pub fn new(sender: impl Into<String>, msg: M) -> Self {
+        Action {
+            sender: Addr::unchecked(sender),
+            msg,
+            funds: vec![],
+        }
+    }
+
+    pub fn with_funds(mut self, funds: Vec<Coin>) -> Self {
+        self.funds = funds;
+        self
+    }
+}
+
+/// The minimal failing sequence found by [`Harness::check`].
+#[derive(Clone, Debug)]
+pub struct Failure<M> {
+    /// Shrunk sequence of actions that still breaks the invariant.
+    pub steps: Vec<Action<M>>,
+    /// Error returned by each executed step, `None` for the ones that succeeded.
+    pub errors: Vec<Option<String>>,
+    /// Number of steps executed when the invariant broke; `0` means right after setup.
+    pub after: usize,
+    pub invariant: String,
+    pub reason: String,
+}
+
+impl<M: Debug> Display for Failure<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "invariant `{}` broken after {} step(s): {}",
+            self.invariant, self.after, self.reason
+        )?;
+        for (idx, (step, error)) in self.steps.iter().zip(&self.errors).enumerate() {
+            write!(
+                f,
+                "  #{} {} -> {:?} funds {:?}",
+                idx + 1,
+                step.sender,
+                step.msg,
+                step.funds
+            )?;
+            match error {
+                Some(err) => writeln!(f, " (rejected: {})", err)?,
+                None => writeln!(f)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+type Setup<A> = dyn Fn() -> (A, Addr);
+type Invariant<A> = dyn Fn(&A, &Addr) -> AnyResult<()>;
+
+/// Runs random action sequences against a contract and checks invariants after every step.
+///
+/// `setup` must build the app from scratch and return it together with the address of the
+/// contract under test; it is called once per generated sequence, so it has to be
+/// deterministic for failures to shrink and reproduce.
+pub struct Harness<A> {
+    setup: Box<Setup<A>>,
+    invariants: Vec<(String, Box<Invariant<A>>)>,
+    max_steps: usize,
+    config: Config,
+}
+
+impl<A> Harness<A> {
+    pub fn new(setup: impl Fn() -> (A, Addr) + 'static) -> Self {
+        Harness {
+            setup: Box::new(setup),
+            invariants: vec![],
+            max_steps: 32,
+            // there is no source file to persist failing seeds next to
+            config: Config {
+                failure_persistence: None,
+                ..Config::default()
+            },
+        }
+    }
+
+    /// Registers an invariant, which fails by returning an error.
+    pub fn with_invariant(
+        mut self,
+        name: impl Into<String>,
+        check: impl Fn(&A, &Addr) -> AnyResult<()> + 'static,
+    ) -> Self {
+        self.invariants.push((name.into(), Box::new(check)));
+        self
+    }
+
+    /// Longest sequence that is generated, 32 by default.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Number of sequences that are generated, 256 by default.
+    pub fn with_cases(mut self, cases: u32) -> Self {
+        self.config.cases = cases;
+        self
+    }
+
+    /// Overrides the whole proptest configuration, e.g. to fix the RNG seed.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Generates sequences from `actions` and returns the minimal failing one, if any.
+    pub fn check<C, M, S>(&self, actions: S) -> Result<(), Failure<M>>
+    where
+        A: Executor<C>,
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+        M: Serialize + Clone + Debug,
+        S: Strategy<Value = Action<M>>,
+    {
+        let mut runner = TestRunner::new(self.config.clone());
+        let sequences = vec(actions, 1..=self.max_steps.max(1));
+        let result = runner.run(&sequences, |steps| {
+            self.replay(steps)
+                .map_err(|failure| TestCaseError::fail(failure.reason))
+        });
+        match result {
+            Ok(()) => Ok(()),
+            Err(TestError::Fail(_, steps)) => self.replay(steps),
+            Err(TestError::Abort(reason)) => panic!("fuzzing aborted: {}", reason),
+        }
+    }
+
+    /// Like [`Harness::check`], but panics with the minimal failing sequence.
+    #[track_caller]
+    pub fn run<C, M, S>(&self, actions: S)
+    where
+        A: Executor<C>,
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+        M: Serialize + Clone + Debug,
+        S: Strategy<Value = Action<M>>,
+    {
+        if let Err(failure) = self.check(actions) {
+            panic!("{}", failure);
+        }
+    }
+
+    /// Runs one sequence against a fresh app.
+    fn replay<C, M>(&self, mut steps: Vec<Action<M>>) -> Result<(), Failure<M>>
+    where
+        A: Executor<C>,
+        C: Clone + Debug + PartialEq + JsonSchema + 'static,
+        M: Serialize + Clone + Debug,
+    {
+        let (app, contract) = (self.setup)();
+        let mut errors = Vec::with_capacity(steps.len());
+        for after in 0..=steps.len() {
+            if after > 0 {
+                let step = &steps[after - 1];
+                let res = app.execute_contract(
+                    step.sender.clone(),
+                    contract.clone(),
+                    &step.msg,
+                    &step.funds,
+                );
+                errors.push(res.err().map(|err| err.to_string()));
+            }
+            for (name, check) in &self.invariants {
+                if let Err(err) = check(&app, &contract) {
+                    // whatever came after the breaking step is irrelevant to the report
+                    steps.truncate(after);
+                    return Err(Failure {
+                        steps,
+                        errors,
+                        after,
+                        invariant: name.clone(),
+                        reason: err.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use anyhow::ensure;
+    use cosmwasm_std::{
+        to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdError, StdResult,
+    };
+    use cw_storage_plus::{Item, Map};
+    use proptest::prelude::*;
+    use proptest::test_runner::{RngAlgorithm, RngSeed};
+    use serde::Deserialize;
+
+    use crate::{App, ContractWrapper};
+
+    const TOTAL: Item<u64> = Item::new("total");
+    const BALANCES: Map<&Addr, u64> = Map::new("balances");
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum ExecMsg {
+        Deposit { amount: u64 },
+        Withdraw { amount: u64 },
+    }
+
+    #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum QueryMsg {
+        Total {},
+        Balance { address: String },
+    }
+
+    /// A ledger whose withdrawals forget to lower the total.
+    fn execute(deps: DepsMut, _env: Env, info: MessageInfo, msg: ExecMsg) -> StdResult<Response> {
+        let balance = BALANCES
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        match msg {
+            ExecMsg::Deposit { amount } => {
+                let total = TOTAL.may_load(deps.storage)?.unwrap_or_default();
+                BALANCES.save(deps.storage, &info.sender, &(balance + amount))?;
+                TOTAL.save(deps.storage, &(total + amount))?;
+            }
+            ExecMsg::Withdraw { amount } => {
+                let left = balance
+                    .checked_sub(amount)
+                    .ok_or_else(|| StdError::generic_err("insufficient balance"))?;
+                BALANCES.save(deps.storage, &info.sender, &left)?;
+            }
+        }
+        Ok(Response::new())
+    }
+
+    fn instantiate(_: DepsMut, _: Env, _: MessageInfo, _: Empty) -> StdResult<Response> {
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+        match msg {
+            QueryMsg::Total {} => to_binary(&TOTAL.may_load(deps.storage)?.unwrap_or_default()),
+            QueryMsg::Balance { address } => to_binary(
+                &BALANCES
+                    .may_load(deps.storage, &Addr::unchecked(address))?
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+
+    fn setup() -> (App, Addr) {
+        let app = App::default();
+        let code_id = app.store_code(Box::new(ContractWrapper::new(execute, instantiate, query)));
+        let contract = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &Empty {},
+                &[],
+                "ledger",
+                None,
+            )
+            .unwrap();
+        (app, contract)
+    }
+
+    const USERS: [&str; 2] = ["alice", "bob"];
+
+    fn total_matches_balances(app: &App, contract: &Addr) -> AnyResult<()> {
+        let total: u64 = app.wrap().query_wasm_smart(contract, &QueryMsg::Total {})?;
+        let mut sum = 0u64;
+        for user in USERS {
+            let balance: u64 = app.wrap().query_wasm_smart(
+                contract,
+                &QueryMsg::Balance {
+                    address: user.to_string(),
+                },
+            )?;
+            sum += balance;
+        }
+        ensure!(total == sum, "total {} != sum of balances {}", total, sum);
+        Ok(())
+    }
+
+    fn actions(withdraw: bool) -> impl Strategy<Value = Action<ExecMsg>> {
+        let user = prop::sample::select(USERS.to_vec());
+        let msg = if withdraw {
+            prop_oneof![
+                (1..100u64).prop_map(|amount| ExecMsg::Deposit { amount }),
+                (1..100u64).prop_map(|amount| ExecMsg::Withdraw { amount }),
+            ]
+            .boxed()
+        } else {
+            (1..100u64)
+                .prop_map(|amount| ExecMsg::Deposit { amount })
+                .boxed()
+        };
+        (user, msg).prop_map(|(user, msg)| Action::new(user, msg))
+    }
+
+    #[test]
+    fn holding_invariant_passes() {
+        Harness::new(setup)
+            .with_invariant("total matches balances", total_matches_balances)
+            .with_cases(16)
+            .run(actions(false));
+    }
+
+    #[test]
+    fn broken_invariant_is_shrunk() {
+        // a fixed seed keeps the shrunk sequence the same on every run
+        let failure = Harness::new(setup)
+            .with_invariant("total matches balances", total_matches_balances)
+            .with_config(Config {
+                cases: 256,
+                failure_persistence: None,
+                rng_algorithm: RngAlgorithm::ChaCha,
+                rng_seed: RngSeed::Fixed(42),
+                ..Config::default()
+            })
+            .check(actions(true))
+            .unwrap_err();
+
+        // nothing can be dropped from a deposit followed by a withdrawal of the same funds
+        assert_eq!(failure.invariant, "total matches balances");
+        assert_eq!(failure.after, 2);
+        assert_eq!(failure.errors, vec![None, None]);
+        match &failure.steps[..] {
+            [deposit, withdraw] => {
+                assert!(matches!(deposit.msg, ExecMsg::Deposit { .. }));
+                assert!(matches!(withdraw.msg, ExecMsg::Withdraw { .. }));
+                assert_eq!(deposit.sender, withdraw.sender);
+            }
+            steps => panic!("sequence was not shrunk: {:?}", steps),
+        }
+        assert!(failure.to_string().contains("broken after 2 step(s)"));
+    }
+}
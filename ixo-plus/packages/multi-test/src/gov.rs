@@ -6,7 +6,7 @@ pub trait Gov: Module<ExecT = GovMsg, QueryT = Empty, SudoT = Empty> {}
 
 impl Gov for FailingModule<GovMsg, Empty, Empty> {}
 
-#[cfg(test)]
+#[cfg(all(test, feature = "gov"))]
 mod test {
     use cosmwasm_std::{Addr, Binary, Empty, GovMsg};
 
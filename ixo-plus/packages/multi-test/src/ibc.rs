@@ -6,7 +6,7 @@ pub trait Ibc: Module<ExecT = IbcMsg, QueryT = IbcQuery, SudoT = Empty> {}
 
 impl Ibc for FailingModule<IbcMsg, IbcQuery, Empty> {}
 
-#[cfg(test)]
+#[cfg(all(test, feature = "ibc"))]
 mod test {
     use cosmwasm_std::{Addr, Binary, Empty, IbcMsg, IbcQuery};
 
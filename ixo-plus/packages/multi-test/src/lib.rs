@@ -13,9 +13,15 @@ mod contracts;
 pub mod custom_handler;
 pub mod error;
 mod executor;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+mod gov;
+mod ibc;
 mod module;
 mod prefixed_storage;
+mod replay;
 mod staking;
+mod stargate;
 mod test_helpers;
 mod transactions;
 mod wasm;
@@ -27,6 +33,32 @@ pub use crate::app::{
 pub use crate::bank::{Bank, BankKeeper, BankSudo};
 pub use crate::contracts::{Contract, ContractWrapper};
 pub use crate::executor::{AppResponse, Executor};
+pub use crate::gov::Gov;
+pub use crate::ibc::Ibc;
 pub use crate::module::{FailingModule, Module};
-pub use crate::staking::{DistributionKeeper, StakeKeeper, Staking, StakingInfo, StakingSudo};
-pub use crate::wasm::{Wasm, WasmKeeper, WasmSudo};
+pub use crate::replay::{
+    AccountBalance, ChainState, CodeMap, ContractState, DelegationState, StakingState,
+    StorageRecord,
+};
+pub use crate::staking::{
+    Distribution, DistributionKeeper, StakeKeeper, Staking, StakingInfo, StakingSudo,
+};
+pub use crate::stargate::*;
+pub use crate::wasm::{AddressGenerator, ContractData, Wasm, WasmKeeper, WasmSudo};
+
+pub const QUERY_ALL_BALANCES_PATH: &str = "/cosmos.bank.v1beta1.Query/AllBalances";
+pub const QUERY_BALANCE_PATH: &str = "/cosmos.bank.v1beta1.Query/Balance";
+pub const QUERY_SUPPLY_PATH: &str = "/cosmos.bank.v1beta1.Query/SupplyOf";
+pub const QUERY_DENOM_METADATA_PATH: &str = "/cosmos.bank.v1beta1.Query/DenomMetadata";
+pub const MSG_SEND_TYPE_URL: &str = "/cosmos.bank.v1beta1.MsgSend";
+pub const QUERY_GRANTS_PATH: &str = "/cosmos.authz.v1beta1.Query/Grants";
+pub const MSG_GRANT_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgGrant";
+pub const MSG_EXEC_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgExec";
+pub const MSG_REVOKE_TYPE_URL: &str = "/cosmos.authz.v1beta1.MsgRevoke";
+pub const GENERIC_AUTHORIZATION_TYPE_URL: &str = "/cosmos.authz.v1beta1.GenericAuthorization";
+pub const SEND_AUTHORIZATION_TYPE_URL: &str = "/cosmos.bank.v1beta1.SendAuthorization";
+pub const QUERY_TOKEN_METADATA_PATH: &str = "/ixo.token.v1beta1.Query/TokenMetadata";
+pub const QUERY_WASM_CONTRACT_SMART_PATH: &str = "/cosmwasm.wasm.v1.Query/SmartContractState";
+pub const QUERY_WASM_CONTRACT_RAW_PATH: &str = "/cosmwasm.wasm.v1.Query/RawContractState";
+pub const QUERY_WASM_CONTRACT_INFO_PATH: &str = "/cosmwasm.wasm.v1.Query/ContractInfo";
+pub const QUERY_WASM_CODE_INFO_PATH: &str = "/cosmwasm.wasm.v1.Query/CodeInfo";
@@ -0,0 +1,516 @@
+//! Seeding an `App` with state exported from a live chain, so a failing transaction can be
+//! re-run locally against the same contracts, storage and balances.
+//!
+//! Contract code cannot be exported, so every code id in the export has to be mapped to a
+//! `Contract` implementation (usually a `ContractWrapper` around the contract's entry points)
+//! when the state is loaded. The contracts keep their on-chain code ids and addresses.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_std::{
+    from_slice, Addr, Api, Binary, BlockInfo, Coin, CustomQuery, Record, StakingMsg, Storage,
+    Validator,
+};
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::app::{App, CosmosRouter};
+use crate::bank::{Bank, BankSudo};
+use crate::contracts::Contract;
+use crate::module::Module;
+use crate::staking::{StakeKeeper, StakingInfo};
+use crate::transactions::transactional;
+use crate::wasm::{ContractData, WasmKeeper};
+use crate::{Distribution, Gov, Ibc, Stargate};
+
+/// Genesis-style export of the state a transaction depends on.
+///
+/// All sections are optional when deserializing. Loading staking state requires the `staking`
+/// feature, which routes the delegations to the staking module.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct ChainState {
+    /// Block the app continues from; the current block is kept when this is missing.
+    #[serde(default)]
+    pub block: Option<BlockInfo>,
+    #[serde(default)]
+    pub contracts: Vec<ContractState>,
+    #[serde(default)]
+    pub balances: Vec<AccountBalance>,
+    #[serde(default)]
+    pub staking: Option<StakingState>,
+}
+
+impl ChainState {
+    pub fn from_json(data: &[u8]) -> AnyResult<Self> {
+        Ok(from_slice(data)?)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ContractState {
+    pub address: String,
+    pub code_id: u64,
+    pub creator: String,
+    #[serde(default)]
+    pub admin: Option<String>,
+    pub label: String,
+    /// Height the contract was instantiated at.
+    #[serde(default)]
+    pub created: u64,
+    /// Raw wasm storage of the contract.
+    #[serde(default)]
+    pub storage: Vec<StorageRecord>,
+}
+
+/// A single raw key-value pair of contract storage.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct StorageRecord {
+    pub key: Binary,
+    pub value: Binary,
+}
+
+impl From<Record> for StorageRecord {
+    fn from((key, value): Record) -> Self {
+        StorageRecord {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
+
+impl From<StorageRecord> for Record {
+    fn from(record: StorageRecord) -> Self {
+        (record.key.into(), record.value.into())
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct AccountBalance {
+    pub address: String,
+    pub coins: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StakingState {
+    #[serde(default)]
+    pub params: Option<StakingInfo>,
+    #[serde(default)]
+    pub validators: Vec<Validator>,
+    #[serde(default)]
+    pub delegations: Vec<DelegationState>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct DelegationState {
+    pub delegator: String,
+    pub validator: String,
+    pub amount: Coin,
+}
+
+/// Implementations of the contract codes referenced by an export, keyed by on-chain code id.
+pub type CodeMap<ExecC, QueryC> = HashMap<u64, Box<dyn Contract<ExecC, QueryC>>>;
+
+impl<BankT, ApiT, StorageT, CustomT, DistrT, IbcT, GovT, StargateT>
+    App<
+        BankT,
+        ApiT,
+        StorageT,
+        CustomT,
+        WasmKeeper<CustomT::ExecT, CustomT::QueryT>,
+        StakeKeeper,
+        DistrT,
+        IbcT,
+        GovT,
+        StargateT,
+    >
+where
+    BankT: Bank,
+    ApiT: Api,
+    StorageT: Storage,
+    CustomT: Module,
+    DistrT: Distribution,
+    IbcT: Ibc,
+    GovT: Gov,
+    CustomT::ExecT: Clone + fmt::Debug + PartialEq + JsonSchema + DeserializeOwned + 'static,
+    CustomT::QueryT: CustomQuery + DeserializeOwned + 'static,
+    StargateT: Stargate<CustomT::ExecT, CustomT::QueryT>,
+{
+    /// Seeds the app with an exported `ChainState`.
+    ///
+    /// `codes` must provide an implementation for every code id used by the exported
+    /// contracts; code ids already stored in the app cannot be reused. Balances are minted on
+    /// top of whatever the accounts already hold, and delegated tokens are minted to the
+    /// delegator before being delegated, so its liquid balance ends up as exported.
+    ///
+    /// Loading is atomic: the state is written to a cache that is only committed, and the
+    /// codes and block only applied, once everything loaded without error.
+    pub fn load_state(
+        &self,
+        state: ChainState,
+        codes: CodeMap<CustomT::ExecT, CustomT::QueryT>,
+    ) -> AnyResult<()> {
+        for contract in &state.contracts {
+            if !codes.contains_key(&contract.code_id) {
+                bail!(
+                    "No contract code provided for code id {} used by {}",
+                    contract.code_id,
+                    contract.address
+                );
+            }
+        }
+        self.read_module(|router, _, _| -> AnyResult<()> {
+            for code_id in codes.keys() {
+                if router.wasm.has_code(*code_id as usize) {
+                    bail!("Code id {} is already registered", code_id);
+                }
+            }
+            Ok(())
+        })?;
+        if state.staking.is_some() && !cfg!(feature = "staking") {
+            bail!("Loading staking state requires the `staking` feature");
+        }
+
+        let block = state.block.unwrap_or_else(|| self.block_info());
+        self.init_modules(|router, api, storage| -> AnyResult<()> {
+            transactional(storage, |cache, _| {
+                for contract in state.contracts {
+                    let info = ContractData {
+                        code_id: contract.code_id as usize,
+                        creator: Addr::unchecked(contract.creator),
+                        admin: contract.admin.map(Addr::unchecked),
+                        label: contract.label,
+                        created: contract.created,
+                    };
+                    router.wasm.restore_contract_unchecked(
+                        cache,
+                        &Addr::unchecked(contract.address),
+                        &info,
+                        contract.storage.into_iter().map(Record::from),
+                    )?;
+                }
+
+                for balance in state.balances {
+                    router.sudo(
+                        api,
+                        cache,
+                        &block,
+                        BankSudo::Mint {
+                            to_address: balance.address,
+                            amount: balance.coins,
+                        }
+                        .into(),
+                    )?;
+                }
+
+                if let Some(staking) = state.staking {
+                    Self::load_staking(router, &router.staking, api, cache, &block, staking)?;
+                }
+                Ok(())
+            })?;
+
+            // cannot fail, all code ids were checked to be free above
+            for (code_id, code) in codes {
+                router.wasm.store_code_with_id(code_id as usize, code)?;
+            }
+            Ok(())
+        })?;
+
+        self.set_block(block);
+        Ok(())
+    }
+
+    fn load_staking(
+        router: &dyn CosmosRouter<ExecC = CustomT::ExecT, QueryC = CustomT::QueryT>,
+        keeper: &StakeKeeper,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        staking: StakingState,
+    ) -> AnyResult<()> {
+        if let Some(params) = staking.params {
+            keeper.setup(storage, params)?;
+        }
+        for validator in staking.validators {
+            keeper.add_validator(api, storage, block, validator)?;
+        }
+
+        for delegation in staking.delegations {
+            router.sudo(
+                api,
+                storage,
+                block,
+                BankSudo::Mint {
+                    to_address: delegation.delegator.clone(),
+                    amount: vec![delegation.amount.clone()],
+                }
+                .into(),
+            )?;
+            router.execute(
+                api,
+                storage,
+                block,
+                Addr::unchecked(delegation.delegator),
+                StakingMsg::Delegate {
+                    validator: delegation.validator,
+                    amount: delegation.amount,
+                }
+                .into(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Exports a contract in the format understood by `load_state`.
+    pub fn contract_state(&self, address: &Addr) -> AnyResult<ContractState> {
+        let info = self.contract_data(address)?;
+        Ok(ContractState {
+            address: address.to_string(),
+            code_id: info.code_id as u64,
+            creator: info.creator.into(),
+            admin: info.admin.map(Into::into),
+            label: info.label,
+            created: info.created,
+            storage: self
+                .dump_wasm_raw(address)
+                .into_iter()
+                .map(StorageRecord::from)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use cosmwasm_std::testing::mock_env;
+    use cosmwasm_std::{coin, Empty};
+
+    use crate::executor::Executor;
+    use crate::test_helpers::contracts::payout;
+    use crate::test_helpers::EmptyMsg;
+
+    fn payout_codes() -> CodeMap<Empty, Empty> {
+        let mut codes = CodeMap::new();
+        codes.insert(42, payout::contract());
+        codes
+    }
+
+    #[test]
+    fn exported_contract_round_trips() {
+        let source = App::default();
+        let code_id = source.store_code(payout::contract());
+        let contract = source
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("creator"),
+                &payout::InstantiateMessage {
+                    payout: coin(5, "uixo"),
+                },
+                &[],
+                "payout",
+                Some("admin".to_string()),
+            )
+            .unwrap();
+        let exported = source.contract_state(&contract).unwrap();
+
+        let mut codes = CodeMap::new();
+        codes.insert(exported.code_id, payout::contract());
+        let replica = App::default();
+        replica
+            .load_state(
+                ChainState {
+                    contracts: vec![exported],
+                    ..ChainState::default()
+                },
+                codes,
+            )
+            .unwrap();
+
+        assert_eq!(
+            replica.dump_wasm_raw(&contract),
+            source.dump_wasm_raw(&contract)
+        );
+        assert_eq!(
+            replica.contract_data(&contract).unwrap(),
+            source.contract_data(&contract).unwrap()
+        );
+    }
+
+    #[test]
+    fn replays_transaction_against_export() {
+        let export = r#"{
+            "block": {
+                "height": 1200,
+                "time": "1690000000000000000",
+                "chain_id": "ixo-5"
+            },
+            "contracts": [{
+                "address": "ixo1payoutcontract",
+                "code_id": 42,
+                "creator": "ixo1creator",
+                "label": "payout",
+                "created": 1100,
+                "storage": [
+                    { "key": "cGF5b3V0", "value": "eyJwYXlvdXQiOnsiZGVub20iOiJ1aXhvIiwiYW1vdW50IjoiNyJ9fQ==" },
+                    { "key": "Y291bnQ=", "value": "Mw==" }
+                ]
+            }],
+            "balances": [
+                { "address": "ixo1payoutcontract", "coins": [{ "denom": "uixo", "amount": "20" }] }
+            ]
+        }"#;
+        let state = ChainState::from_json(export.as_bytes()).unwrap();
+
+        let app = App::default();
+        app.load_state(state, payout_codes()).unwrap();
+        assert_eq!(app.block_info().height, 1200);
+        assert_eq!(app.block_info().chain_id, "ixo-5");
+
+        let contract = Addr::unchecked("ixo1payoutcontract");
+        let count: payout::CountResponse = app
+            .wrap()
+            .query_wasm_smart(&contract, &payout::QueryMsg::Count {})
+            .unwrap();
+        assert_eq!(count.count, 3);
+        assert_eq!(app.contract_data(&contract).unwrap().code_id, 42);
+
+        app.execute_contract(Addr::unchecked("ixo1user"), contract, &EmptyMsg {}, &[])
+            .unwrap();
+        let balance = app.wrap().query_balance("ixo1user", "uixo").unwrap();
+        assert_eq!(balance, coin(7, "uixo"));
+
+        // code ids stored afterwards do not clash with the imported ones
+        assert_eq!(app.store_code(payout::contract()), 43);
+    }
+
+    #[test]
+    fn missing_code_is_rejected() {
+        let state = ChainState {
+            contracts: vec![ContractState {
+                address: "ixo1contract".to_string(),
+                code_id: 7,
+                creator: "ixo1creator".to_string(),
+                admin: None,
+                label: "unknown".to_string(),
+                created: 0,
+                storage: vec![],
+            }],
+            ..ChainState::default()
+        };
+        let err = App::default()
+            .load_state(state, payout_codes())
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No contract code provided for code id 7 used by ixo1contract"
+        );
+    }
+
+    #[test]
+    fn failed_load_leaves_app_untouched() {
+        let contract = ContractState {
+            address: "ixo1contract".to_string(),
+            code_id: 42,
+            creator: "ixo1creator".to_string(),
+            admin: None,
+            label: "payout".to_string(),
+            created: 0,
+            storage: vec![],
+        };
+        let state = ChainState {
+            block: Some(BlockInfo {
+                height: 1200,
+                ..mock_env().block
+            }),
+            balances: vec![AccountBalance {
+                address: "ixo1user".to_string(),
+                coins: vec![coin(20, "uixo")],
+            }],
+            ..ChainState::default()
+        };
+
+        // the duplicated contract is only detected after the first one was written
+        let app = App::default();
+        let err = app
+            .load_state(
+                ChainState {
+                    contracts: vec![contract.clone(), contract.clone()],
+                    ..state.clone()
+                },
+                payout_codes(),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Contract ixo1contract already exists");
+        assert_ne!(app.block_info().height, 1200);
+        assert!(app.contract_data(&Addr::unchecked("ixo1contract")).is_err());
+        assert_eq!(app.wrap().query_all_balances("ixo1user").unwrap(), vec![]);
+
+        // the codes were not registered either, so the fixed export still loads
+        app.load_state(
+            ChainState {
+                contracts: vec![contract],
+                ..state
+            },
+            payout_codes(),
+        )
+        .unwrap();
+        assert_eq!(app.block_info().height, 1200);
+        assert_eq!(
+            app.wrap().query_all_balances("ixo1user").unwrap(),
+            vec![coin(20, "uixo")]
+        );
+    }
+
+    #[test]
+    fn registered_code_id_is_rejected() {
+        let app = App::default();
+        app.load_state(ChainState::default(), payout_codes())
+            .unwrap();
+        let err = app
+            .load_state(ChainState::default(), payout_codes())
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Code id 42 is already registered");
+    }
+
+    #[cfg(feature = "staking")]
+    #[test]
+    fn loads_staking_state() {
+        let export = r#"{
+            "balances": [{ "address": "ixo1delegator", "coins": [{ "denom": "uixo", "amount": "10" }] }],
+            "staking": {
+                "params": { "bonded_denom": "uixo", "unbonding_time": 1814400, "apr": "0.2" },
+                "validators": [{
+                    "address": "ixovaloper1validator",
+                    "commission": "0.05",
+                    "max_commission": "0.2",
+                    "max_change_rate": "0.01"
+                }],
+                "delegations": [
+                    { "delegator": "ixo1delegator", "validator": "ixovaloper1validator", "amount": { "denom": "uixo", "amount": "100" } }
+                ]
+            }
+        }"#;
+        let app = App::default();
+        app.load_state(
+            ChainState::from_json(export.as_bytes()).unwrap(),
+            CodeMap::new(),
+        )
+        .unwrap();
+
+        assert_eq!(app.wrap().query_bonded_denom().unwrap(), "uixo");
+        let delegation = app
+            .wrap()
+            .query_delegation("ixo1delegator", "ixovaloper1validator")
+            .unwrap()
+            .unwrap();
+        assert_eq!(delegation.amount, coin(100, "uixo"));
+        assert_eq!(
+            app.wrap().query_all_balances("ixo1delegator").unwrap(),
+            vec![coin(10, "uixo")]
+        );
+    }
+}
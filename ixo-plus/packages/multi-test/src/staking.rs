@@ -1,4 +1,4 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap, VecDeque};
 
 use anyhow::{anyhow, bail, Result as AnyResult};
 use schemars::JsonSchema;
@@ -18,7 +18,7 @@ use crate::prefixed_storage::{prefixed, prefixed_read};
 use crate::{BankSudo, Module};
 
 // Contains some general staking parameters
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
 pub struct StakingInfo {
     /// The denominator of the staking token
     pub bonded_denom: String,
@@ -48,6 +48,9 @@ struct Shares {
 impl Shares {
     /// Calculates the share of validator rewards that should be given to this staker.
     pub fn share_of_rewards(&self, validator: &ValidatorInfo, rewards: Decimal) -> Decimal {
+        if validator.stake.is_zero() {
+            return Decimal::zero();
+        }
         rewards * self.stake / validator.stake
     }
 }
@@ -74,6 +77,14 @@ impl ValidatorInfo {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+struct Unbonding {
+    pub delegator: Addr,
+    pub validator: Addr,
+    pub amount: Uint128,
+    pub payout_at: Timestamp,
+}
+
 const STAKING_INFO: Item<StakingInfo> = Item::new("staking_info");
 /// (staker_addr, validator_addr) -> shares
 const STAKES: Map<(&Addr, &Addr), Shares> = Map::new("stakes");
@@ -83,9 +94,15 @@ const VALIDATORS: Deque<Validator> = Deque::new("validators");
 /// Contains additional info for each validator
 const VALIDATOR_INFO: Map<&Addr, ValidatorInfo> = Map::new("validator_info");
 /// The queue of unbonding operations. This is needed because unbonding has a waiting time. See [`StakeKeeper`]
-const UNBONDING_QUEUE: Deque<(Addr, Timestamp, u128)> = Deque::new("unbonding_queue");
+const UNBONDING_QUEUE: Item<VecDeque<Unbonding>> = Item::new("unbonding_queue");
+/// (addr) -> addr. Maps addresses to the address they have delegated
+/// to receive their staking rewards. A missing key => no delegation
+/// has been set.
+const WITHDRAW_ADDRESS: Map<&Addr, Addr> = Map::new("withdraw_address");
 
 pub const NAMESPACE_STAKING: &[u8] = b"staking";
+// https://github.com/cosmos/cosmos-sdk/blob/4f6f6c00021f4b5ee486bbb71ae2071a8ceb47c9/x/distribution/types/keys.go#L16
+pub const NAMESPACE_DISTRIBUTION: &[u8] = b"distribution";
 
 // We need to expand on this, but we will need this to properly test out staking
 #[derive(Clone, std::fmt::Debug, PartialEq, Eq, JsonSchema)]
@@ -404,8 +421,17 @@ impl StakeKeeper {
             validator_info.stake = validator_info.stake.checked_add(amount)?;
         }
 
+        // check if any unbonding stake is left
+        let unbonding = UNBONDING_QUEUE
+            .may_load(staking_storage)?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|ub| ub.delegator == *delegator && ub.validator == *validator)
+            .map(|ub| ub.amount)
+            .sum::<Uint128>();
+
         // save updated values
-        if shares.stake.is_zero() {
+        if shares.stake.is_zero() && unbonding.is_zero() {
             // no more stake, so remove
             STAKES.remove(staking_storage, (delegator, validator));
             validator_info.stakers.remove(delegator);
@@ -460,6 +486,18 @@ impl StakeKeeper {
                 )?;
             }
         }
+        // go through the queue to slash all pending unbondings
+        let mut unbonding_queue = UNBONDING_QUEUE
+            .may_load(staking_storage)?
+            .unwrap_or_default();
+        unbonding_queue
+            .iter_mut()
+            .filter(|ub| ub.validator == *validator)
+            .for_each(|ub| {
+                ub.amount = ub.amount * remaining_percentage;
+            });
+        UNBONDING_QUEUE.save(staking_storage, &unbonding_queue)?;
+
         VALIDATOR_INFO.save(staking_storage, validator, &validator_info)?;
         Ok(())
     }
@@ -555,14 +593,16 @@ impl Module for StakeKeeper {
                 )?;
                 // add tokens to unbonding queue
                 let staking_info = Self::get_staking_info(&staking_storage)?;
-                UNBONDING_QUEUE.push_back(
-                    &mut staking_storage,
-                    &(
-                        sender.clone(),
-                        block.time.plus_seconds(staking_info.unbonding_time),
-                        amount.amount.u128(),
-                    ),
-                )?;
+                let mut unbonding_queue = UNBONDING_QUEUE
+                    .may_load(&staking_storage)?
+                    .unwrap_or_default();
+                unbonding_queue.push_back(Unbonding {
+                    delegator: sender.clone(),
+                    validator,
+                    amount: amount.amount,
+                    payout_at: block.time.plus_seconds(staking_info.unbonding_time),
+                });
+                UNBONDING_QUEUE.save(&mut staking_storage, &unbonding_queue)?;
                 Ok(AppResponse { events, data: None })
             }
             StakingMsg::Redelegate {
@@ -623,18 +663,49 @@ impl Module for StakeKeeper {
                 Ok(AppResponse::default())
             }
             StakingSudo::ProcessQueue {} => {
+                let staking_storage = prefixed_read(storage, NAMESPACE_STAKING);
+                let mut unbonding_queue = UNBONDING_QUEUE
+                    .may_load(&staking_storage)?
+                    .unwrap_or_default();
                 loop {
                     let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
-                    let front = UNBONDING_QUEUE.front(&staking_storage)?;
-                    match front {
+                    match unbonding_queue.front() {
                         // assuming the queue is sorted by payout_at
-                        Some((_, payout_at, _)) if payout_at <= block.time => {
+                        Some(Unbonding { payout_at, .. }) if payout_at <= &block.time => {
                             // remove from queue
-                            let (delegator, _, amount) =
-                                UNBONDING_QUEUE.pop_front(&mut staking_storage)?.unwrap();
+                            let Unbonding {
+                                delegator,
+                                validator,
+                                amount,
+                                ..
+                            } = unbonding_queue.pop_front().unwrap();
+
+                            // remove staking entry if it is empty
+                            let delegation = self
+                                .get_stake(&staking_storage, &delegator, &validator)?
+                                .map(|mut stake| {
+                                    // add unbonding amounts
+                                    stake.amount += unbonding_queue
+                                        .iter()
+                                        .filter(|u| {
+                                            u.delegator == delegator && u.validator == validator
+                                        })
+                                        .map(|u| u.amount)
+                                        .sum::<Uint128>();
+                                    stake
+                                });
+                            match delegation {
+                                Some(delegation) if delegation.amount.is_zero() => {
+                                    STAKES.remove(&mut staking_storage, (&delegator, &validator));
+                                }
+                                None => {
+                                    STAKES.remove(&mut staking_storage, (&delegator, &validator))
+                                }
+                                _ => {}
+                            }
 
                             let staking_info = Self::get_staking_info(&staking_storage)?;
-                            if amount > 0 {
+                            if !amount.is_zero() {
                                 router.execute(
                                     api,
                                     storage,
@@ -642,7 +713,10 @@ impl Module for StakeKeeper {
                                     self.module_addr.clone(),
                                     BankMsg::Send {
                                         to_address: delegator.into_string(),
-                                        amount: vec![coin(amount, &staking_info.bonded_denom)],
+                                        amount: vec![coin(
+                                            amount.u128(),
+                                            &staking_info.bonded_denom,
+                                        )],
                                     }
                                     .into(),
                                 )?;
@@ -651,6 +725,8 @@ impl Module for StakeKeeper {
                         _ => break,
                     }
                 }
+                let mut staking_storage = prefixed(storage, NAMESPACE_STAKING);
+                UNBONDING_QUEUE.save(&mut staking_storage, &unbonding_queue)?;
                 Ok(AppResponse::default())
             }
         }
@@ -673,6 +749,16 @@ impl Module for StakeKeeper {
                 let delegator = api.addr_validate(&delegator)?;
                 let validators = self.get_validators(&staking_storage)?;
 
+                let mut unbondings = HashMap::new();
+                for ub in UNBONDING_QUEUE
+                    .may_load(&staking_storage)?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|ub| ub.delegator == delegator)
+                {
+                    *unbondings.entry(ub.validator.into_string()).or_default() += ub.amount;
+                }
+
                 let res: AnyResult<Vec<Delegation>> = validators
                     .into_iter()
                     .filter_map(|validator| {
@@ -685,10 +771,17 @@ impl Module for StakeKeeper {
                             )
                             .transpose()?;
 
-                        Some(amount.map(|amount| Delegation {
-                            delegator,
-                            validator: validator.address,
-                            amount,
+                        Some(amount.map(|mut amount| {
+                            // include unbonding amounts, mimicing the behaviour of the Cosmos SDK
+                            amount.amount += unbondings
+                                .get(&validator.address)
+                                .copied()
+                                .unwrap_or(Uint128::zero());
+                            Delegation {
+                                delegator,
+                                validator: validator.address,
+                                amount,
+                            }
                         }))
                     })
                     .collect();
@@ -706,13 +799,10 @@ impl Module for StakeKeeper {
                 };
                 let delegator = api.addr_validate(&delegator)?;
 
-                let shares = match STAKES.load(&staking_storage, (&delegator, &validator_addr)) {
-                    Ok(stakes) => stakes,
-                    Err(_) => {
-                        let response = DelegationResponse { delegation: None };
-                        return Ok(to_binary(&response)?);
-                    }
-                };
+                let shares = STAKES
+                    .may_load(&staking_storage, (&delegator, &validator_addr))?
+                    .unwrap_or_default();
+
                 let validator_info = VALIDATOR_INFO.load(&staking_storage, &validator_addr)?;
                 let reward = Self::get_rewards_internal(
                     &staking_storage,
@@ -722,22 +812,40 @@ impl Module for StakeKeeper {
                     &validator_info,
                 )?;
                 let staking_info = Self::get_staking_info(&staking_storage)?;
+
+                // include unbonding amounts, mimicing the behaviour of the Cosmos SDK
+                let unbonding_amounts: Uint128 = UNBONDING_QUEUE
+                    .may_load(&staking_storage)?
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|ub| ub.delegator == delegator && ub.validator == validator)
+                    .map(|ub| ub.amount)
+                    .sum();
+
                 let amount = coin(
-                    (shares.stake * Uint128::new(1)).u128(),
+                    (shares.stake * Uint128::new(1))
+                        .checked_add(unbonding_amounts)?
+                        .u128(),
                     staking_info.bonded_denom,
                 );
-                let full_delegation_response = DelegationResponse {
-                    delegation: Some(FullDelegation {
-                        delegator,
-                        validator,
-                        amount: amount.clone(),
-                        can_redelegate: amount, // TODO: not implemented right now
-                        accumulated_rewards: if reward.amount.is_zero() {
-                            vec![]
-                        } else {
-                            vec![reward]
-                        },
-                    }),
+
+                let full_delegation_response = if amount.amount.is_zero() {
+                    // no delegation
+                    DelegationResponse { delegation: None }
+                } else {
+                    DelegationResponse {
+                        delegation: Some(FullDelegation {
+                            delegator,
+                            validator,
+                            amount: amount.clone(),
+                            can_redelegate: amount, // TODO: not implemented right now
+                            accumulated_rewards: if reward.amount.is_zero() {
+                                vec![]
+                            } else {
+                                vec![reward]
+                            },
+                        }),
+                    }
                 };
 
                 let res = to_binary(&full_delegation_response)?;
@@ -785,6 +893,31 @@ impl DistributionKeeper {
 
         Ok(rewards)
     }
+
+    pub fn get_withdraw_address(storage: &dyn Storage, delegator: &Addr) -> AnyResult<Addr> {
+        Ok(match WITHDRAW_ADDRESS.may_load(storage, delegator)? {
+            Some(a) => a,
+            None => delegator.clone(),
+        })
+    }
+
+    // https://docs.cosmos.network/main/modules/distribution#msgsetwithdrawaddress
+    pub fn set_withdraw_address(
+        storage: &mut dyn Storage,
+        delegator: &Addr,
+        withdraw_address: &Addr,
+    ) -> AnyResult<()> {
+        if delegator == withdraw_address {
+            WITHDRAW_ADDRESS.remove(storage, delegator);
+            Ok(())
+        } else {
+            // technically we should require that this address is not
+            // the address of a module. TODO: how?
+            WITHDRAW_ADDRESS
+                .save(storage, delegator, withdraw_address)
+                .map_err(|e| e.into())
+        }
+    }
 }
 
 impl Distribution for DistributionKeeper {}
@@ -810,14 +943,16 @@ impl Module for DistributionKeeper {
                 let rewards = self.remove_rewards(api, storage, block, &sender, &validator_addr)?;
 
                 let staking_storage = prefixed_read(storage, NAMESPACE_STAKING);
+                let distribution_storage = prefixed_read(storage, NAMESPACE_DISTRIBUTION);
                 let staking_info = StakeKeeper::get_staking_info(&staking_storage)?;
+                let receiver = Self::get_withdraw_address(&distribution_storage, &sender)?;
                 // directly mint rewards to delegator
                 router.sudo(
                     api,
                     storage,
                     block,
                     BankSudo::Mint {
-                        to_address: sender.to_string(),
+                        to_address: receiver.into_string(),
                         amount: vec![Coin {
                             amount: rewards,
                             denom: staking_info.bonded_denom.clone(),
@@ -835,6 +970,18 @@ impl Module for DistributionKeeper {
                     )];
                 Ok(AppResponse { events, data: None })
             }
+            DistributionMsg::SetWithdrawAddress { address } => {
+                let address = api.addr_validate(&address)?;
+                // https://github.com/cosmos/cosmos-sdk/blob/4f6f6c00021f4b5ee486bbb71ae2071a8ceb47c9/x/distribution/keeper/msg_server.go#L38
+                let storage = &mut prefixed(storage, NAMESPACE_DISTRIBUTION);
+                Self::set_withdraw_address(storage, &sender, &address)?;
+                Ok(AppResponse {
+                    data: None,
+                    // https://github.com/cosmos/cosmos-sdk/blob/4f6f6c00021f4b5ee486bbb71ae2071a8ceb47c9/x/distribution/keeper/keeper.go#L74
+                    events: vec![Event::new("set_withdraw_address")
+                        .add_attribute("withdraw_address", address)],
+                })
+            }
             m => bail!("Unsupported distribution message: {:?}", m),
         }
     }
@@ -864,14 +1011,16 @@ impl Module for DistributionKeeper {
 
 #[cfg(test)]
 mod test {
-    use crate::{app::MockRouter, BankKeeper, FailingModule, Router, WasmKeeper};
+    use crate::app::MockRouter;
+    use crate::stargate::StargateKeeper;
+    use crate::{BankKeeper, FailingModule, Router, WasmKeeper};
 
     use super::*;
 
     use cosmwasm_std::{
         from_slice,
         testing::{mock_env, MockApi, MockStorage},
-        BalanceResponse, BankQuery,
+        BalanceResponse, BankQuery, GovMsg, IbcMsg, IbcQuery,
     };
 
     /// Type alias for default build `Router` to make its reference in typical scenario
@@ -881,6 +1030,9 @@ mod test {
         WasmKeeper<ExecC, QueryC>,
         StakeKeeper,
         DistributionKeeper,
+        FailingModule<IbcMsg, IbcQuery, Empty>,
+        FailingModule<GovMsg, Empty, Empty>,
+        StargateKeeper<ExecC, QueryC>,
     >;
 
     fn mock_router() -> BasicRouter {
@@ -890,6 +1042,9 @@ mod test {
             custom: FailingModule::new(),
             staking: StakeKeeper::new(),
             distribution: DistributionKeeper::new(),
+            ibc: FailingModule::new(),
+            gov: FailingModule::new(),
+            stargate: StargateKeeper::new(),
         }
     }
 
@@ -1284,7 +1439,7 @@ mod test {
     }
 
     mod msg {
-        use cosmwasm_std::{from_slice, Addr, BondedDenomResponse, Decimal, StakingQuery};
+        use cosmwasm_std::{coins, from_slice, Addr, BondedDenomResponse, Decimal, StakingQuery};
         use serde::de::DeserializeOwned;
 
         use super::*;
@@ -1382,6 +1537,7 @@ mod test {
                 TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::percent(10)));
 
             let delegator1 = Addr::unchecked("delegator1");
+            let reward_receiver = Addr::unchecked("rewardreceiver");
 
             // fund delegator1 account
             test_env
@@ -1425,6 +1581,16 @@ mod test {
             // wait a year
             test_env.block.time = test_env.block.time.plus_seconds(60 * 60 * 24 * 365);
 
+            // change the withdrawal address
+            execute_distr(
+                &mut test_env,
+                delegator1.clone(),
+                DistributionMsg::SetWithdrawAddress {
+                    address: reward_receiver.to_string(),
+                },
+            )
+            .unwrap();
+
             // withdraw rewards
             execute_distr(
                 &mut test_env,
@@ -1435,6 +1601,13 @@ mod test {
             )
             .unwrap();
 
+            // withdrawal address received rewards.
+            assert_balances(
+                &test_env,
+                // one year, 10%apr, 10%commision, 100 tokens staked
+                vec![(reward_receiver, 100 / 10 * 9 / 10)],
+            );
+
             // redelegate to validator2
             execute_stake(
                 &mut test_env,
@@ -1447,11 +1620,8 @@ mod test {
             )
             .unwrap();
 
-            // should have same amount as before
-            assert_balances(
-                &test_env,
-                vec![(delegator1.clone(), 900 + 100 / 10 * 9 / 10)],
-            );
+            // should have same amount as before (rewards receiver received rewards).
+            assert_balances(&test_env, vec![(delegator1.clone(), 900)]);
 
             let delegations: AllDelegationsResponse = query_stake(
                 &test_env,
@@ -1497,9 +1667,86 @@ mod test {
                 .unwrap();
 
             // check bank balance
+            assert_balances(&test_env, vec![(delegator1.clone(), 1000)]);
+        }
+
+        #[test]
+        fn can_set_withdraw_address() {
+            let (mut test_env, validator) =
+                TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::percent(10)));
+
+            let delegator = Addr::unchecked("delegator");
+            let reward_receiver = Addr::unchecked("rewardreceiver");
+
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator, coins(100, "TOKEN"))
+                .unwrap();
+
+            // Stake 100 tokens to the validator.
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Delegate {
+                    validator: validator.to_string(),
+                    amount: coin(100, "TOKEN"),
+                },
+            )
+            .unwrap();
+
+            // Change rewards receiver.
+            execute_distr(
+                &mut test_env,
+                delegator.clone(),
+                DistributionMsg::SetWithdrawAddress {
+                    address: reward_receiver.to_string(),
+                },
+            )
+            .unwrap();
+
+            // A year passes.
+            test_env.block.time = test_env.block.time.plus_seconds(60 * 60 * 24 * 365);
+
+            // Withdraw rewards to reward receiver.
+            execute_distr(
+                &mut test_env,
+                delegator.clone(),
+                DistributionMsg::WithdrawDelegatorReward {
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+
+            // Change reward receiver back to delegator.
+            execute_distr(
+                &mut test_env,
+                delegator.clone(),
+                DistributionMsg::SetWithdrawAddress {
+                    address: delegator.to_string(),
+                },
+            )
+            .unwrap();
+
+            // Another year passes.
+            test_env.block.time = test_env.block.time.plus_seconds(60 * 60 * 24 * 365);
+
+            // Withdraw rewards to delegator.
+            execute_distr(
+                &mut test_env,
+                delegator.clone(),
+                DistributionMsg::WithdrawDelegatorReward {
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+
+            // one year, 10%apr, 10%commision, 100 tokens staked
+            let rewards_yr = 100 / 10 * 9 / 10;
+
             assert_balances(
                 &test_env,
-                vec![(delegator1.clone(), 1000 + 100 / 10 * 9 / 10)],
+                vec![(reward_receiver, rewards_yr), (delegator, rewards_yr)],
             );
         }
 
@@ -1762,6 +2009,25 @@ mod test {
                 },
             )
             .unwrap();
+            // unstake some again
+            execute_stake(
+                &mut test_env,
+                delegator1.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator1.to_string(),
+                    amount: coin(50, "TOKEN"),
+                },
+            )
+            .unwrap();
+            execute_stake(
+                &mut test_env,
+                delegator2.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator1.to_string(),
+                    amount: coin(50, "TOKEN"),
+                },
+            )
+            .unwrap();
 
             // query all delegations
             let response1: AllDelegationsResponse = query_stake(
@@ -1805,5 +2071,435 @@ mod test {
                 },
             );
         }
+
+        #[test]
+        fn delegation_queries_unbonding() {
+            // run all staking queries
+            let (mut test_env, validator) =
+                TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::percent(10)));
+            let delegator1 = Addr::unchecked("delegator1");
+            let delegator2 = Addr::unchecked("delegator2");
+
+            // init balances
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator1, vec![coin(100, "TOKEN")])
+                .unwrap();
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator2, vec![coin(150, "TOKEN")])
+                .unwrap();
+
+            // delegate some tokens with delegator1 and delegator2
+            execute_stake(
+                &mut test_env,
+                delegator1.clone(),
+                StakingMsg::Delegate {
+                    validator: validator.to_string(),
+                    amount: coin(100, "TOKEN"),
+                },
+            )
+            .unwrap();
+            execute_stake(
+                &mut test_env,
+                delegator2.clone(),
+                StakingMsg::Delegate {
+                    validator: validator.to_string(),
+                    amount: coin(150, "TOKEN"),
+                },
+            )
+            .unwrap();
+            // unstake some of delegator1's stake
+            execute_stake(
+                &mut test_env,
+                delegator1.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(50, "TOKEN"),
+                },
+            )
+            .unwrap();
+            // unstake all of delegator2's stake
+            execute_stake(
+                &mut test_env,
+                delegator2.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(150, "TOKEN"),
+                },
+            )
+            .unwrap();
+
+            // query all delegations
+            let response1: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator1.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegations,
+                vec![Delegation {
+                    delegator: delegator1.clone(),
+                    validator: validator.to_string(),
+                    amount: coin(100, "TOKEN"),
+                }]
+            );
+            let response2: DelegationResponse = query_stake(
+                &test_env,
+                StakingQuery::Delegation {
+                    delegator: delegator2.to_string(),
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response2.delegation.unwrap(),
+                FullDelegation {
+                    delegator: delegator2.clone(),
+                    validator: validator.to_string(),
+                    amount: coin(150, "TOKEN"),
+                    accumulated_rewards: vec![],
+                    can_redelegate: coin(150, "TOKEN"),
+                },
+            );
+
+            // wait until unbonding time is over
+            test_env.block.time = test_env.block.time.plus_seconds(60);
+            test_env
+                .router
+                .staking
+                .sudo(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.router,
+                    &test_env.block,
+                    StakingSudo::ProcessQueue {},
+                )
+                .unwrap();
+
+            // query all delegations again
+            let response1: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator1.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegations,
+                vec![Delegation {
+                    delegator: delegator1.clone(),
+                    validator: validator.to_string(),
+                    amount: coin(50, "TOKEN"),
+                }],
+                "delegator1 should have less now"
+            );
+            let response2: DelegationResponse = query_stake(
+                &test_env,
+                StakingQuery::Delegation {
+                    delegator: delegator2.to_string(),
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response2.delegation, None,
+                "delegator2 should have nothing left"
+            );
+
+            // unstake rest of delegator1's stake in two steps
+            execute_stake(
+                &mut test_env,
+                delegator1.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(25, "TOKEN"),
+                },
+            )
+            .unwrap();
+            test_env.block.time = test_env.block.time.plus_seconds(10);
+            execute_stake(
+                &mut test_env,
+                delegator1.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(25, "TOKEN"),
+                },
+            )
+            .unwrap();
+
+            // query all delegations again
+            let response1: DelegationResponse = query_stake(
+                &test_env,
+                StakingQuery::Delegation {
+                    delegator: delegator1.to_string(),
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            let response2: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator1.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegation.unwrap().amount.amount.u128(),
+                50,
+                "delegator1 should still have 50 tokens unbonding"
+            );
+            assert_eq!(response2.delegations[0].amount.amount.u128(), 50);
+
+            // wait until unbonding time is over
+            test_env.block.time = test_env.block.time.plus_seconds(60);
+            test_env
+                .router
+                .staking
+                .sudo(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.router,
+                    &test_env.block,
+                    StakingSudo::ProcessQueue {},
+                )
+                .unwrap();
+
+            // query all delegations again
+            let response1: DelegationResponse = query_stake(
+                &test_env,
+                StakingQuery::Delegation {
+                    delegator: delegator1.to_string(),
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            let response2: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator1.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegation, None,
+                "delegator1 should have nothing left"
+            );
+            assert!(response2.delegations.is_empty());
+        }
+
+        #[test]
+        fn partial_unbonding_keeps_stake() {
+            let (mut test_env, validator) =
+                TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::percent(10)));
+            let delegator = Addr::unchecked("delegator1");
+
+            // init balance
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator, vec![coin(100, "TOKEN")])
+                .unwrap();
+
+            // delegate all tokens
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Delegate {
+                    validator: validator.to_string(),
+                    amount: coin(100, "TOKEN"),
+                },
+            )
+            .unwrap();
+            // unstake in multiple steps
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(50, "TOKEN"),
+                },
+            )
+            .unwrap();
+            test_env.block.time = test_env.block.time.plus_seconds(10);
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(30, "TOKEN"),
+                },
+            )
+            .unwrap();
+            test_env.block.time = test_env.block.time.plus_seconds(10);
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(20, "TOKEN"),
+                },
+            )
+            .unwrap();
+
+            // wait for first unbonding to complete (but not the others) and process queue
+            test_env.block.time = test_env.block.time.plus_seconds(40);
+            test_env
+                .router
+                .staking
+                .sudo(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.router,
+                    &test_env.block,
+                    StakingSudo::ProcessQueue {},
+                )
+                .unwrap();
+
+            // query delegations
+            // we now have 0 stake, 50 unbonding and 50 completed unbonding
+            let response1: DelegationResponse = query_stake(
+                &test_env,
+                StakingQuery::Delegation {
+                    delegator: delegator.to_string(),
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            let response2: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegation,
+                Some(FullDelegation {
+                    delegator: delegator.clone(),
+                    validator: validator.to_string(),
+                    amount: coin(50, "TOKEN"),
+                    can_redelegate: coin(50, "TOKEN"),
+                    accumulated_rewards: vec![],
+                })
+            );
+            assert_eq!(
+                response2.delegations,
+                vec![Delegation {
+                    delegator: delegator.clone(),
+                    validator: validator.to_string(),
+                    amount: coin(50, "TOKEN"),
+                }]
+            );
+
+            // wait for the rest to complete
+            test_env.block.time = test_env.block.time.plus_seconds(20);
+            test_env
+                .router
+                .staking
+                .sudo(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.router,
+                    &test_env.block,
+                    StakingSudo::ProcessQueue {},
+                )
+                .unwrap();
+
+            // query delegations again
+            let response1: DelegationResponse = query_stake(
+                &test_env,
+                StakingQuery::Delegation {
+                    delegator: delegator.to_string(),
+                    validator: validator.to_string(),
+                },
+            )
+            .unwrap();
+            let response2: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegation, None,
+                "delegator should have nothing left"
+            );
+            assert!(response2.delegations.is_empty());
+        }
+
+        #[test]
+        fn delegation_queries_slashed() {
+            // run all staking queries
+            let (mut test_env, validator) =
+                TestEnv::wrap(setup_test_env(Decimal::percent(10), Decimal::percent(10)));
+            let delegator = Addr::unchecked("delegator");
+
+            // init balance
+            test_env
+                .router
+                .bank
+                .init_balance(&mut test_env.store, &delegator, vec![coin(333, "TOKEN")])
+                .unwrap();
+
+            // delegate some tokens
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Delegate {
+                    validator: validator.to_string(),
+                    amount: coin(333, "TOKEN"),
+                },
+            )
+            .unwrap();
+            // unstake some
+            execute_stake(
+                &mut test_env,
+                delegator.clone(),
+                StakingMsg::Undelegate {
+                    validator: validator.to_string(),
+                    amount: coin(111, "TOKEN"),
+                },
+            )
+            .unwrap();
+
+            // slash validator
+            test_env
+                .router
+                .staking
+                .sudo(
+                    &test_env.api,
+                    &mut test_env.store,
+                    &test_env.router,
+                    &test_env.block,
+                    StakingSudo::Slash {
+                        validator: "testvaloper1".to_string(),
+                        percentage: Decimal::percent(50),
+                    },
+                )
+                .unwrap();
+
+            // query all delegations
+            let response1: AllDelegationsResponse = query_stake(
+                &test_env,
+                StakingQuery::AllDelegations {
+                    delegator: delegator.to_string(),
+                },
+            )
+            .unwrap();
+            assert_eq!(
+                response1.delegations[0],
+                Delegation {
+                    delegator: delegator.clone(),
+                    validator: validator.to_string(),
+                    amount: coin(166, "TOKEN"),
+                }
+            );
+        }
     }
 }
@@ -3,7 +3,26 @@ use std::collections::HashMap;
 use anyhow::bail;
 use cosmwasm_std::{Addr, Api, Binary, BlockInfo, CosmosMsg, CustomQuery, Empty, Querier, Storage};
 
-use crate::{AppResponse, CosmosRouter};
+use crate::{AppResponse, CosmosRouter, MSG_SEND_TYPE_URL, QUERY_GRANTS_PATH};
+
+mod authz;
+mod bank;
+mod ixo;
+
+pub use authz::{
+    Any, AuthzHandler, GenericAuthorization, Grant, MsgExec, MsgGrant, MsgRevoke, ProtoTimestamp,
+    QueryGrantsRequest, QueryGrantsResponse, SendAuthorization,
+};
+pub use bank::{
+    BankMsgHandler, BankQueryHandler, DenomUnit, Metadata, MsgSend, ProtoCoin,
+    QueryAllBalancesRequest, QueryAllBalancesResponse, QueryBalanceRequest, QueryBalanceResponse,
+    QueryDenomMetadataRequest, QueryDenomMetadataResponse, QuerySupplyOfRequest,
+    QuerySupplyOfResponse,
+};
+pub use ixo::{
+    QueryTokenMetadataRequest, QueryTokenMetadataResponse, TokenMetadataHandler,
+    TokenMetadataResolver,
+};
 
 // TODO: turn into extensions of Fn trait
 pub trait StargateQueryHandler {
@@ -38,12 +57,30 @@ pub struct StargateKeeper<ExecC, QueryC> {
     queries: HashMap<String, Box<dyn StargateQueryHandler>>,
 }
 
-impl<'a, ExecC, QueryC> StargateKeeper<ExecC, QueryC> {
+impl<ExecC, QueryC: CustomQuery> Default for StargateKeeper<ExecC, QueryC> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<ExecC, QueryC: CustomQuery> StargateKeeper<ExecC, QueryC> {
+    /// Creates a keeper with the built-in bank (`MsgSend`, balances, supply, denom metadata)
+    /// and authz handlers registered. Any of them can be replaced with `register_msg` or
+    /// `register_query`.
     pub fn new() -> Self {
-        Self {
+        let mut keeper = Self {
             messages: HashMap::new(),
             queries: HashMap::new(),
+        };
+        keeper.register_msg(MSG_SEND_TYPE_URL, Box::new(BankMsgHandler));
+        for type_url in authz::AUTHZ_MSG_TYPE_URLS {
+            keeper.register_msg(type_url, Box::new(AuthzHandler));
+        }
+        for path in bank::BANK_QUERY_PATHS {
+            keeper.register_query(path, Box::new(BankQueryHandler));
         }
+        keeper.register_query(QUERY_GRANTS_PATH, Box::new(AuthzHandler));
+        keeper
     }
 
     pub fn register_msg(
@@ -103,7 +140,7 @@ pub trait Stargate<ExecC, QueryC> {
     ) -> anyhow::Result<cosmwasm_std::Binary>;
 }
 
-impl<'a, ExecC, QueryC: CustomQuery> Stargate<ExecC, QueryC> for StargateKeeper<ExecC, QueryC> {
+impl<ExecC, QueryC: CustomQuery> Stargate<ExecC, QueryC> for StargateKeeper<ExecC, QueryC> {
     fn execute(
         &self,
         api: &dyn Api,
@@ -145,17 +182,17 @@ impl<'a, ExecC, QueryC: CustomQuery> Stargate<ExecC, QueryC> for StargateKeeper<
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "stargate"))]
 mod tests {
-    use std::str::FromStr;
+    use std::convert::TryFrom;
 
     use anyhow::Ok;
     use cosmwasm_std::{coin, from_binary, to_binary, Coin, CosmosMsg, Event, QueryRequest};
-    use osmosis_std::types::cosmos::bank::v1beta1::{
-        QueryAllBalancesRequest, QueryBalanceRequest, QuerySupplyOfRequest,
-    };
+    use prost::Message;
 
-    use crate::{BasicAppBuilder, Executor};
+    use crate::{
+        BasicAppBuilder, Executor, QUERY_ALL_BALANCES_PATH, QUERY_BALANCE_PATH, QUERY_SUPPLY_PATH,
+    };
 
     use super::*;
 
@@ -228,10 +265,10 @@ mod tests {
                 },
             )?;
 
-            let mut res = AppResponse::default();
-            res.data = Some(query_res);
-
-            Ok(res)
+            Ok(AppResponse {
+                data: Some(query_res),
+                ..AppResponse::default()
+            })
         }
 
         fn register_msgs(&'static self, keeper: &mut StargateKeeper<Empty, Empty>) {
@@ -331,35 +368,50 @@ mod tests {
         let querier = app.wrap();
 
         // QueryAllBalancesRequest
-        let res = QueryAllBalancesRequest {
-            address: owner.to_string(),
-            pagination: None,
-        }
-        .query(&querier)
-        .unwrap();
-        let blances: Vec<Coin> = res
+        let res: QueryAllBalancesResponse = querier
+            .query(&QueryRequest::Stargate {
+                path: QUERY_ALL_BALANCES_PATH.to_string(),
+                data: QueryAllBalancesRequest {
+                    address: owner.to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
+        let balances: Vec<Coin> = res
             .balances
             .into_iter()
-            .map(|c| Coin::new(u128::from_str(&c.amount).unwrap(), c.denom))
+            .map(|c| Coin::try_from(c).unwrap())
             .collect();
-        assert_eq!(blances, init_funds);
+        assert_eq!(balances, init_funds);
 
         // QueryBalanceRequest
-        let res = QueryBalanceRequest {
-            address: owner.to_string(),
-            denom: "eth".to_string(),
-        }
-        .query(&querier)
-        .unwrap();
+        let res: QueryBalanceResponse = querier
+            .query(&QueryRequest::Stargate {
+                path: QUERY_BALANCE_PATH.to_string(),
+                data: QueryBalanceRequest {
+                    address: owner.to_string(),
+                    denom: "eth".to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
         let balance = res.balance.unwrap();
         assert_eq!(balance.amount, init_funds[1].amount.to_string());
         assert_eq!(balance.denom, init_funds[1].denom);
 
-        // QueryTotalSupplyRequest
-        let res = QuerySupplyOfRequest {
-            denom: "eth".to_string(),
-        };
-        let res = res.query(&querier).unwrap();
+        // QuerySupplyOfRequest
+        let res: QuerySupplyOfResponse = querier
+            .query(&QueryRequest::Stargate {
+                path: QUERY_SUPPLY_PATH.to_string(),
+                data: QuerySupplyOfRequest {
+                    denom: "eth".to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
         let supply = res.amount.unwrap();
         assert_eq!(supply.amount, init_funds[1].amount.to_string());
         assert_eq!(supply.denom, init_funds[1].denom);
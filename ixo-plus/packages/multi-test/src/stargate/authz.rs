@@ -0,0 +1,615 @@
+use std::convert::TryFrom;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_std::{
+    to_binary, Addr, Api, Binary, BlockInfo, Coin, CosmosMsg, CustomQuery, Empty, Order, Querier,
+    StdResult, Storage, Timestamp,
+};
+use cw_storage_plus::Map;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::app::CosmosRouter;
+use crate::executor::AppResponse;
+use crate::prefixed_storage::{prefixed, prefixed_read};
+use crate::{
+    GENERIC_AUTHORIZATION_TYPE_URL, MSG_EXEC_TYPE_URL, MSG_GRANT_TYPE_URL, MSG_REVOKE_TYPE_URL,
+    MSG_SEND_TYPE_URL, QUERY_GRANTS_PATH, SEND_AUTHORIZATION_TYPE_URL,
+};
+
+use super::bank::{MsgSend, ProtoCoin};
+use super::{StargateKeeper, StargateMessageHandler, StargateMsg, StargateQueryHandler};
+
+/// (granter, grantee, msg_type_url) -> grant
+const GRANTS: Map<(&Addr, &Addr, &str), Grant> = Map::new("grants");
+
+pub const NAMESPACE_AUTHZ: &[u8] = b"authz";
+
+pub(crate) const AUTHZ_MSG_TYPE_URLS: [&str; 3] =
+    [MSG_GRANT_TYPE_URL, MSG_EXEC_TYPE_URL, MSG_REVOKE_TYPE_URL];
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct Any {
+    #[prost(string, tag = "1")]
+    pub type_url: ::prost::alloc::string::String,
+    #[prost(bytes, tag = "2")]
+    pub value: ::prost::alloc::vec::Vec<u8>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct ProtoTimestamp {
+    #[prost(int64, tag = "1")]
+    pub seconds: i64,
+    #[prost(int32, tag = "2")]
+    pub nanos: i32,
+}
+
+impl From<Timestamp> for ProtoTimestamp {
+    fn from(time: Timestamp) -> Self {
+        ProtoTimestamp {
+            seconds: time.seconds() as i64,
+            nanos: time.subsec_nanos() as i32,
+        }
+    }
+}
+
+impl From<ProtoTimestamp> for Timestamp {
+    fn from(time: ProtoTimestamp) -> Self {
+        Timestamp::from_seconds(time.seconds as u64).plus_nanos(time.nanos as u64)
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct Grant {
+    #[prost(message, optional, tag = "1")]
+    pub authorization: ::core::option::Option<Any>,
+    #[prost(message, optional, tag = "2")]
+    pub expiration: ::core::option::Option<ProtoTimestamp>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct GenericAuthorization {
+    #[prost(string, tag = "1")]
+    pub msg: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct SendAuthorization {
+    #[prost(message, repeated, tag = "1")]
+    pub spend_limit: ::prost::alloc::vec::Vec<ProtoCoin>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct MsgGrant {
+    #[prost(string, tag = "1")]
+    pub granter: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub grantee: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub grant: ::core::option::Option<Grant>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct MsgExec {
+    #[prost(string, tag = "1")]
+    pub grantee: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub msgs: ::prost::alloc::vec::Vec<Any>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct MsgRevoke {
+    #[prost(string, tag = "1")]
+    pub granter: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub grantee: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub msg_type_url: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryGrantsRequest {
+    #[prost(string, tag = "1")]
+    pub granter: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub grantee: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub msg_type_url: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryGrantsResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub grants: ::prost::alloc::vec::Vec<Grant>,
+}
+
+/// Implements the `cosmos.authz.v1beta1` module: `MsgGrant`, `MsgRevoke` and `MsgExec`
+/// along with the `Grants` query.
+///
+/// `GenericAuthorization` and `SendAuthorization` grants are supported. `MsgExec` can only
+/// dispatch `MsgSend`, as that is the only message whose signer can be derived here; the
+/// inner message is re-routed through the stargate keeper on behalf of the granter.
+#[derive(Clone, Default)]
+pub struct AuthzHandler;
+
+impl AuthzHandler {
+    fn grant(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: MsgGrant,
+    ) -> AnyResult<AppResponse> {
+        let granter = api.addr_validate(&msg.granter)?;
+        if granter != sender {
+            bail!(
+                "MsgGrant granter {} does not match signer {}",
+                granter,
+                sender
+            );
+        }
+        let grantee = api.addr_validate(&msg.grantee)?;
+        if granter == grantee {
+            bail!("granter and grantee cannot be the same");
+        }
+
+        let grant = match msg.grant {
+            Some(grant) => grant,
+            None => bail!("MsgGrant is missing a grant"),
+        };
+        if let Some(expiration) = grant.expiration.clone() {
+            if Timestamp::from(expiration) <= block.time {
+                bail!("authorization expiration must be in the future");
+            }
+        }
+        let msg_type_url = match &grant.authorization {
+            Some(authorization) => authorized_msg_type_url(authorization)?,
+            None => bail!("grant is missing an authorization"),
+        };
+
+        let mut authz_storage = prefixed(storage, NAMESPACE_AUTHZ);
+        GRANTS.save(
+            &mut authz_storage,
+            (&granter, &grantee, &msg_type_url),
+            &grant,
+        )?;
+
+        Ok(AppResponse::default())
+    }
+
+    fn revoke(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        sender: Addr,
+        msg: MsgRevoke,
+    ) -> AnyResult<AppResponse> {
+        let granter = api.addr_validate(&msg.granter)?;
+        if granter != sender {
+            bail!(
+                "MsgRevoke granter {} does not match signer {}",
+                granter,
+                sender
+            );
+        }
+        let grantee = api.addr_validate(&msg.grantee)?;
+
+        let mut authz_storage = prefixed(storage, NAMESPACE_AUTHZ);
+        let key = (&granter, &grantee, msg.msg_type_url.as_str());
+        if !GRANTS.has(&authz_storage, key) {
+            bail!("authorization not found");
+        }
+        GRANTS.remove(&mut authz_storage, key);
+
+        Ok(AppResponse::default())
+    }
+
+    fn exec<ExecC, QueryC: CustomQuery>(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: MsgExec,
+    ) -> AnyResult<AppResponse> {
+        let grantee = api.addr_validate(&msg.grantee)?;
+        if grantee != sender {
+            bail!(
+                "MsgExec grantee {} does not match signer {}",
+                grantee,
+                sender
+            );
+        }
+
+        let mut res = AppResponse::default();
+        for inner in msg.msgs {
+            let granter = match inner.type_url.as_str() {
+                MSG_SEND_TYPE_URL => {
+                    let send = MsgSend::decode(inner.value.as_slice())?;
+                    let granter = api.addr_validate(&send.from_address)?;
+                    self.spend_grant(storage, block, &granter, &grantee, send)?;
+                    granter
+                }
+                type_url => bail!("Unsupported authz exec message: {}", type_url),
+            };
+
+            let msg = CosmosMsg::Stargate {
+                type_url: inner.type_url,
+                value: inner.value.into(),
+            };
+            let inner_res = router.execute(api, storage, block, granter, msg)?;
+            res.events.extend(inner_res.events);
+        }
+
+        Ok(res)
+    }
+
+    /// Checks the grant allowing `grantee` to send on behalf of `granter` and, for a
+    /// `SendAuthorization`, deducts the sent amount from the spend limit.
+    fn spend_grant(
+        &self,
+        storage: &mut dyn Storage,
+        block: &BlockInfo,
+        granter: &Addr,
+        grantee: &Addr,
+        send: MsgSend,
+    ) -> AnyResult<()> {
+        let mut authz_storage = prefixed(storage, NAMESPACE_AUTHZ);
+        let key = (granter, grantee, MSG_SEND_TYPE_URL);
+        let mut grant = match GRANTS.may_load(&authz_storage, key)? {
+            Some(grant) => grant,
+            None => bail!("authorization not found"),
+        };
+        if let Some(expiration) = grant.expiration.clone() {
+            if Timestamp::from(expiration) <= block.time {
+                bail!("authorization expired");
+            }
+        }
+
+        let authorization = grant.authorization.clone().unwrap_or_default();
+        if authorization.type_url != SEND_AUTHORIZATION_TYPE_URL {
+            return Ok(());
+        }
+
+        let limit = SendAuthorization::decode(authorization.value.as_slice())?;
+        let mut remaining = limit
+            .spend_limit
+            .into_iter()
+            .map(Coin::try_from)
+            .collect::<AnyResult<Vec<_>>>()?;
+        for sent in send.amount {
+            let sent = Coin::try_from(sent)?;
+            match remaining.iter_mut().find(|c| c.denom == sent.denom) {
+                Some(c) if c.amount >= sent.amount => c.amount -= sent.amount,
+                _ => bail!("requested amount is more than spend limit"),
+            }
+        }
+        remaining.retain(|c| !c.amount.is_zero());
+
+        if remaining.is_empty() {
+            GRANTS.remove(&mut authz_storage, key);
+        } else {
+            let limit = SendAuthorization {
+                spend_limit: remaining.into_iter().map(Into::into).collect(),
+            };
+            grant.authorization = Some(Any {
+                type_url: SEND_AUTHORIZATION_TYPE_URL.to_string(),
+                value: limit.encode_to_vec(),
+            });
+            GRANTS.save(&mut authz_storage, key, &grant)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the message type url an authorization applies to.
+fn authorized_msg_type_url(authorization: &Any) -> AnyResult<String> {
+    match authorization.type_url.as_str() {
+        GENERIC_AUTHORIZATION_TYPE_URL => {
+            Ok(GenericAuthorization::decode(authorization.value.as_slice())?.msg)
+        }
+        SEND_AUTHORIZATION_TYPE_URL => Ok(MSG_SEND_TYPE_URL.to_string()),
+        type_url => bail!("Unsupported authorization type: {}", type_url),
+    }
+}
+
+impl<ExecC, QueryC: CustomQuery> StargateMessageHandler<ExecC, QueryC> for AuthzHandler {
+    fn execute(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: StargateMsg,
+    ) -> AnyResult<AppResponse> {
+        match msg.type_url.as_str() {
+            MSG_GRANT_TYPE_URL => {
+                let msg = MsgGrant::decode(msg.value.as_slice())?;
+                self.grant(api, storage, block, sender, msg)
+            }
+            MSG_REVOKE_TYPE_URL => {
+                let msg = MsgRevoke::decode(msg.value.as_slice())?;
+                self.revoke(api, storage, sender, msg)
+            }
+            MSG_EXEC_TYPE_URL => {
+                let msg = MsgExec::decode(msg.value.as_slice())?;
+                self.exec(api, storage, router, block, sender, msg)
+            }
+            type_url => bail!("Unsupported authz stargate message: {}", type_url),
+        }
+    }
+
+    fn register_msgs(&'static self, keeper: &mut StargateKeeper<Empty, Empty>) {
+        for type_url in AUTHZ_MSG_TYPE_URLS {
+            keeper.register_msg(type_url, Box::new(self.clone()));
+        }
+    }
+}
+
+impl StargateQueryHandler for AuthzHandler {
+    fn stargate_query(
+        &self,
+        api: &dyn Api,
+        storage: &dyn Storage,
+        _querier: &dyn Querier,
+        block: &BlockInfo,
+        request: StargateMsg,
+    ) -> AnyResult<Binary> {
+        if request.type_url != QUERY_GRANTS_PATH {
+            bail!("Unsupported authz stargate query: {}", request.type_url);
+        }
+        let req = QueryGrantsRequest::decode(request.value.as_slice())?;
+        let granter = api.addr_validate(&req.granter)?;
+        let grantee = api.addr_validate(&req.grantee)?;
+
+        let authz_storage = prefixed_read(storage, NAMESPACE_AUTHZ);
+        let grants = if req.msg_type_url.is_empty() {
+            GRANTS
+                .prefix((&granter, &grantee))
+                .range(&authz_storage, None, None, Order::Ascending)
+                .map(|item| item.map(|(_, grant)| grant))
+                .collect::<StdResult<Vec<_>>>()?
+        } else {
+            GRANTS
+                .may_load(
+                    &authz_storage,
+                    (&granter, &grantee, req.msg_type_url.as_str()),
+                )?
+                .into_iter()
+                .collect()
+        };
+        // expired grants are kept in storage, hide them from queries
+        let grants = grants
+            .into_iter()
+            .filter(|grant| match grant.expiration.clone() {
+                Some(expiration) => Timestamp::from(expiration) > block.time,
+                None => true,
+            })
+            .collect();
+
+        Ok(to_binary(&QueryGrantsResponse { grants })?)
+    }
+
+    fn register_queries(&'static self, keeper: &mut StargateKeeper<Empty, Empty>) {
+        keeper.register_query(QUERY_GRANTS_PATH, Box::new(self.clone()))
+    }
+}
+
+#[cfg(all(test, feature = "stargate"))]
+mod test {
+    use super::*;
+
+    use cosmwasm_std::{coin, QueryRequest};
+
+    use crate::{BasicApp, BasicAppBuilder, Executor};
+
+    fn stargate<M: Message>(type_url: &str, msg: M) -> CosmosMsg {
+        StargateMsg {
+            type_url: type_url.to_string(),
+            value: msg.encode_to_vec().into(),
+        }
+        .into()
+    }
+
+    fn send_grant(spend_limit: Vec<Coin>, expiration: Option<Timestamp>) -> Grant {
+        Grant {
+            authorization: Some(Any {
+                type_url: SEND_AUTHORIZATION_TYPE_URL.to_string(),
+                value: SendAuthorization {
+                    spend_limit: spend_limit.into_iter().map(Into::into).collect(),
+                }
+                .encode_to_vec(),
+            }),
+            expiration: expiration.map(Into::into),
+        }
+    }
+
+    fn exec_send(granter: &Addr, grantee: &Addr, rcpt: &str, amount: Vec<Coin>) -> CosmosMsg {
+        let send = MsgSend {
+            from_address: granter.to_string(),
+            to_address: rcpt.to_string(),
+            amount: amount.into_iter().map(Into::into).collect(),
+        };
+        stargate(
+            MSG_EXEC_TYPE_URL,
+            MsgExec {
+                grantee: grantee.to_string(),
+                msgs: vec![Any {
+                    type_url: MSG_SEND_TYPE_URL.to_string(),
+                    value: send.encode_to_vec(),
+                }],
+            },
+        )
+    }
+
+    fn query_grants(app: &BasicApp, granter: &Addr, grantee: &Addr) -> Vec<Grant> {
+        let res: QueryGrantsResponse = app
+            .wrap()
+            .query(&QueryRequest::Stargate {
+                path: QUERY_GRANTS_PATH.to_string(),
+                data: QueryGrantsRequest {
+                    granter: granter.to_string(),
+                    grantee: grantee.to_string(),
+                    msg_type_url: String::new(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
+        res.grants
+    }
+
+    #[test]
+    fn send_authorization_spend_limit() {
+        let granter = Addr::unchecked("granter");
+        let grantee = Addr::unchecked("grantee");
+
+        let app = BasicAppBuilder::<Empty, Empty>::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &granter, vec![coin(100, "uixo")])
+                .unwrap();
+        });
+
+        // nothing can be sent without a grant
+        let err = app
+            .execute(
+                grantee.clone(),
+                exec_send(&granter, &grantee, "rcpt", vec![coin(10, "uixo")]),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "authorization not found");
+
+        let grant = send_grant(vec![coin(30, "uixo")], None);
+        app.execute(
+            granter.clone(),
+            stargate(
+                MSG_GRANT_TYPE_URL,
+                MsgGrant {
+                    granter: granter.to_string(),
+                    grantee: grantee.to_string(),
+                    grant: Some(grant.clone()),
+                },
+            ),
+        )
+        .unwrap();
+        assert_eq!(query_grants(&app, &granter, &grantee), vec![grant]);
+
+        app.execute(
+            grantee.clone(),
+            exec_send(&granter, &grantee, "rcpt", vec![coin(20, "uixo")]),
+        )
+        .unwrap();
+        let balance = app.wrap().query_balance("rcpt", "uixo").unwrap();
+        assert_eq!(balance, coin(20, "uixo"));
+        assert_eq!(
+            query_grants(&app, &granter, &grantee),
+            vec![send_grant(vec![coin(10, "uixo")], None)]
+        );
+
+        // spend limit is enforced
+        let err = app
+            .execute(
+                grantee.clone(),
+                exec_send(&granter, &grantee, "rcpt", vec![coin(11, "uixo")]),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "requested amount is more than spend limit");
+
+        // using up the limit removes the grant
+        app.execute(
+            grantee.clone(),
+            exec_send(&granter, &grantee, "rcpt", vec![coin(10, "uixo")]),
+        )
+        .unwrap();
+        assert_eq!(query_grants(&app, &granter, &grantee), vec![]);
+    }
+
+    #[test]
+    fn generic_authorization_expires_and_revokes() {
+        let granter = Addr::unchecked("granter");
+        let grantee = Addr::unchecked("grantee");
+
+        let app = BasicAppBuilder::<Empty, Empty>::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &granter, vec![coin(100, "uixo")])
+                .unwrap();
+        });
+        let expiration = app.block_info().time.plus_seconds(10);
+
+        let grant = Grant {
+            authorization: Some(Any {
+                type_url: GENERIC_AUTHORIZATION_TYPE_URL.to_string(),
+                value: GenericAuthorization {
+                    msg: MSG_SEND_TYPE_URL.to_string(),
+                }
+                .encode_to_vec(),
+            }),
+            expiration: Some(expiration.into()),
+        };
+        let msg = MsgGrant {
+            granter: granter.to_string(),
+            grantee: grantee.to_string(),
+            grant: Some(grant),
+        };
+
+        // only the granter can grant
+        let err = app
+            .execute(grantee.clone(), stargate(MSG_GRANT_TYPE_URL, msg.clone()))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "MsgGrant granter granter does not match signer grantee"
+        );
+        app.execute(granter.clone(), stargate(MSG_GRANT_TYPE_URL, msg.clone()))
+            .unwrap();
+
+        app.execute(
+            grantee.clone(),
+            exec_send(&granter, &grantee, "rcpt", vec![coin(60, "uixo")]),
+        )
+        .unwrap();
+        let balance = app.wrap().query_balance("rcpt", "uixo").unwrap();
+        assert_eq!(balance, coin(60, "uixo"));
+
+        // grant is gone once expired
+        app.update_block(|block| block.time = block.time.plus_seconds(10));
+        assert_eq!(query_grants(&app, &granter, &grantee), vec![]);
+        let err = app
+            .execute(
+                grantee.clone(),
+                exec_send(&granter, &grantee, "rcpt", vec![coin(1, "uixo")]),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "authorization expired");
+
+        // revoke removes a live grant
+        let mut msg = msg;
+        msg.grant.as_mut().unwrap().expiration = None;
+        app.execute(granter.clone(), stargate(MSG_GRANT_TYPE_URL, msg))
+            .unwrap();
+        app.execute(
+            granter.clone(),
+            stargate(
+                MSG_REVOKE_TYPE_URL,
+                MsgRevoke {
+                    granter: granter.to_string(),
+                    grantee: grantee.to_string(),
+                    msg_type_url: MSG_SEND_TYPE_URL.to_string(),
+                },
+            ),
+        )
+        .unwrap();
+        let err = app
+            .execute(
+                grantee.clone(),
+                exec_send(&granter, &grantee, "rcpt", vec![coin(1, "uixo")]),
+            )
+            .unwrap_err();
+        assert_eq!(err.to_string(), "authorization not found");
+    }
+}
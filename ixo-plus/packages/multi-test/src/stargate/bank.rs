@@ -0,0 +1,382 @@
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_std::{
+    to_binary, Addr, Api, BankMsg, Binary, BlockInfo, Coin, CustomQuery, Empty, Querier,
+    QuerierWrapper, Storage, Uint128,
+};
+use cw_storage_plus::Map;
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::app::CosmosRouter;
+use crate::bank::NAMESPACE_BANK;
+use crate::executor::AppResponse;
+use crate::prefixed_storage::{prefixed, prefixed_read};
+use crate::{
+    MSG_SEND_TYPE_URL, QUERY_ALL_BALANCES_PATH, QUERY_BALANCE_PATH, QUERY_DENOM_METADATA_PATH,
+    QUERY_SUPPLY_PATH,
+};
+
+use super::{StargateKeeper, StargateMessageHandler, StargateMsg, StargateQueryHandler};
+
+const DENOM_METADATA: Map<&str, Metadata> = Map::new("denom_metadata");
+
+pub(crate) const BANK_QUERY_PATHS: [&str; 4] = [
+    QUERY_BALANCE_PATH,
+    QUERY_ALL_BALANCES_PATH,
+    QUERY_SUPPLY_PATH,
+    QUERY_DENOM_METADATA_PATH,
+];
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct ProtoCoin {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub amount: ::prost::alloc::string::String,
+}
+
+impl From<Coin> for ProtoCoin {
+    fn from(coin: Coin) -> Self {
+        ProtoCoin {
+            denom: coin.denom,
+            amount: coin.amount.to_string(),
+        }
+    }
+}
+
+impl TryFrom<ProtoCoin> for Coin {
+    type Error = anyhow::Error;
+
+    fn try_from(coin: ProtoCoin) -> AnyResult<Self> {
+        Ok(Coin {
+            amount: Uint128::from_str(&coin.amount)?,
+            denom: coin.denom,
+        })
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct MsgSend {
+    #[prost(string, tag = "1")]
+    pub from_address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub to_address: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub amount: ::prost::alloc::vec::Vec<ProtoCoin>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryBalanceRequest {
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub denom: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryBalanceResponse {
+    #[prost(message, optional, tag = "1")]
+    pub balance: ::core::option::Option<ProtoCoin>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryAllBalancesRequest {
+    #[prost(string, tag = "1")]
+    pub address: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryAllBalancesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub balances: ::prost::alloc::vec::Vec<ProtoCoin>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QuerySupplyOfRequest {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QuerySupplyOfResponse {
+    #[prost(message, optional, tag = "1")]
+    pub amount: ::core::option::Option<ProtoCoin>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryDenomMetadataRequest {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryDenomMetadataResponse {
+    #[prost(message, optional, tag = "1")]
+    pub metadata: ::core::option::Option<Metadata>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct DenomUnit {
+    #[prost(string, tag = "1")]
+    pub denom: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub exponent: u32,
+    #[prost(string, repeated, tag = "3")]
+    pub aliases: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct Metadata {
+    #[prost(string, tag = "1")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "2")]
+    pub denom_units: ::prost::alloc::vec::Vec<DenomUnit>,
+    #[prost(string, tag = "3")]
+    pub base: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub display: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub symbol: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub uri: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub uri_hash: ::prost::alloc::string::String,
+}
+
+/// Serves the `cosmos.bank.v1beta1` gRPC queries. Balances and supply are read through the
+/// app's `Bank` module, denom metadata is kept next to it and set with `set_denom_metadata`.
+#[derive(Clone, Default)]
+pub struct BankQueryHandler;
+
+impl BankQueryHandler {
+    // this is an "admin" function to register denom metadata in genesis
+    pub fn set_denom_metadata(
+        &self,
+        storage: &mut dyn Storage,
+        metadata: Metadata,
+    ) -> AnyResult<()> {
+        let mut bank_storage = prefixed(storage, NAMESPACE_BANK);
+        DENOM_METADATA
+            .save(&mut bank_storage, &metadata.base.clone(), &metadata)
+            .map_err(Into::into)
+    }
+
+    pub fn denom_metadata(
+        &self,
+        storage: &dyn Storage,
+        denom: &str,
+    ) -> AnyResult<Option<Metadata>> {
+        let bank_storage = prefixed_read(storage, NAMESPACE_BANK);
+        DENOM_METADATA
+            .may_load(&bank_storage, denom)
+            .map_err(Into::into)
+    }
+}
+
+impl StargateQueryHandler for BankQueryHandler {
+    fn stargate_query(
+        &self,
+        _api: &dyn Api,
+        storage: &dyn Storage,
+        querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: StargateMsg,
+    ) -> AnyResult<Binary> {
+        let querier = QuerierWrapper::<Empty>::new(querier);
+        match request.type_url.as_str() {
+            QUERY_BALANCE_PATH => {
+                let req = QueryBalanceRequest::decode(request.value.as_slice())?;
+                let balance = querier.query_balance(req.address, req.denom)?;
+                Ok(to_binary(&QueryBalanceResponse {
+                    balance: Some(balance.into()),
+                })?)
+            }
+            QUERY_ALL_BALANCES_PATH => {
+                let req = QueryAllBalancesRequest::decode(request.value.as_slice())?;
+                let balances = querier.query_all_balances(req.address)?;
+                Ok(to_binary(&QueryAllBalancesResponse {
+                    balances: balances.into_iter().map(Into::into).collect(),
+                })?)
+            }
+            QUERY_SUPPLY_PATH => {
+                let req = QuerySupplyOfRequest::decode(request.value.as_slice())?;
+                let supply = querier.query_supply(req.denom)?;
+                Ok(to_binary(&QuerySupplyOfResponse {
+                    amount: Some(supply.into()),
+                })?)
+            }
+            QUERY_DENOM_METADATA_PATH => {
+                let req = QueryDenomMetadataRequest::decode(request.value.as_slice())?;
+                let metadata = self.denom_metadata(storage, &req.denom)?;
+                Ok(to_binary(&QueryDenomMetadataResponse { metadata })?)
+            }
+            path => bail!("Unsupported bank stargate query: {}", path),
+        }
+    }
+
+    fn register_queries(&'static self, keeper: &mut StargateKeeper<Empty, Empty>) {
+        for path in BANK_QUERY_PATHS {
+            keeper.register_query(path, Box::new(self.clone()));
+        }
+    }
+}
+
+/// Executes `MsgSend` by routing it to the app's `Bank` module as a `BankMsg::Send`.
+#[derive(Clone, Default)]
+pub struct BankMsgHandler;
+
+impl<ExecC, QueryC: CustomQuery> StargateMessageHandler<ExecC, QueryC> for BankMsgHandler {
+    fn execute(
+        &self,
+        api: &dyn Api,
+        storage: &mut dyn Storage,
+        router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        block: &BlockInfo,
+        sender: Addr,
+        msg: StargateMsg,
+    ) -> AnyResult<AppResponse> {
+        if msg.type_url != MSG_SEND_TYPE_URL {
+            bail!("Unsupported bank stargate message: {}", msg.type_url);
+        }
+        let msg = MsgSend::decode(msg.value.as_slice())?;
+        if msg.from_address != sender.as_str() {
+            bail!(
+                "MsgSend from_address {} does not match signer {}",
+                msg.from_address,
+                sender
+            );
+        }
+        let amount = msg
+            .amount
+            .into_iter()
+            .map(Coin::try_from)
+            .collect::<AnyResult<Vec<_>>>()?;
+        let send = BankMsg::Send {
+            to_address: msg.to_address,
+            amount,
+        };
+        router.execute(api, storage, block, sender, send.into())
+    }
+
+    fn register_msgs(&'static self, keeper: &mut StargateKeeper<Empty, Empty>) {
+        keeper.register_msg(MSG_SEND_TYPE_URL, Box::new(self.clone()))
+    }
+}
+
+#[cfg(all(test, feature = "stargate"))]
+mod test {
+    use super::*;
+
+    use cosmwasm_std::{coin, Event, QueryRequest};
+
+    use crate::{BasicAppBuilder, Executor};
+
+    fn denom_metadata(base: &str) -> Metadata {
+        Metadata {
+            description: "Test denom".to_string(),
+            denom_units: vec![DenomUnit {
+                denom: base.to_string(),
+                exponent: 0,
+                aliases: vec![],
+            }],
+            base: base.to_string(),
+            display: base.to_string(),
+            name: base.to_string(),
+            symbol: base.to_uppercase(),
+            uri: String::new(),
+            uri_hash: String::new(),
+        }
+    }
+
+    #[test]
+    fn query_denom_metadata() {
+        let app = BasicAppBuilder::<Empty, Empty>::new().build(|_, _, storage| {
+            BankQueryHandler
+                .set_denom_metadata(storage, denom_metadata("uixo"))
+                .unwrap();
+        });
+
+        let res: QueryDenomMetadataResponse = app
+            .wrap()
+            .query(&QueryRequest::Stargate {
+                path: QUERY_DENOM_METADATA_PATH.to_string(),
+                data: QueryDenomMetadataRequest {
+                    denom: "uixo".to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
+        assert_eq!(res.metadata, Some(denom_metadata("uixo")));
+
+        // unknown denoms have no metadata
+        let res: QueryDenomMetadataResponse = app
+            .wrap()
+            .query(&QueryRequest::Stargate {
+                path: QUERY_DENOM_METADATA_PATH.to_string(),
+                data: QueryDenomMetadataRequest {
+                    denom: "uatom".to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
+        assert_eq!(res.metadata, None);
+    }
+
+    #[test]
+    fn execute_msg_send() {
+        let owner = Addr::unchecked("owner");
+        let rcpt = Addr::unchecked("rcpt");
+
+        let app = BasicAppBuilder::<Empty, Empty>::new().build(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &owner, vec![coin(100, "uixo")])
+                .unwrap();
+        });
+
+        let send = MsgSend {
+            from_address: owner.to_string(),
+            to_address: rcpt.to_string(),
+            amount: vec![coin(40, "uixo").into()],
+        };
+        let res = app
+            .execute(
+                owner.clone(),
+                StargateMsg {
+                    type_url: MSG_SEND_TYPE_URL.to_string(),
+                    value: send.encode_to_vec().into(),
+                }
+                .into(),
+            )
+            .unwrap();
+        res.assert_event(&Event::new("transfer").add_attribute("recipient", "rcpt"));
+
+        let balance = app.wrap().query_balance(&rcpt, "uixo").unwrap();
+        assert_eq!(balance, coin(40, "uixo"));
+        let balance = app.wrap().query_balance(&owner, "uixo").unwrap();
+        assert_eq!(balance, coin(60, "uixo"));
+
+        // cannot send on behalf of someone else
+        let err = app
+            .execute(
+                rcpt,
+                StargateMsg {
+                    type_url: MSG_SEND_TYPE_URL.to_string(),
+                    value: send.encode_to_vec().into(),
+                }
+                .into(),
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "MsgSend from_address owner does not match signer rcpt"
+        );
+    }
+}
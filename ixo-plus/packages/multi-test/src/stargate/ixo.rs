@@ -0,0 +1,129 @@
+use std::rc::Rc;
+
+use anyhow::{bail, Result as AnyResult};
+use cosmwasm_std::{to_binary, Api, Binary, BlockInfo, Empty, Querier, Storage};
+use prost::Message;
+use serde::{Deserialize, Serialize};
+
+use crate::QUERY_TOKEN_METADATA_PATH;
+
+use super::{StargateKeeper, StargateMsg, StargateQueryHandler};
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryTokenMetadataRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Message)]
+pub struct QueryTokenMetadataResponse {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub description: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub decimals: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub image: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub index: ::prost::alloc::string::String,
+}
+
+/// Resolves the metadata of an ixo token id, returning `None` for unknown ids.
+pub type TokenMetadataResolver = dyn Fn(&str) -> Option<QueryTokenMetadataResponse>;
+
+/// Serves `/ixo.token.v1beta1.Query/TokenMetadata`.
+///
+/// The ixo token module is not simulated, so the handler is built around a resolver supplied
+/// by the test that decides which token ids exist and what their metadata is. It is not
+/// registered by default; plug it in with `StargateKeeper::register_query`.
+#[derive(Clone)]
+pub struct TokenMetadataHandler {
+    resolver: Rc<TokenMetadataResolver>,
+}
+
+impl TokenMetadataHandler {
+    pub fn new<F>(resolver: F) -> Self
+    where
+        F: Fn(&str) -> Option<QueryTokenMetadataResponse> + 'static,
+    {
+        TokenMetadataHandler {
+            resolver: Rc::new(resolver),
+        }
+    }
+}
+
+impl StargateQueryHandler for TokenMetadataHandler {
+    fn stargate_query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        _block: &BlockInfo,
+        request: StargateMsg,
+    ) -> AnyResult<Binary> {
+        if request.type_url != QUERY_TOKEN_METADATA_PATH {
+            bail!("Unsupported ixo token stargate query: {}", request.type_url);
+        }
+        let req = QueryTokenMetadataRequest::decode(request.value.as_slice())?;
+        match (self.resolver)(&req.id) {
+            Some(metadata) => Ok(to_binary(&metadata)?),
+            None => bail!("token {} not found", req.id),
+        }
+    }
+
+    fn register_queries(&'static self, keeper: &mut StargateKeeper<Empty, Empty>) {
+        keeper.register_query(QUERY_TOKEN_METADATA_PATH, Box::new(self.clone()))
+    }
+}
+
+#[cfg(all(test, feature = "stargate"))]
+mod test {
+    use super::*;
+
+    use cosmwasm_std::QueryRequest;
+
+    use crate::BasicAppBuilder;
+
+    fn token_metadata_query(id: &str) -> QueryRequest<Empty> {
+        QueryRequest::Stargate {
+            path: QUERY_TOKEN_METADATA_PATH.to_string(),
+            data: QueryTokenMetadataRequest { id: id.to_string() }
+                .encode_to_vec()
+                .into(),
+        }
+    }
+
+    #[test]
+    fn query_token_metadata() {
+        let mut stargate_keeper = StargateKeeper::new();
+        stargate_keeper.register_query(
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(TokenMetadataHandler::new(|id| {
+                let (name, index) = id.split_once('/')?;
+                Some(QueryTokenMetadataResponse {
+                    name: name.to_string(),
+                    description: "Test credits".to_string(),
+                    decimals: "0".to_string(),
+                    image: String::new(),
+                    index: index.to_string(),
+                })
+            })),
+        );
+
+        let app = BasicAppBuilder::<Empty, Empty>::new()
+            .with_stargate(stargate_keeper)
+            .build(|_, _, _| {});
+
+        let res: QueryTokenMetadataResponse =
+            app.wrap().query(&token_metadata_query("CARBON/1")).unwrap();
+        assert_eq!(res.name, "CARBON");
+        assert_eq!(res.index, "1");
+
+        let err = app
+            .wrap()
+            .query::<QueryTokenMetadataResponse>(&token_metadata_query("unknown"))
+            .unwrap_err();
+        assert!(err.to_string().contains("token unknown not found"));
+    }
+}
@@ -6,3 +6,5 @@ pub mod error;
 pub mod hackatom;
 pub mod payout;
 pub mod reflect;
+#[cfg(any(feature = "ibc", feature = "gov"))]
+pub mod stargate;
@@ -102,6 +102,28 @@ pub struct WasmKeeper<ExecC, QueryC> {
     codes: HashMap<usize, Box<dyn Contract<ExecC, QueryC>>>,
     /// Just markers to make type elision fork when using it as `Wasm` trait
     _p: std::marker::PhantomData<QueryC>,
+    generator: Box<dyn AddressGenerator>,
+}
+
+pub trait AddressGenerator {
+    fn next_address(&self, storage: &mut dyn Storage) -> Addr;
+}
+
+#[derive(Debug)]
+struct SimpleAddressGenerator();
+
+impl AddressGenerator for SimpleAddressGenerator {
+    fn next_address(&self, storage: &mut dyn Storage) -> Addr {
+        let count = CONTRACTS
+            .range_raw(
+                &prefixed_read(storage, NAMESPACE_WASM),
+                None,
+                None,
+                Order::Ascending,
+            )
+            .count();
+        Addr::unchecked(format!("contract{}", count))
+    }
 }
 
 impl<ExecC, QueryC> Default for WasmKeeper<ExecC, QueryC> {
@@ -109,6 +131,7 @@ impl<ExecC, QueryC> Default for WasmKeeper<ExecC, QueryC> {
         Self {
             codes: HashMap::default(),
             _p: std::marker::PhantomData,
+            generator: Box::new(SimpleAddressGenerator()),
         }
     }
 }
@@ -138,7 +161,9 @@ where
             WasmQuery::ContractInfo { contract_addr } => {
                 let addr = api.addr_validate(&contract_addr)?;
                 let contract = self.load_contract(storage, &addr)?;
-                let mut res = ContractInfoResponse::new(contract.code_id as u64, contract.creator);
+                let mut res = ContractInfoResponse::default();
+                res.code_id = contract.code_id as u64;
+                res.creator = contract.creator.to_string();
                 res.admin = contract.admin.map(|x| x.into());
                 to_binary(&res).map_err(Into::into)
             }
@@ -181,11 +206,29 @@ where
 
 impl<ExecC, QueryC> WasmKeeper<ExecC, QueryC> {
     pub fn store_code(&mut self, code: Box<dyn Contract<ExecC, QueryC>>) -> usize {
-        let idx = self.codes.len() + 1;
+        let idx = self.codes.keys().max().copied().unwrap_or_default() + 1;
         self.codes.insert(idx, code);
         idx
     }
 
+    /// Stores code under a fixed code id, e.g. the one it has on a live chain.
+    pub fn store_code_with_id(
+        &mut self,
+        code_id: usize,
+        code: Box<dyn Contract<ExecC, QueryC>>,
+    ) -> AnyResult<()> {
+        if self.codes.contains_key(&code_id) {
+            bail!("Code id {} is already registered", code_id);
+        }
+        self.codes.insert(code_id, code);
+        Ok(())
+    }
+
+    /// Whether code is stored under the given code id.
+    pub(crate) fn has_code(&self, code_id: usize) -> bool {
+        self.codes.contains_key(&code_id)
+    }
+
     pub fn load_contract(&self, storage: &dyn Storage, address: &Addr) -> AnyResult<ContractData> {
         CONTRACTS
             .load(&prefixed_read(storage, NAMESPACE_WASM), address)
@@ -197,6 +240,44 @@ impl<ExecC, QueryC> WasmKeeper<ExecC, QueryC> {
         storage.range(None, None, Order::Ascending).collect()
     }
 
+    /// Recreates a contract at a fixed address from its info and raw storage, as returned by
+    /// `dump_wasm_raw`. Unlike `register_contract`, no address is generated.
+    pub fn restore_contract(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        contract: &ContractData,
+        records: impl IntoIterator<Item = Record>,
+    ) -> AnyResult<()> {
+        if !self.codes.contains_key(&contract.code_id) {
+            bail!(Error::UnregisteredCodeId(contract.code_id));
+        }
+        self.restore_contract_unchecked(storage, address, contract, records)
+    }
+
+    /// Like `restore_contract`, but leaves checking the code id to the caller.
+    pub(crate) fn restore_contract_unchecked(
+        &self,
+        storage: &mut dyn Storage,
+        address: &Addr,
+        contract: &ContractData,
+        records: impl IntoIterator<Item = Record>,
+    ) -> AnyResult<()> {
+        if CONTRACTS
+            .may_load(&prefixed_read(storage, NAMESPACE_WASM), address)?
+            .is_some()
+        {
+            bail!("Contract {} already exists", address);
+        }
+
+        CONTRACTS.save(&mut prefixed(storage, NAMESPACE_WASM), address, contract)?;
+        let mut contract_storage = self.contract_storage(storage, address);
+        for (key, value) in records {
+            contract_storage.set(&key, &value);
+        }
+        Ok(())
+    }
+
     fn contract_namespace(&self, contract: &Addr) -> Vec<u8> {
         let mut name = b"contract_data/".to_vec();
         name.extend_from_slice(contract.as_bytes());
@@ -276,6 +357,15 @@ where
         Self::default()
     }
 
+    pub fn new_with_custom_address_generator(generator: impl AddressGenerator + 'static) -> Self {
+        let default = Self::default();
+        Self {
+            codes: default.codes,
+            _p: default._p,
+            generator: Box::new(generator),
+        }
+    }
+
     pub fn query_smart(
         &self,
         address: Addr,
@@ -685,7 +775,7 @@ where
             bail!("Cannot init contract with unregistered code id");
         }
 
-        let addr = self.next_address(&prefixed_read(storage, NAMESPACE_WASM));
+        let addr = self.generator.next_address(storage);
 
         let info = ContractData {
             code_id,
@@ -880,17 +970,6 @@ where
             .save(&mut prefixed(storage, NAMESPACE_WASM), address, contract)
             .map_err(Into::into)
     }
-
-    // FIXME: better addr generation
-    fn next_address(&self, storage: &dyn Storage) -> Addr {
-        // FIXME: quite inefficient if we actually had 100s of contracts
-        let count = CONTRACTS
-            .range_raw(storage, None, None, Order::Ascending)
-            .count();
-        // we make this longer so it is not rejected by tests
-        // it is lowercase to be compatible with the MockApi implementation of cosmwasm-std >= 1.0.0-beta8
-        Addr::unchecked(format!("contract{}", count))
-    }
 }
 
 // TODO: replace with code in utils
@@ -936,12 +1015,16 @@ fn execute_response(data: Option<Binary>) -> Option<Binary> {
 #[cfg(test)]
 mod test {
     use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockQuerier, MockStorage};
-    use cosmwasm_std::{coin, from_slice, to_vec, BankMsg, Coin, CosmosMsg, Empty, StdError};
+    use cosmwasm_std::{
+        coin, from_slice, to_vec, BankMsg, Coin, CosmosMsg, Empty, GovMsg, IbcMsg, IbcQuery,
+        StdError,
+    };
 
     use crate::app::Router;
     use crate::bank::BankKeeper;
     use crate::module::FailingModule;
     use crate::staking::{DistributionKeeper, StakeKeeper};
+    use crate::stargate::StargateKeeper;
     use crate::test_helpers::contracts::{caller, error, payout};
     use crate::test_helpers::EmptyMsg;
     use crate::transactions::StorageTransaction;
@@ -955,6 +1038,9 @@ mod test {
         WasmKeeper<ExecC, QueryC>,
         StakeKeeper,
         DistributionKeeper,
+        FailingModule<IbcMsg, IbcQuery, Empty>,
+        FailingModule<GovMsg, Empty, Empty>,
+        StargateKeeper<ExecC, QueryC>,
     >;
 
     fn mock_router() -> BasicRouter {
@@ -964,6 +1050,9 @@ mod test {
             custom: FailingModule::new(),
             staking: StakeKeeper::new(),
             distribution: DistributionKeeper::new(),
+            ibc: FailingModule::new(),
+            gov: FailingModule::new(),
+            stargate: StargateKeeper::new(),
         }
     }
 
@@ -1086,7 +1175,9 @@ mod test {
             .query(&api, &wasm_storage, &querier, &block, query)
             .unwrap();
 
-        let mut expected = ContractInfoResponse::new(code_id as u64, "foobar");
+        let mut expected = ContractInfoResponse::default();
+        expected.code_id = code_id as u64;
+        expected.creator = "foobar".to_string();
         expected.admin = Some("admin".to_owned());
         assert_eq!(expected, from_slice(&info).unwrap());
     }
@@ -1519,4 +1610,65 @@ mod test {
         // should have no admin now
         assert_admin(&wasm_storage, &keeper, &contract_addr, None);
     }
+
+    #[test]
+    fn by_default_uses_simple_address_generator() {
+        let mut keeper = WasmKeeper::<Empty, Empty>::new();
+        let code_id = keeper.store_code(payout::contract());
+
+        let mut wasm_storage = MockStorage::new();
+
+        let contract_addr = keeper
+            .register_contract(
+                &mut wasm_storage,
+                code_id,
+                Addr::unchecked("foobar"),
+                Addr::unchecked("admin"),
+                "label".to_owned(),
+                1000,
+            )
+            .unwrap();
+
+        assert_eq!(
+            "contract0", contract_addr,
+            "default address generator returned incorrect address"
+        )
+    }
+
+    struct TestAddressGenerator {
+        addr_to_return: Addr,
+    }
+    impl AddressGenerator for TestAddressGenerator {
+        fn next_address(&self, _: &mut dyn Storage) -> Addr {
+            self.addr_to_return.clone()
+        }
+    }
+
+    #[test]
+    fn can_use_custom_address_generator() {
+        let expected_addr = Addr::unchecked("new_test_addr");
+        let mut keeper =
+            WasmKeeper::<Empty, Empty>::new_with_custom_address_generator(TestAddressGenerator {
+                addr_to_return: expected_addr.clone(),
+            });
+        let code_id = keeper.store_code(payout::contract());
+
+        let mut wasm_storage = MockStorage::new();
+
+        let contract_addr = keeper
+            .register_contract(
+                &mut wasm_storage,
+                code_id,
+                Addr::unchecked("foobar"),
+                Addr::unchecked("admin"),
+                "label".to_owned(),
+                1000,
+            )
+            .unwrap();
+
+        assert_eq!(
+            expected_addr, contract_addr,
+            "custom address generator returned incorrect address"
+        )
+    }
 }
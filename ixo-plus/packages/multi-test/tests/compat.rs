@@ -0,0 +1,427 @@
+//! Compatibility suite for the multi-test crate shared by every workspace.
+//!
+//! The cw-plus contracts were written against an `App` driven through `&mut self`, while the
+//! ixo-swap fork executed through `&self`. Both styles have to keep compiling and behave the
+//! same, and each optional module only takes part in routing when its feature is enabled.
+
+use cosmwasm_std::{
+    coins, Addr, Binary, CosmosMsg, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+};
+use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+
+const OWNER: &str = "owner";
+const DENOM: &str = "uixo";
+
+fn instantiate(_deps: DepsMut, _env: Env, _info: MessageInfo, _msg: Empty) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+/// Dispatches whatever message it is given, so each module can be reached from a contract.
+fn execute(_deps: DepsMut, _env: Env, _info: MessageInfo, msg: CosmosMsg) -> StdResult<Response> {
+    Ok(Response::new().add_message(msg))
+}
+
+fn query(_deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Ok(Binary::default())
+}
+
+fn forwarder() -> Box<dyn Contract<Empty>> {
+    Box::new(ContractWrapper::new(execute, instantiate, query))
+}
+
+fn funded_app() -> App {
+    AppBuilder::new().build(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &Addr::unchecked(OWNER), coins(1_000, DENOM))
+            .unwrap();
+    })
+}
+
+fn bank_send(to: &str, amount: u128) -> CosmosMsg {
+    cosmwasm_std::BankMsg::Send {
+        to_address: to.to_string(),
+        amount: coins(amount, DENOM),
+    }
+    .into()
+}
+
+fn balance(app: &App, addr: &str) -> u128 {
+    app.wrap().query_balance(addr, DENOM).unwrap().amount.u128()
+}
+
+/// The cw-plus style: helpers take `&mut App`.
+fn instantiate_forwarder_mut(app: &mut App) -> Addr {
+    let code_id = app.store_code(forwarder());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &Empty {},
+        &coins(100, DENOM),
+        "forwarder",
+        None,
+    )
+    .unwrap()
+}
+
+/// The ixo-swap style: helpers take a shared `&App`.
+fn instantiate_forwarder(app: &App) -> Addr {
+    let code_id = app.store_code(forwarder());
+    app.instantiate_contract(
+        code_id,
+        Addr::unchecked(OWNER),
+        &Empty {},
+        &coins(100, DENOM),
+        "forwarder",
+        None,
+    )
+    .unwrap()
+}
+
+#[test]
+fn mutable_app_usage() {
+    let mut app = funded_app();
+    let forwarder = instantiate_forwarder_mut(&mut app);
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        forwarder.clone(),
+        &bank_send("alice", 40),
+        &[],
+    )
+    .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    assert_eq!(balance(&app, OWNER), 900);
+    assert_eq!(balance(&app, forwarder.as_str()), 60);
+    assert_eq!(balance(&app, "alice"), 40);
+}
+
+#[test]
+fn shared_app_usage() {
+    let app = funded_app();
+    let forwarder = instantiate_forwarder(&app);
+
+    // several shared handles may drive the same app
+    let (first, second) = (&app, &app);
+    first
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            forwarder.clone(),
+            &bank_send("alice", 15),
+            &[],
+        )
+        .unwrap();
+    second
+        .execute_contract(
+            Addr::unchecked(OWNER),
+            forwarder.clone(),
+            &bank_send("alice", 25),
+            &[],
+        )
+        .unwrap();
+    app.update_block(|block| block.height += 1);
+
+    assert_eq!(balance(&app, OWNER), 900);
+    assert_eq!(balance(&app, forwarder.as_str()), 60);
+    assert_eq!(balance(&app, "alice"), 40);
+}
+
+#[test]
+fn failed_execution_is_rolled_back() {
+    let app = funded_app();
+    let forwarder = instantiate_forwarder(&app);
+
+    app.execute_contract(
+        Addr::unchecked(OWNER),
+        forwarder.clone(),
+        &bank_send("alice", 1_000),
+        &coins(50, DENOM),
+    )
+    .unwrap_err();
+
+    assert_eq!(balance(&app, OWNER), 900);
+    assert_eq!(balance(&app, forwarder.as_str()), 100);
+    assert_eq!(balance(&app, "alice"), 0);
+}
+
+/// The full `App` type names every module, and must not depend on the enabled features.
+type FullApp = App<
+    cw_multi_test::BankKeeper,
+    cosmwasm_std::testing::MockApi,
+    cosmwasm_std::testing::MockStorage,
+    cw_multi_test::FailingModule<Empty, Empty, Empty>,
+    cw_multi_test::WasmKeeper<Empty, Empty>,
+    cw_multi_test::StakeKeeper,
+    cw_multi_test::DistributionKeeper,
+    cw_multi_test::FailingModule<cosmwasm_std::IbcMsg, cosmwasm_std::IbcQuery, Empty>,
+    cw_multi_test::FailingModule<cosmwasm_std::GovMsg, Empty, Empty>,
+    cw_multi_test::StargateKeeper<Empty, Empty>,
+>;
+
+#[test]
+fn app_type_is_the_same_in_every_build() {
+    let app: FullApp = App::default();
+    assert_eq!(balance(&app, OWNER), 0);
+}
+
+#[cfg(not(feature = "staking"))]
+#[test]
+fn staking_is_not_routed_when_disabled() {
+    let app = funded_app();
+    let err = app
+        .execute(
+            Addr::unchecked(OWNER),
+            cosmwasm_std::StakingMsg::Delegate {
+                validator: "validator".to_string(),
+                amount: cosmwasm_std::coin(100, DENOM),
+            }
+            .into(),
+        )
+        .unwrap_err();
+    assert!(err.to_string().starts_with("Cannot execute"));
+}
+
+#[cfg(feature = "staking")]
+mod staking {
+    use cosmwasm_std::{coin, Decimal, StakingMsg, Validator};
+    use cw_multi_test::StakingSudo;
+
+    use super::*;
+
+    #[test]
+    fn delegations_unbond_through_the_queue() {
+        let app = App::default();
+        let owner = Addr::unchecked(OWNER);
+        let bonded = app.wrap().query_bonded_denom().unwrap();
+        let block = app.block_info();
+        app.init_modules(|router, api, storage| {
+            let validator = Validator {
+                address: "testvaloper1".to_string(),
+                commission: Decimal::percent(10),
+                max_commission: Decimal::percent(20),
+                max_change_rate: Decimal::percent(1),
+            };
+            router
+                .staking
+                .add_validator(api, storage, &block, validator)
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &owner, coins(100, &bonded))
+                .unwrap();
+        });
+        let stake = |msg: StakingMsg| app.execute(owner.clone(), msg.into()).unwrap();
+        let bonded_balance = || app.wrap().query_balance(OWNER, &bonded).unwrap().amount;
+
+        stake(StakingMsg::Delegate {
+            validator: "testvaloper1".to_string(),
+            amount: coin(100, &bonded),
+        });
+        stake(StakingMsg::Undelegate {
+            validator: "testvaloper1".to_string(),
+            amount: coin(100, &bonded),
+        });
+
+        // the tokens only come back once the unbonding time has passed
+        app.sudo(StakingSudo::ProcessQueue {}.into()).unwrap();
+        assert_eq!(bonded_balance().u128(), 0);
+        app.update_block(|block| block.time = block.time.plus_seconds(60));
+        app.sudo(StakingSudo::ProcessQueue {}.into()).unwrap();
+        assert_eq!(bonded_balance().u128(), 100);
+    }
+}
+
+#[cfg(feature = "stargate")]
+mod stargate {
+    use cosmwasm_std::QueryRequest;
+    use cw_multi_test::{MsgSend, ProtoCoin, QueryBalanceRequest, QueryBalanceResponse};
+    use cw_multi_test::{MSG_SEND_TYPE_URL, QUERY_BALANCE_PATH};
+    use prost::Message;
+
+    use super::*;
+
+    #[test]
+    fn bank_module_is_reachable_via_stargate() {
+        let app = funded_app();
+        let forwarder = instantiate_forwarder(&app);
+
+        let send: CosmosMsg = CosmosMsg::Stargate {
+            type_url: MSG_SEND_TYPE_URL.to_string(),
+            value: MsgSend {
+                from_address: forwarder.to_string(),
+                to_address: "alice".to_string(),
+                amount: vec![ProtoCoin {
+                    denom: DENOM.to_string(),
+                    amount: "30".to_string(),
+                }],
+            }
+            .encode_to_vec()
+            .into(),
+        };
+        app.execute_contract(Addr::unchecked(OWNER), forwarder, &send, &[])
+            .unwrap();
+
+        let res: QueryBalanceResponse = app
+            .wrap()
+            .query(&QueryRequest::Stargate {
+                path: QUERY_BALANCE_PATH.to_string(),
+                data: QueryBalanceRequest {
+                    address: "alice".to_string(),
+                    denom: DENOM.to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap();
+        assert_eq!(res.balance.unwrap().amount, "30");
+    }
+}
+
+#[cfg(any(feature = "ibc", feature = "gov"))]
+mod accepting {
+    use anyhow::Result as AnyResult;
+    use cosmwasm_std::{Api, BlockInfo, CustomQuery, Querier, Storage};
+    use cw_multi_test::{AppResponse, CosmosRouter, Module};
+    use schemars::JsonSchema;
+    use serde::de::DeserializeOwned;
+    use std::marker::PhantomData;
+
+    use super::*;
+
+    /// A module that accepts every message, standing in for a real ibc or gov implementation.
+    pub struct AcceptingModule<ExecT, QueryT>(PhantomData<(ExecT, QueryT)>);
+
+    impl<ExecT, QueryT> AcceptingModule<ExecT, QueryT> {
+        pub fn new() -> Self {
+            AcceptingModule(PhantomData)
+        }
+    }
+
+    impl<ExecT, QueryT> Module for AcceptingModule<ExecT, QueryT> {
+        type ExecT = ExecT;
+        type QueryT = QueryT;
+        type SudoT = Empty;
+
+        fn execute<ExecC, QueryC>(
+            &self,
+            _api: &dyn Api,
+            _storage: &mut dyn Storage,
+            _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+            _block: &BlockInfo,
+            _sender: Addr,
+            _msg: Self::ExecT,
+        ) -> AnyResult<AppResponse>
+        where
+            ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+            QueryC: CustomQuery + DeserializeOwned + 'static,
+        {
+            Ok(AppResponse::default())
+        }
+
+        fn sudo<ExecC, QueryC>(
+            &self,
+            _api: &dyn Api,
+            _storage: &mut dyn Storage,
+            _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+            _block: &BlockInfo,
+            _msg: Self::SudoT,
+        ) -> AnyResult<AppResponse>
+        where
+            ExecC: std::fmt::Debug + Clone + PartialEq + JsonSchema + DeserializeOwned + 'static,
+            QueryC: CustomQuery + DeserializeOwned + 'static,
+        {
+            Ok(AppResponse::default())
+        }
+
+        fn query(
+            &self,
+            _api: &dyn Api,
+            _storage: &dyn Storage,
+            _querier: &dyn Querier,
+            _block: &BlockInfo,
+            _request: Self::QueryT,
+        ) -> AnyResult<Binary> {
+            Ok(Binary::default())
+        }
+    }
+}
+
+#[cfg(feature = "ibc")]
+mod ibc {
+    use cosmwasm_std::{IbcMsg, IbcQuery};
+    use cw_multi_test::Ibc;
+
+    use super::accepting::AcceptingModule;
+    use super::*;
+
+    impl Ibc for AcceptingModule<IbcMsg, IbcQuery> {}
+
+    fn close_channel() -> CosmosMsg {
+        IbcMsg::CloseChannel {
+            channel_id: "channel-0".to_string(),
+        }
+        .into()
+    }
+
+    #[test]
+    fn ibc_fails_by_default() {
+        let app = funded_app();
+        let forwarder = instantiate_forwarder(&app);
+        app.execute_contract(Addr::unchecked(OWNER), forwarder, &close_channel(), &[])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn ibc_routes_to_the_configured_module() {
+        let app = AppBuilder::new()
+            .with_ibc(AcceptingModule::new())
+            .build(|_, _, _| ());
+        let code_id = app.store_code(forwarder());
+        let forwarder = app
+            .instantiate_contract(code_id, Addr::unchecked(OWNER), &Empty {}, &[], "fwd", None)
+            .unwrap();
+        app.execute_contract(Addr::unchecked(OWNER), forwarder, &close_channel(), &[])
+            .unwrap();
+    }
+}
+
+#[cfg(feature = "gov")]
+mod gov {
+    use cosmwasm_std::{GovMsg, VoteOption};
+    use cw_multi_test::Gov;
+
+    use super::accepting::AcceptingModule;
+    use super::*;
+
+    impl Gov for AcceptingModule<GovMsg, Empty> {}
+
+    fn vote() -> CosmosMsg {
+        GovMsg::Vote {
+            proposal_id: 1,
+            vote: VoteOption::Yes,
+        }
+        .into()
+    }
+
+    #[test]
+    fn gov_fails_by_default() {
+        let app = funded_app();
+        let forwarder = instantiate_forwarder(&app);
+        app.execute_contract(Addr::unchecked(OWNER), forwarder, &vote(), &[])
+            .unwrap_err();
+    }
+
+    #[test]
+    fn gov_routes_to_the_configured_module() {
+        let app = AppBuilder::new()
+            .with_gov(AcceptingModule::new())
+            .build(|_, _, _| ());
+        let code_id = app.store_code(forwarder());
+        let forwarder = app
+            .instantiate_contract(code_id, Addr::unchecked(OWNER), &Empty {}, &[], "fwd", None)
+            .unwrap();
+        app.execute_contract(Addr::unchecked(OWNER), forwarder, &vote(), &[])
+            .unwrap();
+    }
+}
@@ -7,7 +7,9 @@ For more information on this package, please check out the
 
 use proc_macro::TokenStream;
 use syn::{
-    Ident,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    Error, GenericArgument, Ident, ItemConst, LitStr, PathArguments, Token, Type,
     __private::{quote::quote, Span},
     parse_macro_input, ItemStruct,
 };
@@ -42,3 +44,117 @@ pub fn index_list(attr: TokenStream, item: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+/// Arguments of `#[versioned_state(from = "old_key", old = OldType, migrate = convert_fn)]`
+struct VersionedStateArgs {
+    from: LitStr,
+    old: Box<Type>,
+    migrate: syn::Path,
+}
+
+enum VersionedStateArg {
+    From(LitStr),
+    Old(Box<Type>),
+    Migrate(syn::Path),
+}
+
+impl Parse for VersionedStateArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        match name.to_string().as_str() {
+            "from" => Ok(VersionedStateArg::From(input.parse()?)),
+            "old" => Ok(VersionedStateArg::Old(Box::new(input.parse()?))),
+            "migrate" => Ok(VersionedStateArg::Migrate(input.parse()?)),
+            _ => Err(Error::new(
+                name.span(),
+                "expected one of `from`, `old` or `migrate`",
+            )),
+        }
+    }
+}
+
+impl Parse for VersionedStateArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let (mut from, mut old, mut migrate) = (None, None, None);
+        for arg in Punctuated::<VersionedStateArg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                VersionedStateArg::From(v) => from = Some(v),
+                VersionedStateArg::Old(v) => old = Some(v),
+                VersionedStateArg::Migrate(v) => migrate = Some(v),
+            }
+        }
+        let missing = |name| Error::new(Span::call_site(), format!("missing `{}` argument", name));
+        Ok(VersionedStateArgs {
+            from: from.ok_or_else(|| missing("from"))?,
+            old: old.ok_or_else(|| missing("old"))?,
+            migrate: migrate.ok_or_else(|| missing("migrate"))?,
+        })
+    }
+}
+
+/// Splits `Map<'a, K, T>` / `Item<'a, T>` into its name and type arguments, skipping lifetimes.
+fn storage_type(ty: &Type) -> syn::Result<(Ident, Vec<Type>)> {
+    let unsupported = || Error::new_spanned(ty, "expected an `Item` or a `Map`");
+    let segment = match ty {
+        Type::Path(path) => path.path.segments.last().ok_or_else(unsupported)?,
+        _ => return Err(unsupported()),
+    };
+    let args = match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty.clone()),
+                _ => None,
+            })
+            .collect::<Vec<_>>(),
+        _ => return Err(unsupported()),
+    };
+    match (segment.ident.to_string().as_str(), args.len()) {
+        ("Item", 1) | ("Map", 2) => Ok((segment.ident.clone(), args)),
+        _ => Err(unsupported()),
+    }
+}
+
+fn versioned_state_impl(args: VersionedStateArgs, input: ItemConst) -> syn::Result<TokenStream> {
+    let VersionedStateArgs { from, old, migrate } = args;
+    let vis = &input.vis;
+    let name = &input.ident;
+    let migration = Ident::new(&format!("{}_MIGRATION", name), name.span());
+
+    let (kind, args) = storage_type(&input.ty)?;
+    let migration_const = if kind == "Map" {
+        let (key, new) = (&args[0], &args[1]);
+        quote! {
+            #vis const #migration: cw_storage_plus::MapMigration<'static, #key, #old, #new> =
+                cw_storage_plus::MapMigration::new(#from, #name, #migrate);
+        }
+    } else {
+        let new = &args[0];
+        quote! {
+            #vis const #migration: cw_storage_plus::ItemMigration<'static, #old, #new> =
+                cw_storage_plus::ItemMigration::new(#from, #name, #migrate);
+        }
+    };
+
+    Ok(TokenStream::from(quote! {
+        #input
+
+        #migration_const
+    }))
+}
+
+/// Declares how an `Item` or `Map` moved from a previous version of the contract.
+///
+/// Next to the annotated constant, it generates `<NAME>_MIGRATION`, a
+/// `cw_storage_plus::MapMigration` or `cw_storage_plus::ItemMigration` that moves the data from
+/// the `from` key, converting the `old` values with the `migrate` function.
+#[proc_macro_attribute]
+pub fn versioned_state(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let args = parse_macro_input!(attr as VersionedStateArgs);
+    let input = parse_macro_input!(item as ItemConst);
+
+    versioned_state_impl(args, input)
+        .unwrap_or_else(|err| TokenStream::from(err.to_compile_error()))
+}
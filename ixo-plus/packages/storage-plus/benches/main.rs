@@ -4,7 +4,9 @@ use rand::Rng;
 use std::mem;
 use std::time::Duration;
 
-use cw_storage_plus::IntKey;
+use cosmwasm_std::testing::MockStorage;
+use cosmwasm_std::to_vec;
+use cw_storage_plus::{IntKey, MerkleMap, PrimaryKey};
 
 fn bench_signed_int_key(c: &mut Criterion) {
     let mut group = c.benchmark_group("Signed int keys");
@@ -140,6 +142,50 @@ fn bench_unsigned_int_key(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_merkle_map(c: &mut Criterion) {
+    let mut group = c.benchmark_group("Merkle map");
+
+    const MEMBERS: MerkleMap<u32, u64> = MerkleMap::new("members", "members__tree");
+
+    fn k() -> u32 {
+        rand::thread_rng().gen_range(u32::MIN..u32::MAX)
+    }
+
+    let mut store = MockStorage::new();
+    for i in 0..10_000u32 {
+        MEMBERS.save(&mut store, k(), &(i as u64)).unwrap();
+    }
+    let member = k();
+    MEMBERS.save(&mut store, member, &1).unwrap();
+
+    group.bench_function("save into 10k entries", |b| {
+        b.iter(|| {
+            MEMBERS.save(&mut store, black_box(k()), &1).unwrap();
+        });
+    });
+
+    let root = MEMBERS.root(&store).unwrap();
+    let key = member.joined_key();
+    let value = to_vec(&1u64).unwrap();
+
+    group.bench_function("prove in 10k entries", |b| {
+        b.iter(|| {
+            black_box(MEMBERS.prove(&store, black_box(member)).unwrap());
+        });
+    });
+
+    let proof = MEMBERS.prove(&store, member).unwrap();
+    assert!(proof.verify_inclusion(&root, &key, &value));
+
+    group.bench_function("verify inclusion in 10k entries", |b| {
+        b.iter(|| {
+            assert!(black_box(&proof).verify_inclusion(&root, &key, &value));
+        });
+    });
+
+    group.finish();
+}
+
 fn make_config() -> Criterion {
     Criterion::default()
         .without_plots()
@@ -158,4 +204,9 @@ criterion_group!(
     config = make_config();
     targets = bench_unsigned_int_key
 );
-criterion_main!(signed_int_key, unsigned_int_key);
+criterion_group!(
+    name = merkle_map;
+    config = make_config();
+    targets = bench_merkle_map
+);
+criterion_main!(signed_int_key, unsigned_int_key, merkle_map);
@@ -79,6 +79,21 @@ impl<'a, T: Serialize + DeserializeOwned> Deque<'a, T> {
         Ok(value)
     }
 
+    /// Removes the first `count` elements of the deque (all of them if it holds fewer)
+    /// without reading them
+    pub(crate) fn truncate_front(&self, storage: &mut dyn Storage, count: u32) -> StdResult<()> {
+        let head = self.head(storage)?;
+        let count = count.min(calc_len(head, self.tail(storage)?));
+        if count == 0 {
+            return Ok(());
+        }
+        for offset in 0..count {
+            self.remove_unchecked(storage, head.wrapping_add(offset));
+        }
+        self.set_head(storage, head.wrapping_add(count));
+        Ok(())
+    }
+
     /// Returns the first element of the deque without removing it
     pub fn front(&self, storage: &dyn Storage) -> StdResult<Option<T>> {
         let pos = self.head(storage)?;
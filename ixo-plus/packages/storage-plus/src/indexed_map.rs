@@ -141,6 +141,9 @@ where
     }
 
     /// Clears the map, removing all elements.
+    ///
+    /// Only the primary entries are removed, the indexes are not updated. Use `remove` on
+    /// every key instead if the indexes are still read afterwards.
     pub fn clear(&self, store: &mut dyn Storage) {
         const TAKE: usize = 10;
         let mut cleared = false;
@@ -306,9 +309,9 @@ mod test {
     use super::*;
 
     use crate::indexes::test::{index_string_tuple, index_tuple};
-    use crate::{MultiIndex, UniqueIndex};
+    use crate::{CountedIndex, MultiIndex, UniqueIndex};
     use cosmwasm_std::testing::MockStorage;
-    use cosmwasm_std::{MemoryStorage, Order};
+    use cosmwasm_std::{MemoryStorage, Order, Uint128};
     use serde::{Deserialize, Serialize};
 
     #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
@@ -1708,6 +1711,113 @@ mod test {
         }
     }
 
+    #[test]
+    fn counted_index_tracks_counts_and_sums() {
+        struct Indexes<'a> {
+            active: CountedIndex<'a, u8, Module>,
+        }
+
+        impl<'a> IndexList<Module> for Indexes<'a> {
+            fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Module>> + '_> {
+                let v: Vec<&dyn Index<Module>> = vec![&self.active];
+                Box::new(v.into_iter())
+            }
+        }
+
+        #[derive(Serialize, Deserialize, PartialEq, Debug, Clone)]
+        struct Module {
+            active: bool,
+            weight: u64,
+        }
+
+        let mut store = MockStorage::new();
+        let map: IndexedMap<&str, Module, Indexes> = IndexedMap::new(
+            "modules",
+            Indexes {
+                active: CountedIndex::with_sum(
+                    |m| m.active as u8,
+                    |m| Uint128::from(m.weight),
+                    "modules__active",
+                ),
+            },
+        );
+        let module = |active, weight| Module { active, weight };
+
+        map.save(&mut store, "a", &module(true, 10)).unwrap();
+        map.save(&mut store, "b", &module(true, 5)).unwrap();
+        map.save(&mut store, "c", &module(false, 7)).unwrap();
+
+        let active = &map.idx.active;
+        assert_eq!(active.count_by(&store, 1).unwrap(), 2);
+        assert_eq!(active.sum_by(&store, 1).unwrap(), Uint128::new(15));
+        assert_eq!(active.count_by(&store, 0).unwrap(), 1);
+        assert_eq!(active.total_count(&store).unwrap(), 3);
+        assert_eq!(active.total_sum(&store).unwrap(), Uint128::new(22));
+
+        // overwriting moves the value between index entries
+        map.save(&mut store, "a", &module(false, 3)).unwrap();
+        map.update(&mut store, "b", |m| -> StdResult<_> {
+            Ok(Module {
+                weight: 6,
+                ..m.unwrap()
+            })
+        })
+        .unwrap();
+        let active = &map.idx.active;
+        assert_eq!(active.count_by(&store, 1).unwrap(), 1);
+        assert_eq!(active.sum_by(&store, 1).unwrap(), Uint128::new(6));
+        assert_eq!(active.count_by(&store, 0).unwrap(), 2);
+        assert_eq!(active.sum_by(&store, 0).unwrap(), Uint128::new(10));
+        assert_eq!(active.total_count(&store).unwrap(), 3);
+        assert_eq!(active.total_sum(&store).unwrap(), Uint128::new(16));
+
+        // removing the last value of an index entry drops it
+        map.remove(&mut store, "b").unwrap();
+        // removing a missing key is a no-op
+        map.remove(&mut store, "b").unwrap();
+        let active = &map.idx.active;
+        assert_eq!(active.count_by(&store, 1).unwrap(), 0);
+        assert_eq!(active.sum_by(&store, 1).unwrap(), Uint128::zero());
+        assert_eq!(active.total_count(&store).unwrap(), 2);
+        assert_eq!(active.total_sum(&store).unwrap(), Uint128::new(10));
+    }
+
+    #[test]
+    fn counted_index_without_sum() {
+        struct Indexes<'a> {
+            name: CountedIndex<'a, String, Data>,
+        }
+
+        impl<'a> IndexList<Data> for Indexes<'a> {
+            fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Data>> + '_> {
+                let v: Vec<&dyn Index<Data>> = vec![&self.name];
+                Box::new(v.into_iter())
+            }
+        }
+
+        let mut store = MockStorage::new();
+        let map: IndexedMap<&str, Data, Indexes> = IndexedMap::new(
+            "data",
+            Indexes {
+                name: CountedIndex::new(|d| d.name.clone(), "data__name_count"),
+            },
+        );
+        let data = Data {
+            name: "Maria".to_string(),
+            last_name: "Doe".to_string(),
+            age: 42,
+        };
+        map.save(&mut store, "1", &data).unwrap();
+        map.save(&mut store, "2", &data).unwrap();
+
+        let name = &map.idx.name;
+        assert_eq!(name.count_by(&store, "Maria".to_string()).unwrap(), 2);
+        assert_eq!(name.count_by(&store, "John".to_string()).unwrap(), 0);
+        assert_eq!(name.total_count(&store).unwrap(), 2);
+        name.sum_by(&store, "Maria".to_string()).unwrap_err();
+        name.total_sum(&store).unwrap_err();
+    }
+
     #[test]
     fn clear_works() {
         let mut storage = MockStorage::new();
@@ -1,7 +1,7 @@
 // this module requires iterator to be useful at all
 #![cfg(feature = "iterator")]
 
-use cosmwasm_std::{StdError, StdResult, Storage};
+use cosmwasm_std::{StdError, StdResult, Storage, Timestamp};
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
@@ -9,7 +9,7 @@ use crate::de::KeyDeserialize;
 use crate::iter_helpers::deserialize_kv;
 use crate::keys::{Prefixer, PrimaryKey};
 use crate::prefix::{namespaced_prefix_range, Prefix};
-use crate::snapshot::{ChangeSet, SnapshotMap};
+use crate::snapshot::{ChangeSet, Clock, Retention, SnapshotMap};
 use crate::PrefixBound;
 use crate::{Bound, IndexList, Map, Path, Strategy};
 
@@ -58,6 +58,18 @@ impl<'a, K, T, I> IndexedSnapshotMap<'a, K, T, I> {
         }
     }
 
+    /// Sets how long the changelog is kept, `Retention::Forever` by default
+    pub fn with_retention(mut self, retention: Retention) -> Self {
+        self.primary = self.primary.with_retention(retention);
+        self
+    }
+
+    /// Sets what the snapshot is keyed by, `Clock::Height` by default
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.primary = self.primary.with_clock(clock);
+        self
+    }
+
     pub fn changelog(&self) -> &Map<'a, (K, u64), ChangeSet<T>> {
         self.primary.changelog()
     }
@@ -90,6 +102,14 @@ where
         self.primary.assert_checkpointed(store, height)
     }
 
+    pub fn pruned_before(&self, store: &dyn Storage) -> StdResult<u64> {
+        self.primary.pruned_before(store)
+    }
+
+    pub fn prune(&self, store: &mut dyn Storage, before_height: u64, limit: u32) -> StdResult<u32> {
+        self.primary.prune(store, before_height, limit)
+    }
+
     pub fn key(&self, k: K) -> Path<T> {
         self.primary.key(k)
     }
@@ -165,6 +185,86 @@ where
         Ok(output)
     }
 
+    // The same API keyed by block time, for maps built `with_clock(Clock::Timestamp)`.
+    // These return an error on maps keyed by height.
+
+    pub fn save_at_time(
+        &self,
+        store: &mut dyn Storage,
+        key: K,
+        data: &T,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.save(store, key, data, self.primary.time_point(time)?)
+    }
+
+    pub fn remove_at_time(
+        &self,
+        store: &mut dyn Storage,
+        key: K,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.remove(store, key, self.primary.time_point(time)?)
+    }
+
+    pub fn replace_at_time(
+        &self,
+        store: &mut dyn Storage,
+        key: K,
+        data: Option<&T>,
+        old_data: Option<&T>,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.replace(store, key, data, old_data, self.primary.time_point(time)?)
+    }
+
+    pub fn update_at_time<A, E>(
+        &self,
+        store: &mut dyn Storage,
+        key: K,
+        time: Timestamp,
+        action: A,
+    ) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        self.update(store, key, self.primary.time_point(time)?, action)
+    }
+
+    pub fn add_checkpoint_at_time(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.primary.add_checkpoint_at_time(store, time)
+    }
+
+    pub fn remove_checkpoint_at_time(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.primary.remove_checkpoint_at_time(store, time)
+    }
+
+    pub fn may_load_at_time(
+        &self,
+        store: &dyn Storage,
+        key: K,
+        time: Timestamp,
+    ) -> StdResult<Option<T>> {
+        self.primary.may_load_at_time(store, key, time)
+    }
+
+    pub fn assert_checkpointed_at_time(
+        &self,
+        store: &dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.primary.assert_checkpointed_at_time(store, time)
+    }
+
     // Everything else, that doesn't touch indexers, is just pass-through from self.core,
     // thus can be used from while iterating over indexes
 
@@ -1221,4 +1321,54 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn timestamp_clock_works() {
+        let mut store = MockStorage::new();
+        let map = build_snapshot_map().with_clock(Clock::Timestamp);
+        let maria = Data {
+            name: "Maria".to_string(),
+            last_name: "Doe".to_string(),
+            age: 42,
+        };
+        let older = Data {
+            age: 43,
+            ..maria.clone()
+        };
+
+        let t1 = Timestamp::from_seconds(1_000);
+        let t2 = t1.plus_seconds(6);
+        map.save_at_time(&mut store, "1", &maria, t1).unwrap();
+        map.update_at_time(&mut store, "1", t2, |_| -> StdResult<_> {
+            Ok(older.clone())
+        })
+        .unwrap();
+
+        // the index follows the current value
+        let (pk, data) = map.idx.age.item(&store, 43).unwrap().unwrap();
+        assert_eq!((pk, data), (b"1".to_vec(), older.clone()));
+        assert_eq!(map.idx.age.item(&store, 42).unwrap(), None);
+
+        // history is queried by time, as of the beginning of that block
+        assert_eq!(map.may_load_at_time(&store, "1", t1).unwrap(), None);
+        assert_eq!(map.may_load_at_time(&store, "1", t2).unwrap(), Some(maria));
+        assert_eq!(
+            map.may_load_at_time(&store, "1", t2.plus_seconds(1))
+                .unwrap(),
+            Some(older)
+        );
+        map.remove_at_time(&mut store, "1", t2.plus_seconds(6))
+            .unwrap();
+        assert_eq!(map.may_load(&store, "1").unwrap(), None);
+        assert!(map.idx.age.item(&store, 43).unwrap().is_none());
+
+        // a map keyed by height refuses times
+        let by_height = build_snapshot_map();
+        assert_eq!(
+            by_height.may_load_at_time(&store, "1", t1),
+            Err(StdError::generic_err(
+                "Snapshot is keyed by block height, not by time"
+            ))
+        );
+    }
 }
@@ -0,0 +1,194 @@
+// this module requires iterator to be useful at all
+#![cfg(feature = "iterator")]
+
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{StdError, StdResult, Storage, Uint128};
+
+use crate::item::Item;
+use crate::map::Map;
+use crate::{Index, PrimaryKey};
+
+/// Tally is the aggregate kept for every index value, and for the whole map
+#[derive(Default, Deserialize, Serialize)]
+struct Tally {
+    count: u64,
+    sum: Uint128,
+}
+
+/// CountedIndex stores (namespace, index_name, idx_value) -> {count, sum}
+/// and (namespace, index_name) -> {count, sum} for the totals over all values.
+/// It doesn't reference the primary keys, so it can't be iterated into records; it
+/// only answers how many values map to an index value, and optionally the sum of a
+/// numeric field over them, without ranging over the map.
+///
+/// The aggregates are updated by `IndexedMap::save`, `replace`, `update` and `remove`.
+/// `IndexedMap::clear` only deletes the primary entries and leaves every index untouched,
+/// so after clearing a map the aggregates still describe the removed values; remove the
+/// entries one by one instead when the counts must stay correct.
+pub struct CountedIndex<'a, IK, T> {
+    index: fn(&T) -> IK,
+    sum: Option<fn(&T) -> Uint128>,
+    idx_map: Map<'a, IK, Tally>,
+    total: Item<'a, Tally>,
+    phantom: PhantomData<T>,
+}
+
+impl<'a, IK, T> CountedIndex<'a, IK, T> {
+    // TODO: make this a const fn
+    /// Create a new CountedIndex that only counts values
+    ///
+    /// idx_fn - lambda creating index key from value
+    /// idx_namespace - prefix for the index value
+    ///
+    /// ## Example:
+    ///
+    /// ```rust
+    /// use cw_storage_plus::CountedIndex;
+    ///
+    /// struct Data {
+    ///     pub active: bool,
+    ///     pub weight: u64,
+    /// }
+    ///
+    /// CountedIndex::new(|d: &Data| d.active, "data__active");
+    /// ```
+    pub fn new(idx_fn: fn(&T) -> IK, idx_namespace: &'a str) -> Self {
+        CountedIndex {
+            index: idx_fn,
+            sum: None,
+            idx_map: Map::new(idx_namespace),
+            // the map entries are length-prefixed, so they never clash with the bare namespace
+            total: Item::new(idx_namespace),
+            phantom: PhantomData,
+        }
+    }
+
+    /// Create a new CountedIndex that also sums a numeric field of the values
+    ///
+    /// idx_fn - lambda creating index key from value
+    /// sum_fn - lambda extracting the summed amount from value
+    /// idx_namespace - prefix for the index value
+    ///
+    /// ## Example:
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Uint128;
+    /// use cw_storage_plus::CountedIndex;
+    ///
+    /// struct Data {
+    ///     pub active: bool,
+    ///     pub weight: u64,
+    /// }
+    ///
+    /// CountedIndex::with_sum(
+    ///     |d: &Data| d.active,
+    ///     |d: &Data| Uint128::from(d.weight),
+    ///     "data__active",
+    /// );
+    /// ```
+    pub fn with_sum(
+        idx_fn: fn(&T) -> IK,
+        sum_fn: fn(&T) -> Uint128,
+        idx_namespace: &'a str,
+    ) -> Self {
+        CountedIndex {
+            sum: Some(sum_fn),
+            ..Self::new(idx_fn, idx_namespace)
+        }
+    }
+
+    fn amount(&self, data: &T) -> Uint128 {
+        self.sum.map(|sum| sum(data)).unwrap_or_default()
+    }
+}
+
+impl<'a, IK, T> Index<T> for CountedIndex<'a, IK, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    IK: PrimaryKey<'a>,
+{
+    fn save(&self, store: &mut dyn Storage, _pk: &[u8], data: &T) -> StdResult<()> {
+        let amount = self.amount(data);
+        let add = |tally: Option<Tally>| -> StdResult<_> {
+            let tally = tally.unwrap_or_default();
+            Ok(Tally {
+                count: tally.count + 1,
+                sum: tally.sum.checked_add(amount)?,
+            })
+        };
+        self.idx_map.update(store, (self.index)(data), add)?;
+        let total = add(self.total.may_load(store)?)?;
+        self.total.save(store, &total)
+    }
+
+    fn remove(&self, store: &mut dyn Storage, _pk: &[u8], old_data: &T) -> StdResult<()> {
+        let amount = self.amount(old_data);
+        let sub = |tally: Tally| -> StdResult<_> {
+            Ok(Tally {
+                count: tally
+                    .count
+                    .checked_sub(1)
+                    .ok_or_else(|| StdError::generic_err("Counted index out of sync"))?,
+                sum: tally.sum.checked_sub(amount)?,
+            })
+        };
+
+        let idx = (self.index)(old_data);
+        let tally = sub(self
+            .idx_map
+            .may_load(store, idx.clone())?
+            .unwrap_or_default())?;
+        // drop empty entries, so they don't accumulate for values no longer in use
+        if tally.count == 0 {
+            self.idx_map.remove(store, idx);
+        } else {
+            self.idx_map.save(store, idx, &tally)?;
+        }
+        let total = sub(self.total.may_load(store)?.unwrap_or_default())?;
+        self.total.save(store, &total)
+    }
+}
+
+impl<'a, IK, T> CountedIndex<'a, IK, T>
+where
+    IK: PrimaryKey<'a>,
+{
+    /// Number of values stored under this index value
+    pub fn count_by(&self, store: &dyn Storage, idx: IK) -> StdResult<u64> {
+        Ok(self.load_tally(store, idx)?.count)
+    }
+
+    /// Sum of the indexed field over the values stored under this index value.
+    /// Returns an error if the index was created without a sum function.
+    pub fn sum_by(&self, store: &dyn Storage, idx: IK) -> StdResult<Uint128> {
+        self.ensure_sum()?;
+        Ok(self.load_tally(store, idx)?.sum)
+    }
+
+    /// Number of values stored in the whole map
+    pub fn total_count(&self, store: &dyn Storage) -> StdResult<u64> {
+        Ok(self.total.may_load(store)?.unwrap_or_default().count)
+    }
+
+    /// Sum of the indexed field over the whole map.
+    /// Returns an error if the index was created without a sum function.
+    pub fn total_sum(&self, store: &dyn Storage) -> StdResult<Uint128> {
+        self.ensure_sum()?;
+        Ok(self.total.may_load(store)?.unwrap_or_default().sum)
+    }
+
+    fn load_tally(&self, store: &dyn Storage, idx: IK) -> StdResult<Tally> {
+        Ok(self.idx_map.may_load(store, idx)?.unwrap_or_default())
+    }
+
+    fn ensure_sum(&self) -> StdResult<()> {
+        match self.sum {
+            Some(_) => Ok(()),
+            None => Err(StdError::generic_err("Counted index has no sum function")),
+        }
+    }
+}
@@ -1,8 +1,10 @@
 // this module requires iterator to be useful at all
 #![cfg(feature = "iterator")]
+mod counted;
 mod multi;
 mod unique;
 
+pub use counted::CountedIndex;
 pub use multi::MultiIndex;
 pub use unique::UniqueIndex;
 
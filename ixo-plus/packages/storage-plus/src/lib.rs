@@ -26,8 +26,11 @@ mod item;
 mod iter_helpers;
 mod keys;
 mod map;
+mod merkle;
+mod migration;
 mod path;
 mod prefix;
+mod ring_buffer;
 mod snapshot;
 
 #[cfg(feature = "iterator")]
@@ -41,6 +44,8 @@ pub use indexed_map::{IndexList, IndexedMap};
 #[cfg(feature = "iterator")]
 pub use indexed_snapshot::IndexedSnapshotMap;
 #[cfg(feature = "iterator")]
+pub use indexes::CountedIndex;
+#[cfg(feature = "iterator")]
 pub use indexes::Index;
 #[cfg(feature = "iterator")]
 pub use indexes::MultiIndex;
@@ -50,11 +55,15 @@ pub use int_key::IntKey;
 pub use item::Item;
 pub use keys::{Key, Prefixer, PrimaryKey};
 pub use map::Map;
+pub use merkle::{MerkleLeaf, MerkleMap, MerkleProof};
+#[cfg(feature = "iterator")]
+pub use migration::{ItemMigration, MapMigration, MigrationProgress};
 pub use path::Path;
 #[cfg(feature = "iterator")]
 pub use prefix::{range_with_prefix, Prefix};
+pub use ring_buffer::RingBuffer;
 #[cfg(feature = "iterator")]
-pub use snapshot::{SnapshotItem, SnapshotMap, Strategy};
+pub use snapshot::{Clock, Retention, SnapshotItem, SnapshotMap, Strategy};
 
 // cw_storage_macro reexports
 #[cfg(all(feature = "iterator", feature = "macro"))]
@@ -85,3 +94,41 @@ extern crate cw_storage_macro;
 /// ```
 ///
 pub use cw_storage_macro::index_list;
+#[cfg(all(feature = "iterator", feature = "macro"))]
+/// Declare how an `Item` or `Map` moved from a previous version of the contract.
+/// This generates a `<NAME>_MIGRATION` constant next to it, an `ItemMigration` or a
+/// `MapMigration` that converts the old values with the given function.
+///
+/// # Example
+///
+/// ```rust
+/// use cosmwasm_std::StdResult;
+/// use cw_storage_plus::{versioned_state, Map};
+/// use serde::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct ModuleV1 {
+///     address: String,
+/// }
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Module {
+///     address: String,
+///     active: bool,
+/// }
+///
+/// fn upgrade(old: ModuleV1) -> StdResult<Module> {
+///     Ok(Module { address: old.address, active: true })
+/// }
+///
+/// #[versioned_state(from = "modules", old = ModuleV1, migrate = upgrade)]
+/// const MODULES: Map<u32, Module> = Map::new("modules_v2");
+///
+/// // in a migrate or execute entry point
+/// # let mut storage = cosmwasm_std::testing::MockStorage::new();
+/// let progress = MODULES_MIGRATION.migrate_chunk(&mut storage, 50)?;
+/// # assert!(progress.done);
+/// # StdResult::Ok(())
+/// ```
+///
+pub use cw_storage_macro::versioned_state;
@@ -0,0 +1,611 @@
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use cosmwasm_std::{to_vec, Binary, StdError, StdResult, Storage};
+
+#[cfg(feature = "iterator")]
+use crate::bound::Bound;
+#[cfg(feature = "iterator")]
+use crate::de::KeyDeserialize;
+use crate::item::Item;
+use crate::keys::PrimaryKey;
+use crate::map::Map;
+#[cfg(feature = "iterator")]
+use crate::prefix::Prefix;
+
+type Hash = [u8; 32];
+
+/// The hash of an empty subtree, and the root of an empty map
+const EMPTY: Hash = [0; 32];
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+/// A leaf of the tree commits to one key and its value, by their hashes.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MerkleLeaf {
+    /// sha256 of the raw (joined) map key
+    pub key_hash: Binary,
+    /// sha256 of the value, as serialized in storage
+    pub value_hash: Binary,
+}
+
+impl MerkleLeaf {
+    fn hash(&self) -> Hash {
+        hash_parts(LEAF_PREFIX, &self.key_hash, &self.value_hash)
+    }
+}
+
+/// Proof that a key is (or is not) part of a [`MerkleMap`] with a given root.
+///
+/// It lists the sibling hashes from the root down to the position of the key in the tree, and the
+/// leaf found at that position, if any. A proof for a missing key ends either in an empty
+/// position or in a leaf for another key sharing the same path.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema)]
+pub struct MerkleProof {
+    pub siblings: Vec<Binary>,
+    pub leaf: Option<MerkleLeaf>,
+}
+
+impl MerkleProof {
+    /// Checks that `key` (the raw joined key, see [`PrimaryKey::joined_key`]) is stored with
+    /// `value` (the serialized value, as by `cosmwasm_std::to_vec`) in a map with the given root.
+    pub fn verify_inclusion(&self, root: &[u8], key: &[u8], value: &[u8]) -> bool {
+        let key_hash = sha256(key);
+        match &self.leaf {
+            Some(leaf) => {
+                leaf.key_hash.as_slice() == key_hash
+                    && leaf.value_hash.as_slice() == sha256(value)
+                    && self.computed_root(&key_hash).as_ref().map(|r| &r[..]) == Some(root)
+            }
+            None => false,
+        }
+    }
+
+    /// Checks that `key` (the raw joined key, see [`PrimaryKey::joined_key`]) is not stored in a
+    /// map with the given root.
+    pub fn verify_exclusion(&self, root: &[u8], key: &[u8]) -> bool {
+        let key_hash = sha256(key);
+        if let Some(leaf) = &self.leaf {
+            if leaf.key_hash.as_slice() == key_hash {
+                return false;
+            }
+        }
+        self.computed_root(&key_hash).as_ref().map(|r| &r[..]) == Some(root)
+    }
+
+    /// Folds the siblings into a root, or returns None if the proof is malformed
+    fn computed_root(&self, key_hash: &Hash) -> Option<Hash> {
+        if self.siblings.len() > 256 {
+            return None;
+        }
+        let mut current = match &self.leaf {
+            Some(leaf) => {
+                let leaf_path = to_hash(&leaf.key_hash).ok()?;
+                // the leaf must sit on the path of the key
+                if common_prefix_len(&leaf_path, key_hash) < self.siblings.len() {
+                    return None;
+                }
+                to_hash(&leaf.value_hash).ok()?;
+                leaf.hash()
+            }
+            None => EMPTY,
+        };
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            let sibling = to_hash(sibling).ok()?;
+            current = if bit(key_hash, depth) {
+                hash_parts(NODE_PREFIX, &sibling, &current)
+            } else {
+                hash_parts(NODE_PREFIX, &current, &sibling)
+            };
+        }
+        Some(current)
+    }
+}
+
+/// Nodes are stored only for non-empty subtrees. A subtree holding a single value is stored as
+/// its leaf, so a branch only exists where at least two keys share a path.
+#[derive(Serialize, Deserialize)]
+enum Node {
+    Leaf(MerkleLeaf),
+    Branch { left: Binary, right: Binary },
+}
+
+impl Node {
+    fn hash(&self) -> Hash {
+        match self {
+            Node::Leaf(leaf) => leaf.hash(),
+            Node::Branch { left, right } => hash_parts(NODE_PREFIX, left, right),
+        }
+    }
+
+    fn branch(side: bool, child: &[u8], other: &[u8]) -> Self {
+        let (child, other) = (Binary::from(child), Binary::from(other));
+        match side {
+            false => Node::Branch {
+                left: child,
+                right: other,
+            },
+            true => Node::Branch {
+                left: other,
+                right: child,
+            },
+        }
+    }
+}
+
+/// What a removal left behind in the subtree below the current branch
+enum Removed {
+    Nothing,
+    Leaf(MerkleLeaf),
+    Hash(Hash),
+}
+
+/// MerkleMap is a Map that commits to its whole content with a single root hash, so contracts and
+/// light clients can prove that a key is (or is not) part of the map at a given root.
+///
+/// The commitment is a sparse Merkle tree over the sha256 of the raw keys, with the leaves
+/// committing to the sha256 of the stored values. Every `save` and `remove` updates the tree and
+/// the root, which costs about `log2(n)` node reads and writes. Subtrees holding a single value
+/// are collapsed into their leaf, so only branches where keys actually diverge are stored.
+///
+/// The map entries are stored exactly like a `Map` under `pk_namespace`, while the tree and its
+/// root are stored under `tree_namespace`.
+pub struct MerkleMap<'a, K, T> {
+    primary: Map<'a, K, T>,
+    nodes: Map<'a, Vec<u8>, Node>,
+    // the nodes are length-prefixed, so they never clash with the bare namespace
+    root: Item<'a, Binary>,
+}
+
+impl<'a, K, T> MerkleMap<'a, K, T> {
+    /// Example:
+    ///
+    /// ```rust
+    /// use cw_storage_plus::MerkleMap;
+    ///
+    /// MerkleMap::<&[u8], &str>::new("members", "members__tree");
+    /// ```
+    pub const fn new(pk_namespace: &'a str, tree_namespace: &'a str) -> Self {
+        MerkleMap {
+            primary: Map::new(pk_namespace),
+            nodes: Map::new(tree_namespace),
+            root: Item::new(tree_namespace),
+        }
+    }
+
+    /// The root hash committing to the current content, all zeros for an empty map
+    pub fn root(&self, store: &dyn Storage) -> StdResult<Binary> {
+        Ok(self
+            .root
+            .may_load(store)?
+            .unwrap_or_else(|| Binary::from(EMPTY)))
+    }
+}
+
+impl<'a, K, T> MerkleMap<'a, K, T>
+where
+    T: Serialize + DeserializeOwned,
+    K: PrimaryKey<'a>,
+{
+    pub fn save(&self, store: &mut dyn Storage, k: K, data: &T) -> StdResult<()> {
+        let key_hash = sha256(&k.joined_key());
+        let leaf = MerkleLeaf {
+            key_hash: Binary::from(key_hash),
+            value_hash: Binary::from(sha256(&to_vec(data)?)),
+        };
+        self.primary.save(store, k, data)?;
+        self.insert(store, &key_hash, leaf)
+    }
+
+    pub fn remove(&self, store: &mut dyn Storage, k: K) -> StdResult<()> {
+        let key_hash = sha256(&k.joined_key());
+        self.primary.remove(store, k);
+        self.delete(store, &key_hash)
+    }
+
+    /// load will return an error if no data is set at the given key, or on parse error
+    pub fn load(&self, store: &dyn Storage, k: K) -> StdResult<T> {
+        self.primary.load(store, k)
+    }
+
+    /// may_load will parse the data stored at the key if present, returns Ok(None) if no data there.
+    /// returns an error on issues parsing
+    pub fn may_load(&self, store: &dyn Storage, k: K) -> StdResult<Option<T>> {
+        self.primary.may_load(store, k)
+    }
+
+    /// has returns true or false if any data is at this key, without parsing or interpreting the
+    /// contents.
+    pub fn has(&self, store: &dyn Storage, k: K) -> bool {
+        self.primary.has(store, k)
+    }
+
+    /// Loads the data, perform the specified action, and store the result
+    /// in the database. This is shorthand for some common sequences, which may be useful.
+    ///
+    /// If the data exists, `action(Some(value))` is called. Otherwise `action(None)` is called.
+    pub fn update<A, E>(&self, store: &mut dyn Storage, k: K, action: A) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        let input = self.may_load(store, k.clone())?;
+        let output = action(input)?;
+        self.save(store, k, &output)?;
+        Ok(output)
+    }
+
+    /// Builds a proof for the current root, proving inclusion if the key is stored,
+    /// and exclusion otherwise.
+    pub fn prove(&self, store: &dyn Storage, k: K) -> StdResult<MerkleProof> {
+        let key_hash = sha256(&k.joined_key());
+        let mut siblings = vec![];
+        let mut depth = 0;
+        loop {
+            match self.nodes.may_load(store, position(&key_hash, depth))? {
+                None => {
+                    return Ok(MerkleProof {
+                        siblings,
+                        leaf: None,
+                    })
+                }
+                Some(Node::Leaf(leaf)) => {
+                    return Ok(MerkleProof {
+                        siblings,
+                        leaf: Some(leaf),
+                    })
+                }
+                Some(Node::Branch { left, right }) => {
+                    siblings.push(if bit(&key_hash, depth) { left } else { right });
+                    depth += 1;
+                }
+            }
+        }
+    }
+
+    fn insert(&self, store: &mut dyn Storage, key_hash: &Hash, leaf: MerkleLeaf) -> StdResult<()> {
+        // walk down to the first position that is empty or holds a leaf
+        let mut path = vec![];
+        let mut depth = 0;
+        let mut current = loop {
+            match self.nodes.may_load(store, position(key_hash, depth))? {
+                Some(Node::Branch { left, right }) => {
+                    path.push(Node::Branch { left, right });
+                    depth += 1;
+                }
+                None => break self.save_node(store, key_hash, depth, &Node::Leaf(leaf))?,
+                Some(Node::Leaf(old)) if old.key_hash == leaf.key_hash => {
+                    break self.save_node(store, key_hash, depth, &Node::Leaf(leaf))?
+                }
+                Some(Node::Leaf(old)) => {
+                    // another key shares the path so far, so branch where the two diverge
+                    let old_hash = to_hash(&old.key_hash)?;
+                    let split = common_prefix_len(key_hash, &old_hash);
+                    self.save_node(store, &old_hash, split + 1, &Node::Leaf(old.clone()))?;
+                    let new = self.save_node(store, key_hash, split + 1, &Node::Leaf(leaf))?;
+                    let branch = Node::branch(bit(key_hash, split), &new, &old.hash());
+                    let mut current = self.save_node(store, key_hash, split, &branch)?;
+                    for d in (depth..split).rev() {
+                        let branch = Node::branch(bit(key_hash, d), &current, &EMPTY);
+                        current = self.save_node(store, key_hash, d, &branch)?;
+                    }
+                    break current;
+                }
+            }
+        };
+
+        // then update the hashes of all branches above
+        for (d, node) in path.into_iter().enumerate().rev() {
+            current = self.save_node(
+                store,
+                key_hash,
+                d,
+                &replace_child(node, key_hash, d, &current),
+            )?;
+        }
+        self.root.save(store, &Binary::from(current))
+    }
+
+    fn delete(&self, store: &mut dyn Storage, key_hash: &Hash) -> StdResult<()> {
+        let mut path = vec![];
+        let mut depth = 0;
+        loop {
+            match self.nodes.may_load(store, position(key_hash, depth))? {
+                Some(Node::Branch { left, right }) => {
+                    path.push(Node::Branch { left, right });
+                    depth += 1;
+                }
+                Some(Node::Leaf(leaf)) if leaf.key_hash.as_slice() == key_hash => break,
+                // not in the tree
+                _ => return Ok(()),
+            }
+        }
+        self.nodes.remove(store, position(key_hash, depth));
+
+        // every branch holds two values at least, so a branch left with a single leaf is
+        // replaced by that leaf
+        let mut removed = Removed::Nothing;
+        for (d, node) in path.into_iter().enumerate().rev() {
+            removed = match removed {
+                Removed::Hash(hash) => Removed::Hash(self.save_node(
+                    store,
+                    key_hash,
+                    d,
+                    &replace_child(node, key_hash, d, &hash),
+                )?),
+                Removed::Nothing => {
+                    let mut sibling_path = *key_hash;
+                    sibling_path[d / 8] ^= 0x80 >> (d % 8);
+                    let sibling_pos = position(&sibling_path, d + 1);
+                    match self.nodes.load(store, sibling_pos.clone())? {
+                        Node::Leaf(leaf) => {
+                            self.nodes.remove(store, sibling_pos);
+                            self.nodes.remove(store, position(key_hash, d));
+                            Removed::Leaf(leaf)
+                        }
+                        Node::Branch { .. } => Removed::Hash(self.save_node(
+                            store,
+                            key_hash,
+                            d,
+                            &replace_child(node, key_hash, d, &EMPTY),
+                        )?),
+                    }
+                }
+                Removed::Leaf(leaf) => {
+                    if sibling(&node, key_hash, d) == EMPTY {
+                        // the leaf is the only value below this branch as well
+                        self.nodes.remove(store, position(key_hash, d));
+                        Removed::Leaf(leaf)
+                    } else {
+                        let hash = self.save_node(store, key_hash, d + 1, &Node::Leaf(leaf))?;
+                        Removed::Hash(self.save_node(
+                            store,
+                            key_hash,
+                            d,
+                            &replace_child(node, key_hash, d, &hash),
+                        )?)
+                    }
+                }
+            };
+        }
+
+        let root = match removed {
+            Removed::Nothing => EMPTY,
+            Removed::Leaf(leaf) => self.save_node(store, key_hash, 0, &Node::Leaf(leaf))?,
+            Removed::Hash(hash) => hash,
+        };
+        self.root.save(store, &Binary::from(root))
+    }
+
+    /// Stores the node at the given depth on the path, and returns its hash
+    fn save_node(
+        &self,
+        store: &mut dyn Storage,
+        key_hash: &Hash,
+        depth: usize,
+        node: &Node,
+    ) -> StdResult<Hash> {
+        self.nodes.save(store, position(key_hash, depth), node)?;
+        Ok(node.hash())
+    }
+}
+
+#[cfg(feature = "iterator")]
+impl<'a, K, T> MerkleMap<'a, K, T>
+where
+    T: Serialize + DeserializeOwned,
+    K: PrimaryKey<'a> + KeyDeserialize,
+{
+    pub fn range<'c>(
+        &self,
+        store: &'c dyn Storage,
+        min: Option<Bound<'a, K>>,
+        max: Option<Bound<'a, K>>,
+        order: cosmwasm_std::Order,
+    ) -> Box<dyn Iterator<Item = StdResult<(K::Output, T)>> + 'c>
+    where
+        T: 'c,
+        K::Output: 'static,
+    {
+        self.primary.range(store, min, max, order)
+    }
+
+    pub fn keys<'c>(
+        &self,
+        store: &'c dyn Storage,
+        min: Option<Bound<'a, K>>,
+        max: Option<Bound<'a, K>>,
+        order: cosmwasm_std::Order,
+    ) -> Box<dyn Iterator<Item = StdResult<K::Output>> + 'c>
+    where
+        T: 'c,
+        K::Output: 'static,
+    {
+        self.primary.keys(store, min, max, order)
+    }
+
+    pub fn prefix(&self, p: K::Prefix) -> Prefix<K::Suffix, T, K::Suffix> {
+        self.primary.prefix(p)
+    }
+}
+
+fn sha256(data: &[u8]) -> Hash {
+    Sha256::digest(data).into()
+}
+
+fn hash_parts(prefix: u8, first: &[u8], second: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([prefix]);
+    hasher.update(first);
+    hasher.update(second);
+    hasher.finalize().into()
+}
+
+fn to_hash(data: &Binary) -> StdResult<Hash> {
+    data.to_array()
+}
+
+/// Bit `depth` of the path, `true` meaning the right child
+fn bit(path: &Hash, depth: usize) -> bool {
+    path[depth / 8] & (0x80 >> (depth % 8)) != 0
+}
+
+fn common_prefix_len(a: &Hash, b: &Hash) -> usize {
+    (0..256).find(|&d| bit(a, d) != bit(b, d)).unwrap_or(256)
+}
+
+/// Storage key of the node at `depth` on the path: the depth followed by the first `depth` bits
+fn position(path: &Hash, depth: usize) -> Vec<u8> {
+    let mut key = (depth as u16).to_be_bytes().to_vec();
+    key.extend_from_slice(&path[..depth / 8]);
+    let bits = depth % 8;
+    if bits > 0 {
+        key.push(path[depth / 8] & (0xff << (8 - bits)));
+    }
+    key
+}
+
+fn sibling(node: &Node, path: &Hash, depth: usize) -> Hash {
+    match node {
+        Node::Branch { left, right } => {
+            let sibling = if bit(path, depth) { left } else { right };
+            to_hash(sibling).unwrap_or(EMPTY)
+        }
+        Node::Leaf(_) => EMPTY,
+    }
+}
+
+fn replace_child(node: Node, path: &Hash, depth: usize, child: &[u8]) -> Node {
+    Node::branch(bit(path, depth), child, &sibling(&node, path, depth))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use cosmwasm_std::testing::MockStorage;
+
+    const MEMBERS: MerkleMap<String, u64> = MerkleMap::new("members", "members__tree");
+
+    fn verify_inclusion(store: &dyn Storage, key: &str, weight: u64) -> bool {
+        let root = MEMBERS.root(store).unwrap();
+        let proof = MEMBERS.prove(store, key.to_string()).unwrap();
+        proof.verify_inclusion(&root, key.as_bytes(), &to_vec(&weight).unwrap())
+    }
+
+    fn verify_exclusion(store: &dyn Storage, key: &str) -> bool {
+        let root = MEMBERS.root(store).unwrap();
+        let proof = MEMBERS.prove(store, key.to_string()).unwrap();
+        proof.verify_exclusion(&root, key.as_bytes())
+    }
+
+    #[test]
+    fn empty_map() {
+        let store = MockStorage::new();
+        assert_eq!(MEMBERS.root(&store).unwrap(), Binary::from(EMPTY));
+        assert!(verify_exclusion(&store, "alice"));
+        assert!(!verify_inclusion(&store, "alice", 1));
+    }
+
+    #[test]
+    fn proves_inclusion_and_exclusion() {
+        let mut store = MockStorage::new();
+        let members: Vec<_> = (0..50).map(|i| format!("member{}", i)).collect();
+        for (i, member) in members.iter().enumerate() {
+            MEMBERS
+                .save(&mut store, member.clone(), &(i as u64))
+                .unwrap();
+        }
+
+        for (i, member) in members.iter().enumerate() {
+            assert!(verify_inclusion(&store, member, i as u64));
+            assert!(!verify_inclusion(&store, member, i as u64 + 1));
+            assert!(!verify_exclusion(&store, member));
+        }
+        assert!(verify_exclusion(&store, "stranger"));
+        assert!(!verify_inclusion(&store, "stranger", 0));
+
+        // an old proof doesn't verify against the new root
+        let old_root = MEMBERS.root(&store).unwrap();
+        MEMBERS
+            .save(&mut store, "member3".to_string(), &30)
+            .unwrap();
+        let proof = MEMBERS.prove(&store, "member3".to_string()).unwrap();
+        assert!(!proof.verify_inclusion(&old_root, b"member3", &to_vec(&30u64).unwrap()));
+        assert!(verify_inclusion(&store, "member3", 30));
+    }
+
+    #[test]
+    fn root_depends_only_on_content() {
+        let mut store = MockStorage::new();
+        let mut other = MockStorage::new();
+
+        for i in 0..20u64 {
+            MEMBERS.save(&mut store, format!("m{}", i), &i).unwrap();
+        }
+        for i in (0..20u64).rev() {
+            MEMBERS.save(&mut other, format!("m{}", i), &i).unwrap();
+        }
+        // an extra value which is removed again
+        MEMBERS.save(&mut other, "temp".to_string(), &99).unwrap();
+        MEMBERS.remove(&mut other, "temp".to_string()).unwrap();
+        assert_eq!(MEMBERS.root(&store).unwrap(), MEMBERS.root(&other).unwrap());
+
+        // removing everything leaves no nodes behind
+        for i in 0..20u64 {
+            let key = format!("m{}", i);
+            MEMBERS.remove(&mut store, key.clone()).unwrap();
+            assert!(verify_exclusion(&store, &key));
+            for j in i + 1..20 {
+                assert!(verify_inclusion(&store, &format!("m{}", j), j));
+            }
+        }
+        assert_eq!(MEMBERS.root(&store).unwrap(), Binary::from(EMPTY));
+        let nodes = MEMBERS
+            .nodes
+            .keys_raw(&store, None, None, cosmwasm_std::Order::Ascending)
+            .count();
+        assert_eq!(nodes, 0);
+    }
+
+    #[test]
+    fn update_and_remove_missing() {
+        let mut store = MockStorage::new();
+        MEMBERS.save(&mut store, "alice".to_string(), &1).unwrap();
+        let root = MEMBERS.root(&store).unwrap();
+
+        MEMBERS.remove(&mut store, "bob".to_string()).unwrap();
+        assert_eq!(MEMBERS.root(&store).unwrap(), root);
+
+        let weight = MEMBERS
+            .update(&mut store, "alice".to_string(), |w| -> StdResult<_> {
+                Ok(w.unwrap_or_default() + 2)
+            })
+            .unwrap();
+        assert_eq!(weight, 3);
+        assert!(verify_inclusion(&store, "alice", 3));
+        assert_eq!(MEMBERS.load(&store, "alice".to_string()).unwrap(), 3);
+    }
+
+    #[test]
+    fn tampered_proof_fails() {
+        let mut store = MockStorage::new();
+        for i in 0..10u64 {
+            MEMBERS.save(&mut store, format!("m{}", i), &i).unwrap();
+        }
+        let root = MEMBERS.root(&store).unwrap();
+
+        let mut proof = MEMBERS.prove(&store, "m4".to_string()).unwrap();
+        assert!(!proof.siblings.is_empty());
+        // dropping the leaf doesn't turn it into an exclusion proof
+        proof.leaf = None;
+        assert!(!proof.verify_exclusion(&root, b"m4"));
+
+        // a proof for one key doesn't prove another one
+        let proof = MEMBERS.prove(&store, "m4".to_string()).unwrap();
+        assert!(!proof.verify_exclusion(&root, b"m5"));
+        assert!(!proof.verify_inclusion(&root, b"m5", &to_vec(&4u64).unwrap()));
+    }
+}
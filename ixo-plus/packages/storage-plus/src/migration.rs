@@ -0,0 +1,369 @@
+// this module requires iterator to be useful at all
+#![cfg(feature = "iterator")]
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::{to_vec, Order, StdError, StdResult, Storage};
+
+use crate::helpers::namespaces_with_key;
+use crate::{Item, Map, PrimaryKey};
+
+/// Progress of a `MapMigration`, stored next to the old map
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationProgress {
+    /// Number of entries moved to the new map so far
+    pub migrated: u64,
+    /// Whether the old map is empty
+    pub done: bool,
+}
+
+/// MapMigration moves the entries of a map to a new namespace, converting every value from the
+/// old layout `OT` to the new one `NT`, while keeping the keys.
+///
+/// Large maps can't be migrated in a single transaction, so `migrate_chunk` moves a bounded
+/// number of entries at a time and records the progress in storage. Until it is done, reads and
+/// writes must go through the migration: `may_load` falls back to the old map and converts on
+/// the fly, and `save`/`remove` drop the old entry so it isn't migrated over newer data.
+///
+/// Usually declared with the `#[versioned_state]` macro of the `macro` feature.
+pub struct MapMigration<'a, K, OT, NT> {
+    old: Map<'a, K, OT>,
+    new: Map<'a, K, NT>,
+    // stored under the bare old namespace, the map entries are length-prefixed
+    progress_key: &'a str,
+    migrate: fn(OT) -> StdResult<NT>,
+}
+
+impl<'a, K, OT, NT> MapMigration<'a, K, OT, NT> {
+    /// Create a new MapMigration
+    ///
+    /// old_namespace - namespace of the map in the previous version
+    /// new - the map in the current version, under a different namespace
+    /// migrate - converts a value from the old layout
+    ///
+    /// ## Example:
+    ///
+    /// ```rust
+    /// use cosmwasm_std::StdResult;
+    /// use cw_storage_plus::{Map, MapMigration};
+    ///
+    /// const BALANCES: Map<&str, u128> = Map::new("balances_v2");
+    /// const BALANCES_MIGRATION: MapMigration<&str, u64, u128> =
+    ///     MapMigration::new("balances", BALANCES, |old: u64| -> StdResult<u128> { Ok(old.into()) });
+    /// ```
+    pub const fn new(
+        old_namespace: &'a str,
+        new: Map<'a, K, NT>,
+        migrate: fn(OT) -> StdResult<NT>,
+    ) -> Self {
+        MapMigration {
+            old: Map::new(old_namespace),
+            new,
+            progress_key: old_namespace,
+            migrate,
+        }
+    }
+
+    /// The map in the current version
+    pub fn map(&self) -> &Map<'a, K, NT> {
+        &self.new
+    }
+
+    fn progress_item(&self) -> Item<'_, MigrationProgress> {
+        Item::new(self.progress_key)
+    }
+}
+
+impl<'a, K, OT, NT> MapMigration<'a, K, OT, NT>
+where
+    K: PrimaryKey<'a>,
+    OT: Serialize + DeserializeOwned,
+    NT: Serialize + DeserializeOwned,
+{
+    pub fn progress(&self, store: &dyn Storage) -> StdResult<MigrationProgress> {
+        Ok(self.progress_item().may_load(store)?.unwrap_or_default())
+    }
+
+    pub fn is_done(&self, store: &dyn Storage) -> StdResult<bool> {
+        Ok(self.progress(store)?.done)
+    }
+
+    /// Moves up to `limit` entries to the new map and returns the updated progress.
+    /// Entries already written to the new map are kept, as they are newer than the old ones.
+    pub fn migrate_chunk(
+        &self,
+        store: &mut dyn Storage,
+        limit: u32,
+    ) -> StdResult<MigrationProgress> {
+        if self.old.namespace() == self.new.namespace() {
+            return Err(StdError::generic_err(
+                "Migrated map must move to a new namespace",
+            ));
+        }
+        let mut progress = self.progress(store)?;
+        if progress.done {
+            return Ok(progress);
+        }
+
+        // migrated entries are removed, so the next chunk always starts from the beginning
+        let chunk = self
+            .old
+            .range_raw(store, None, None, Order::Ascending)
+            .take(limit as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (key, old) in chunk {
+            let new_key = namespaces_with_key(&[self.new.namespace()], &key);
+            if store.get(&new_key).is_none() {
+                store.set(&new_key, &to_vec(&(self.migrate)(old)?)?);
+            }
+            store.remove(&namespaces_with_key(&[self.old.namespace()], &key));
+            progress.migrated += 1;
+        }
+        progress.done = self.old.is_empty(store);
+
+        self.progress_item().save(store, &progress)?;
+        Ok(progress)
+    }
+
+    /// Loads the value from the new map, or from the old one if it wasn't migrated yet
+    pub fn may_load(&self, store: &dyn Storage, k: K) -> StdResult<Option<NT>> {
+        if let Some(value) = self.new.may_load(store, k.clone())? {
+            return Ok(Some(value));
+        }
+        self.old.may_load(store, k)?.map(self.migrate).transpose()
+    }
+
+    /// load will return an error if no data is set at the given key in either map
+    pub fn load(&self, store: &dyn Storage, k: K) -> StdResult<NT> {
+        self.may_load(store, k)?
+            .ok_or_else(|| StdError::not_found(std::any::type_name::<NT>()))
+    }
+
+    /// Saves to the new map, dropping the old entry so it isn't migrated later
+    pub fn save(&self, store: &mut dyn Storage, k: K, data: &NT) -> StdResult<()> {
+        self.old.remove(store, k.clone());
+        self.new.save(store, k, data)
+    }
+
+    pub fn remove(&self, store: &mut dyn Storage, k: K) {
+        self.old.remove(store, k.clone());
+        self.new.remove(store, k);
+    }
+}
+
+/// ItemMigration moves an item to a new key, converting it from the old layout `OT` to the new
+/// one `NT`. A single item is always migrated at once; `may_load` converts on the fly until then.
+///
+/// Usually declared with the `#[versioned_state]` macro of the `macro` feature.
+pub struct ItemMigration<'a, OT, NT> {
+    old: Item<'a, OT>,
+    new: Item<'a, NT>,
+    migrate: fn(OT) -> StdResult<NT>,
+}
+
+impl<'a, OT, NT> ItemMigration<'a, OT, NT> {
+    /// Create a new ItemMigration
+    ///
+    /// old_key - storage key of the item in the previous version
+    /// new - the item in the current version, under a different key
+    /// migrate - converts the value from the old layout
+    pub const fn new(
+        old_key: &'a str,
+        new: Item<'a, NT>,
+        migrate: fn(OT) -> StdResult<NT>,
+    ) -> Self {
+        ItemMigration {
+            old: Item::new(old_key),
+            new,
+            migrate,
+        }
+    }
+
+    /// The item in the current version
+    pub fn item(&self) -> &Item<'a, NT> {
+        &self.new
+    }
+}
+
+impl<'a, OT, NT> ItemMigration<'a, OT, NT>
+where
+    OT: Serialize + DeserializeOwned,
+    NT: Serialize + DeserializeOwned,
+{
+    /// Moves the item to its new key, unless it is already there, and returns its value
+    pub fn migrate(&self, store: &mut dyn Storage) -> StdResult<Option<NT>> {
+        if self.old.as_slice() == self.new.as_slice() {
+            return Err(StdError::generic_err(
+                "Migrated item must move to a new key",
+            ));
+        }
+        if let Some(old) = self.old.may_load(store)? {
+            if self.new.may_load(store)?.is_none() {
+                self.new.save(store, &(self.migrate)(old)?)?;
+            }
+            self.old.remove(store);
+        }
+        self.new.may_load(store)
+    }
+
+    /// Loads the value from the new key, or from the old one if it wasn't migrated yet
+    pub fn may_load(&self, store: &dyn Storage) -> StdResult<Option<NT>> {
+        if let Some(value) = self.new.may_load(store)? {
+            return Ok(Some(value));
+        }
+        self.old.may_load(store)?.map(self.migrate).transpose()
+    }
+
+    /// load will return an error if no data is set under either key
+    pub fn load(&self, store: &dyn Storage) -> StdResult<NT> {
+        self.may_load(store)?
+            .ok_or_else(|| StdError::not_found(std::any::type_name::<NT>()))
+    }
+
+    /// Saves to the new key, dropping the old value
+    pub fn save(&self, store: &mut dyn Storage, data: &NT) -> StdResult<()> {
+        self.old.remove(store);
+        self.new.save(store, data)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use cosmwasm_std::testing::MockStorage;
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct ModuleV1 {
+        address: String,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Module {
+        address: String,
+        active: bool,
+    }
+
+    fn upgrade(old: ModuleV1) -> StdResult<Module> {
+        Ok(Module {
+            address: old.address,
+            active: true,
+        })
+    }
+
+    const MODULES_V1: Map<u32, ModuleV1> = Map::new("modules");
+    const MODULES: Map<u32, Module> = Map::new("modules_v2");
+    const MODULES_MIGRATION: MapMigration<u32, ModuleV1, Module> =
+        MapMigration::new("modules", MODULES, upgrade);
+
+    fn module(n: u32) -> ModuleV1 {
+        ModuleV1 {
+            address: format!("module{}", n),
+        }
+    }
+
+    #[test]
+    fn map_migrates_in_chunks() {
+        let mut store = MockStorage::new();
+        for n in 0..5 {
+            MODULES_V1.save(&mut store, n, &module(n)).unwrap();
+        }
+
+        // reads are converted on the fly before the migration
+        assert_eq!(
+            MODULES_MIGRATION.load(&store, 3).unwrap(),
+            upgrade(module(3)).unwrap()
+        );
+        assert_eq!(MODULES.may_load(&store, 3).unwrap(), None);
+
+        let progress = MODULES_MIGRATION.migrate_chunk(&mut store, 2).unwrap();
+        assert_eq!(
+            progress,
+            MigrationProgress {
+                migrated: 2,
+                done: false
+            }
+        );
+        assert_eq!(MODULES_MIGRATION.progress(&store).unwrap(), progress);
+        assert_eq!(MODULES_V1.may_load(&store, 0).unwrap(), None);
+        assert_eq!(
+            MODULES.load(&store, 1).unwrap(),
+            upgrade(module(1)).unwrap()
+        );
+
+        // a write before the entry is migrated wins over the old value
+        let updated = Module {
+            address: "updated".to_string(),
+            active: false,
+        };
+        MODULES_MIGRATION.save(&mut store, 4, &updated).unwrap();
+        MODULES_MIGRATION.remove(&mut store, 3);
+
+        let progress = MODULES_MIGRATION.migrate_chunk(&mut store, 2).unwrap();
+        assert_eq!(
+            progress,
+            MigrationProgress {
+                migrated: 3,
+                done: true
+            }
+        );
+        assert!(MODULES_MIGRATION.is_done(&store).unwrap());
+        assert!(MODULES_V1.is_empty(&store));
+
+        let all = MODULES
+            .range(&store, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            all,
+            vec![
+                (0, upgrade(module(0)).unwrap()),
+                (1, upgrade(module(1)).unwrap()),
+                (2, upgrade(module(2)).unwrap()),
+                (4, updated),
+            ]
+        );
+
+        // further chunks are no-ops
+        assert_eq!(
+            MODULES_MIGRATION.migrate_chunk(&mut store, 2).unwrap(),
+            progress
+        );
+    }
+
+    #[test]
+    fn map_migration_needs_new_namespace() {
+        const IN_PLACE: MapMigration<u32, ModuleV1, Module> =
+            MapMigration::new("modules", Map::new("modules"), upgrade);
+        let mut store = MockStorage::new();
+        IN_PLACE.migrate_chunk(&mut store, 10).unwrap_err();
+    }
+
+    #[test]
+    fn item_migrates_at_once() {
+        const CONFIG: Item<Module> = Item::new("config_v2");
+        const CONFIG_V1: Item<ModuleV1> = Item::new("config");
+        const CONFIG_MIGRATION: ItemMigration<ModuleV1, Module> =
+            ItemMigration::new("config", CONFIG, upgrade);
+
+        let mut store = MockStorage::new();
+        assert_eq!(CONFIG_MIGRATION.migrate(&mut store).unwrap(), None);
+        CONFIG_MIGRATION.load(&store).unwrap_err();
+
+        CONFIG_V1.save(&mut store, &module(1)).unwrap();
+        let expected = upgrade(module(1)).unwrap();
+        assert_eq!(CONFIG_MIGRATION.load(&store).unwrap(), expected);
+        assert_eq!(
+            CONFIG_MIGRATION.migrate(&mut store).unwrap(),
+            Some(expected.clone())
+        );
+        assert_eq!(CONFIG.load(&store).unwrap(), expected);
+        assert_eq!(CONFIG_V1.may_load(&store).unwrap(), None);
+        // migrating again is harmless
+        assert_eq!(
+            CONFIG_MIGRATION.migrate(&mut store).unwrap(),
+            Some(expected)
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use std::cmp::Ordering;
+
+use cosmwasm_std::{StdResult, Storage};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::deque::{Deque, DequeIter};
+
+/// A ring buffer keeps the last `capacity` items pushed to it. Once it is full, every push
+/// overwrites the oldest item, which makes it a good fit for "last N observations" such as
+/// price windows, recent trades or audit logs.
+///
+/// It is stored exactly like a [`Deque`] under the same prefix, so a `Deque` can be turned into a
+/// `RingBuffer` (and back) without migrating any data. If more than `capacity` items are stored,
+/// e.g. after lowering the capacity, the oldest ones are dropped on the next push.
+pub struct RingBuffer<'a, T> {
+    deque: Deque<'a, T>,
+    capacity: u32,
+}
+
+impl<'a, T> RingBuffer<'a, T> {
+    /// Creates a ring buffer holding at most `capacity` items, which must not be zero.
+    pub const fn new(prefix: &'a str, capacity: u32) -> Self {
+        assert!(capacity > 0, "ring buffer capacity must not be zero");
+        Self {
+            deque: Deque::new(prefix),
+            capacity,
+        }
+    }
+
+    pub const fn capacity(&self) -> u32 {
+        self.capacity
+    }
+}
+
+impl<'a, T: Serialize + DeserializeOwned> RingBuffer<'a, T> {
+    /// Adds the given value to the end of the buffer, dropping the oldest values if it is full
+    pub fn push_back(&self, storage: &mut dyn Storage, value: &T) -> StdResult<()> {
+        let len = self.deque.len(storage)?;
+        if len >= self.capacity {
+            // the dropped values are never read, so don't deserialize them
+            self.deque
+                .truncate_front(storage, len - self.capacity + 1)?;
+        }
+        self.deque.push_back(storage, value)
+    }
+
+    /// Removes the newest element of the buffer and returns it
+    pub fn pop_back(&self, storage: &mut dyn Storage) -> StdResult<Option<T>> {
+        self.deque.pop_back(storage)
+    }
+
+    /// Removes the oldest element of the buffer and returns it
+    pub fn pop_front(&self, storage: &mut dyn Storage) -> StdResult<Option<T>> {
+        self.deque.pop_front(storage)
+    }
+
+    /// Returns the oldest element of the buffer without removing it
+    pub fn front(&self, storage: &dyn Storage) -> StdResult<Option<T>> {
+        self.deque.front(storage)
+    }
+
+    /// Returns the newest element of the buffer without removing it
+    pub fn back(&self, storage: &dyn Storage) -> StdResult<Option<T>> {
+        self.deque.back(storage)
+    }
+
+    /// Gets the number of elements in the buffer, never more than its capacity after a push.
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self, storage: &dyn Storage) -> StdResult<u32> {
+        self.deque.len(storage)
+    }
+
+    /// Returns `true` if the buffer contains no elements.
+    pub fn is_empty(&self, storage: &dyn Storage) -> StdResult<bool> {
+        self.deque.is_empty(storage)
+    }
+
+    /// Returns `true` if the next push will drop the oldest element.
+    pub fn is_full(&self, storage: &dyn Storage) -> StdResult<bool> {
+        Ok(self.len(storage)? >= self.capacity)
+    }
+
+    /// Returns the value at the given position, counting from the oldest element,
+    /// or `None` if the index is out of bounds
+    pub fn get(&self, storage: &dyn Storage, pos: u32) -> StdResult<Option<T>> {
+        self.deque.get(storage, pos)
+    }
+
+    /// Iterates from the oldest to the newest element. Use `rev()` to start from the newest.
+    pub fn iter(&self, storage: &'a dyn Storage) -> StdResult<DequeIter<'_, T>> {
+        self.deque.iter(storage)
+    }
+
+    /// Binary searches the buffer with a comparator function, like `slice::binary_search_by`.
+    ///
+    /// The elements must be sorted by the compared property, which holds for any field that
+    /// grows monotonically with every push, like a timestamp or a sequence number.
+    /// Returns `Ok` with the position of a matching element, or `Err` with the position where
+    /// a matching element could be inserted while keeping the order.
+    /// It loads `O(log n)` elements.
+    pub fn binary_search_by<F>(
+        &self,
+        storage: &dyn Storage,
+        mut f: F,
+    ) -> StdResult<Result<u32, u32>>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        let (mut low, mut high) = (0, self.len(storage)?);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            // within bounds `get` returns either a value or an error for a missing one
+            let value = self.get(storage, mid)?.unwrap();
+            match f(&value) {
+                Ordering::Less => low = mid + 1,
+                Ordering::Greater => high = mid,
+                Ordering::Equal => return Ok(Ok(mid)),
+            }
+        }
+        Ok(Err(low))
+    }
+
+    /// Binary searches the buffer for `key`, extracted from each element by `f`.
+    /// See [`RingBuffer::binary_search_by`].
+    pub fn binary_search_by_key<K, F>(
+        &self,
+        storage: &dyn Storage,
+        key: &K,
+        mut f: F,
+    ) -> StdResult<Result<u32, u32>>
+    where
+        K: Ord,
+        F: FnMut(&T) -> K,
+    {
+        self.binary_search_by(storage, |value| f(value).cmp(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use cosmwasm_std::testing::MockStorage;
+    use serde::Deserialize;
+
+    #[test]
+    fn push_overwrites_oldest() {
+        const LAST: RingBuffer<u32> = RingBuffer::new("last", 3);
+        let mut store = MockStorage::new();
+
+        assert!(LAST.is_empty(&store).unwrap());
+        for i in 1..=3 {
+            LAST.push_back(&mut store, &i).unwrap();
+        }
+        assert!(LAST.is_full(&store).unwrap());
+        assert_eq!(LAST.front(&store).unwrap(), Some(1));
+
+        LAST.push_back(&mut store, &4).unwrap();
+        LAST.push_back(&mut store, &5).unwrap();
+        assert_eq!(LAST.len(&store).unwrap(), 3);
+        assert_eq!(LAST.front(&store).unwrap(), Some(3));
+        assert_eq!(LAST.back(&store).unwrap(), Some(5));
+        assert_eq!(LAST.get(&store, 1).unwrap(), Some(4));
+        assert_eq!(LAST.get(&store, 3).unwrap(), None);
+
+        let items: StdResult<Vec<_>> = LAST.iter(&store).unwrap().collect();
+        assert_eq!(items.unwrap(), vec![3, 4, 5]);
+        let items: StdResult<Vec<_>> = LAST.iter(&store).unwrap().rev().collect();
+        assert_eq!(items.unwrap(), vec![5, 4, 3]);
+
+        assert_eq!(LAST.pop_back(&mut store).unwrap(), Some(5));
+        assert_eq!(LAST.pop_front(&mut store).unwrap(), Some(3));
+        assert!(!LAST.is_full(&store).unwrap());
+    }
+
+    #[test]
+    fn shares_storage_with_deque() {
+        const QUEUE: Deque<u32> = Deque::new("shared");
+        const WINDOW: RingBuffer<u32> = RingBuffer::new("shared", 2);
+        let mut store = MockStorage::new();
+
+        for i in 1..=4 {
+            QUEUE.push_back(&mut store, &i).unwrap();
+        }
+        // more than the capacity is readable, and trimmed on the next push
+        assert_eq!(WINDOW.len(&store).unwrap(), 4);
+        WINDOW.push_back(&mut store, &5).unwrap();
+        assert_eq!(WINDOW.len(&store).unwrap(), 2);
+
+        let items: StdResult<Vec<_>> = QUEUE.iter(&store).unwrap().collect();
+        assert_eq!(items.unwrap(), vec![4, 5]);
+    }
+
+    #[test]
+    fn dropped_items_are_not_read() {
+        const OLD: Deque<String> = Deque::new("changed");
+        const NEW: RingBuffer<u32> = RingBuffer::new("changed", 1);
+        let mut store = MockStorage::new();
+
+        for name in ["alice", "bob", "carol"] {
+            OLD.push_back(&mut store, &name.to_string()).unwrap();
+        }
+        // the stored strings can't be read as numbers, but are dropped anyway
+        NEW.push_back(&mut store, &7).unwrap();
+        let items: StdResult<Vec<_>> = NEW.iter(&store).unwrap().collect();
+        assert_eq!(items.unwrap(), vec![7]);
+        // the two meta keys and the remaining item
+        assert_eq!(
+            store
+                .range(None, None, cosmwasm_std::Order::Ascending)
+                .count(),
+            3
+        );
+    }
+
+    #[test]
+    fn binary_search() {
+        #[derive(Serialize, Deserialize, Debug, PartialEq)]
+        struct Observation {
+            time: u64,
+            price: u128,
+        }
+
+        const PRICES: RingBuffer<Observation> = RingBuffer::new("prices", 4);
+        let mut store = MockStorage::new();
+
+        assert_eq!(
+            PRICES
+                .binary_search_by_key(&store, &10, |o| o.time)
+                .unwrap(),
+            Err(0)
+        );
+
+        // wraps around, so the oldest element is no longer stored at position 0
+        for (time, price) in [(5, 1), (10, 2), (20, 3), (30, 4), (40, 5), (50, 6)] {
+            PRICES
+                .push_back(&mut store, &Observation { time, price })
+                .unwrap();
+        }
+
+        let search = |time| {
+            PRICES
+                .binary_search_by_key(&store, &time, |o| o.time)
+                .unwrap()
+        };
+        assert_eq!(search(20), Ok(0));
+        assert_eq!(search(50), Ok(3));
+        assert_eq!(search(10), Err(0));
+        assert_eq!(search(35), Err(2));
+        assert_eq!(search(60), Err(4));
+
+        // the latest observation at or before a given time
+        let pos = match search(45) {
+            Ok(pos) => pos,
+            Err(pos) => pos - 1,
+        };
+        assert_eq!(PRICES.get(&store, pos).unwrap().unwrap().price, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "ring buffer capacity must not be zero")]
+    fn zero_capacity_panics() {
+        RingBuffer::<u32>::new("empty", 0);
+    }
+}
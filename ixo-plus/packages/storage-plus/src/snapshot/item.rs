@@ -1,9 +1,9 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use cosmwasm_std::{StdError, StdResult, Storage};
+use cosmwasm_std::{StdError, StdResult, Storage, Timestamp};
 
-use crate::snapshot::{ChangeSet, Snapshot};
+use crate::snapshot::{ChangeSet, Clock, Retention, Snapshot};
 use crate::{Item, Map, Strategy};
 
 /// Item that maintains a snapshot of one or more checkpoints.
@@ -40,6 +40,18 @@ impl<'a, T> SnapshotItem<'a, T> {
         }
     }
 
+    /// Sets how long the changelog is kept, `Retention::Forever` by default
+    pub const fn with_retention(mut self, retention: Retention) -> Self {
+        self.snapshots = self.snapshots.with_retention(retention);
+        self
+    }
+
+    /// Sets what the snapshot is keyed by, `Clock::Height` by default
+    pub const fn with_clock(mut self, clock: Clock) -> Self {
+        self.snapshots = self.snapshots.with_clock(clock);
+        self
+    }
+
     pub fn add_checkpoint(&self, store: &mut dyn Storage, height: u64) -> StdResult<()> {
         self.snapshots.add_checkpoint(store, height)
     }
@@ -111,6 +123,18 @@ where
         self.snapshots.assert_checkpointed(store, height)
     }
 
+    /// Height below which the changelog was pruned and can't be queried anymore
+    pub fn pruned_before(&self, store: &dyn Storage) -> StdResult<u64> {
+        self.snapshots.pruned_before(store)
+    }
+
+    /// Removes up to `limit` changelog entries below `before_height`, resuming where the
+    /// previous call stopped.
+    /// Heights below `before_height` can't be queried anymore once this is called.
+    pub fn prune(&self, store: &mut dyn Storage, before_height: u64, limit: u32) -> StdResult<u32> {
+        self.snapshots.prune(store, before_height, limit)
+    }
+
     /// Loads the data, perform the specified action, and store the result in the database.
     /// This is a shorthand for some common sequences, which may be useful.
     ///
@@ -129,6 +153,68 @@ where
     }
 }
 
+// The same API keyed by block time, for items built `with_clock(Clock::Timestamp)`.
+// These return an error on items keyed by height.
+impl<'a, T> SnapshotItem<'a, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+{
+    pub fn add_checkpoint_at_time(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.add_checkpoint(store, self.snapshots.time_point(time)?)
+    }
+
+    pub fn remove_checkpoint_at_time(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.remove_checkpoint(store, self.snapshots.time_point(time)?)
+    }
+
+    pub fn save_at_time(
+        &self,
+        store: &mut dyn Storage,
+        data: &T,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.save(store, data, self.snapshots.time_point(time)?)
+    }
+
+    pub fn remove_at_time(&self, store: &mut dyn Storage, time: Timestamp) -> StdResult<()> {
+        self.remove(store, self.snapshots.time_point(time)?)
+    }
+
+    pub fn update_at_time<A, E>(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+        action: A,
+    ) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        self.update(store, self.snapshots.time_point(time)?, action)
+    }
+
+    /// Value at the beginning of the block with the given time
+    pub fn may_load_at_time(&self, store: &dyn Storage, time: Timestamp) -> StdResult<Option<T>> {
+        self.may_load_at_height(store, self.snapshots.time_point(time)?)
+    }
+
+    pub fn assert_checkpointed_at_time(
+        &self,
+        store: &dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.assert_checkpointed(store, self.snapshots.time_point(time)?)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,6 +237,13 @@ mod tests {
         "select__change",
         Strategy::Selected,
     );
+    const RETAINED: TestItem = SnapshotItem::new(
+        "retained",
+        "retained__check",
+        "retained__change",
+        Strategy::EveryBlock,
+    )
+    .with_retention(Retention::LastBlocks(2));
 
     // Fills an item (u64) with the following writes:
     // 1: 5
@@ -322,4 +415,62 @@ mod tests {
         assert_eq!(1, all.len());
         assert_eq!(all, vec![(4, ChangeSet { old: Some(8) }),]);
     }
+
+    #[test]
+    fn retention_and_prune() {
+        let mut storage = MockStorage::new();
+        init_data(&RETAINED, &mut storage);
+        assert_final_value(&RETAINED, &storage);
+
+        // written at 5 and keeping 2 blocks, so 1 and 2 are gone
+        assert_eq!(RETAINED.pruned_before(&storage).unwrap(), 3);
+        assert_value_at_height(&RETAINED, &storage, 3, VALUE_START_3);
+        assert_value_at_height(&RETAINED, &storage, 5, VALUE_START_5);
+        assert_eq!(
+            RETAINED.may_load_at_height(&storage, 2),
+            Err(StdError::generic_err(
+                "Snapshot at height 2 was pruned, the oldest available height is 3"
+            ))
+        );
+        let heights: Vec<_> = RETAINED
+            .changelog()
+            .keys(&storage, None, None, cosmwasm_std::Order::Ascending)
+            .collect::<StdResult<_>>()
+            .unwrap();
+        assert_eq!(heights, vec![3, 4, 5]);
+
+        assert_eq!(RETAINED.prune(&mut storage, 5, 10).unwrap(), 2);
+        assert!(RETAINED.assert_checkpointed(&storage, 4).is_err());
+        assert_value_at_height(&RETAINED, &storage, 5, VALUE_START_5);
+    }
+
+    #[test]
+    fn timestamp_clock() {
+        const BY_TIME: TestItem = SnapshotItem::new(
+            "timed",
+            "timed__check",
+            "timed__change",
+            Strategy::EveryBlock,
+        )
+        .with_clock(Clock::Timestamp);
+
+        let mut storage = MockStorage::new();
+        let at = |seconds| Timestamp::from_seconds(seconds);
+        BY_TIME.save_at_time(&mut storage, &5, at(10)).unwrap();
+        BY_TIME
+            .update_at_time(&mut storage, at(20), |v| -> StdResult<u64> {
+                Ok(v.unwrap_or_default() + 1)
+            })
+            .unwrap();
+
+        assert_eq!(BY_TIME.may_load_at_time(&storage, at(10)), Ok(None));
+        assert_eq!(BY_TIME.may_load_at_time(&storage, at(15)), Ok(Some(5)));
+        assert_eq!(BY_TIME.may_load_at_time(&storage, at(25)), Ok(Some(6)));
+
+        BY_TIME.remove_at_time(&mut storage, at(30)).unwrap();
+        assert_eq!(BY_TIME.may_load_at_time(&storage, at(30)), Ok(Some(6)));
+        assert_eq!(BY_TIME.may_load(&storage), Ok(None));
+
+        EVERY.may_load_at_time(&storage, at(10)).unwrap_err();
+    }
 }
@@ -1,7 +1,7 @@
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
-use cosmwasm_std::{StdError, StdResult, Storage};
+use cosmwasm_std::{StdError, StdResult, Storage, Timestamp};
 
 use crate::bound::PrefixBound;
 use crate::de::KeyDeserialize;
@@ -10,7 +10,7 @@ use crate::keys::PrimaryKey;
 use crate::map::Map;
 use crate::path::Path;
 use crate::prefix::{namespaced_prefix_range, Prefix};
-use crate::snapshot::{ChangeSet, Snapshot};
+use crate::snapshot::{ChangeSet, Clock, Retention, Snapshot};
 use crate::{Bound, Prefixer, Strategy};
 
 /// Map that maintains a snapshots of one or more checkpoints.
@@ -46,6 +46,50 @@ impl<'a, K, T> SnapshotMap<'a, K, T> {
         }
     }
 
+    /// Sets how long the changelog is kept, `Retention::Forever` by default.
+    ///
+    /// ```rust
+    /// use cw_storage_plus::{Retention, SnapshotMap, Strategy};
+    ///
+    /// const BALANCES: SnapshotMap<&str, u64> = SnapshotMap::new(
+    ///     "balances",
+    ///     "balances__check",
+    ///     "balances__change",
+    ///     Strategy::EveryBlock,
+    /// )
+    /// .with_retention(Retention::LastBlocks(100_000));
+    /// ```
+    pub const fn with_retention(mut self, retention: Retention) -> Self {
+        self.snapshots = self.snapshots.with_retention(retention);
+        self
+    }
+
+    /// Sets what the snapshot is keyed by, `Clock::Height` by default.
+    ///
+    /// ```rust
+    /// use cosmwasm_std::Timestamp;
+    /// use cosmwasm_std::testing::MockStorage;
+    /// use cw_storage_plus::{Clock, SnapshotMap, Strategy};
+    ///
+    /// const POWER: SnapshotMap<&str, u64> = SnapshotMap::new(
+    ///     "power",
+    ///     "power__check",
+    ///     "power__change",
+    ///     Strategy::EveryBlock,
+    /// )
+    /// .with_clock(Clock::Timestamp);
+    ///
+    /// let mut store = MockStorage::new();
+    /// POWER.save_at_time(&mut store, "alice", &5, Timestamp::from_seconds(100)).unwrap();
+    /// POWER.save_at_time(&mut store, "alice", &7, Timestamp::from_seconds(200)).unwrap();
+    /// let power = POWER.may_load_at_time(&store, "alice", Timestamp::from_seconds(150));
+    /// assert_eq!(power, Ok(Some(5)));
+    /// ```
+    pub const fn with_clock(mut self, clock: Clock) -> Self {
+        self.snapshots = self.snapshots.with_clock(clock);
+        self
+    }
+
     pub fn changelog(&self) -> &Map<'a, (K, u64), ChangeSet<T>> {
         &self.snapshots.changelog
     }
@@ -137,6 +181,18 @@ where
         self.snapshots.assert_checkpointed(store, height)
     }
 
+    /// Height below which the changelog was pruned and can't be queried anymore
+    pub fn pruned_before(&self, store: &dyn Storage) -> StdResult<u64> {
+        self.snapshots.pruned_before(store)
+    }
+
+    /// Removes up to `limit` changelog entries below `before_height`, resuming where the
+    /// previous call stopped, so it is safe to call from regular executes.
+    /// Heights below `before_height` can't be queried anymore once this is called.
+    pub fn prune(&self, store: &mut dyn Storage, before_height: u64, limit: u32) -> StdResult<u32> {
+        self.snapshots.prune(store, before_height, limit)
+    }
+
     /// Loads the data, perform the specified action, and store the result
     /// in the database. This is shorthand for some common sequences, which may be useful.
     ///
@@ -161,6 +217,80 @@ where
     }
 }
 
+// The same API keyed by block time, for maps built `with_clock(Clock::Timestamp)`.
+// These return an error on maps keyed by height.
+impl<'a, K, T> SnapshotMap<'a, K, T>
+where
+    T: Serialize + DeserializeOwned + Clone,
+    K: PrimaryKey<'a> + Prefixer<'a> + KeyDeserialize,
+{
+    pub fn add_checkpoint_at_time(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.add_checkpoint(store, self.snapshots.time_point(time)?)
+    }
+
+    pub fn remove_checkpoint_at_time(
+        &self,
+        store: &mut dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.remove_checkpoint(store, self.snapshots.time_point(time)?)
+    }
+
+    pub fn save_at_time(
+        &self,
+        store: &mut dyn Storage,
+        k: K,
+        data: &T,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.save(store, k, data, self.snapshots.time_point(time)?)
+    }
+
+    pub fn remove_at_time(&self, store: &mut dyn Storage, k: K, time: Timestamp) -> StdResult<()> {
+        self.remove(store, k, self.snapshots.time_point(time)?)
+    }
+
+    pub fn update_at_time<A, E>(
+        &self,
+        store: &mut dyn Storage,
+        k: K,
+        time: Timestamp,
+        action: A,
+    ) -> Result<T, E>
+    where
+        A: FnOnce(Option<T>) -> Result<T, E>,
+        E: From<StdError>,
+    {
+        self.update(store, k, self.snapshots.time_point(time)?, action)
+    }
+
+    /// Value of the key at the beginning of the block with the given time
+    pub fn may_load_at_time(
+        &self,
+        store: &dyn Storage,
+        k: K,
+        time: Timestamp,
+    ) -> StdResult<Option<T>> {
+        self.may_load_at_height(store, k, self.snapshots.time_point(time)?)
+    }
+
+    pub fn assert_checkpointed_at_time(
+        &self,
+        store: &dyn Storage,
+        time: Timestamp,
+    ) -> StdResult<()> {
+        self.assert_checkpointed(store, self.snapshots.time_point(time)?)
+    }
+
+    pub(crate) fn time_point(&self, time: Timestamp) -> StdResult<u64> {
+        self.snapshots.time_point(time)
+    }
+}
+
 // short-cut for simple keys, rather than .prefix(()).range_raw(...)
 impl<'a, K, T> SnapshotMap<'a, K, T>
 where
@@ -271,6 +401,7 @@ where
 mod tests {
     use super::*;
     use cosmwasm_std::testing::MockStorage;
+    use cosmwasm_std::Order;
 
     type TestMap = SnapshotMap<'static, &'static str, u64>;
     type TestMapCompositeKey = SnapshotMap<'static, (&'static str, &'static str), u64>;
@@ -295,6 +426,13 @@ mod tests {
         "select__change",
         Strategy::Selected,
     );
+    const RETAINED: TestMap = SnapshotMap::new(
+        "retained",
+        "retained__check",
+        "retained__change",
+        Strategy::EveryBlock,
+    )
+    .with_retention(Retention::LastBlocks(1));
 
     // Fills a map &[u8] -> u64 with the following writes:
     // 1: A = 5
@@ -641,4 +779,170 @@ mod tests {
             ]
         );
     }
+
+    fn changelog_entries(map: &TestMap, storage: &dyn Storage) -> Vec<(String, u64)> {
+        map.changelog()
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn prune_removes_changelog_incrementally() {
+        let mut storage = MockStorage::new();
+        init_data(&EVERY, &mut storage);
+        assert_eq!(changelog_entries(&EVERY, &storage).len(), 8);
+        assert_eq!(EVERY.pruned_before(&storage).unwrap(), 0);
+
+        // looks at A@1, A@3 and A@5 only
+        assert_eq!(EVERY.prune(&mut storage, 5, 3).unwrap(), 2);
+        // heights below the watermark fail right away, even if not all of them are swept
+        assert_eq!(EVERY.pruned_before(&storage).unwrap(), 5);
+        assert_eq!(
+            EVERY.may_load_at_height(&storage, "B", 3),
+            Err(StdError::generic_err(
+                "Snapshot at height 3 was pruned, the oldest available height is 5"
+            ))
+        );
+        // B@2, B@4 and C@3
+        assert_eq!(EVERY.prune(&mut storage, 5, 3).unwrap(), 3);
+        // C@4 and D@5, which is kept
+        assert_eq!(EVERY.prune(&mut storage, 5, 3).unwrap(), 1);
+        // starts over, nothing left to remove
+        assert_eq!(EVERY.prune(&mut storage, 5, 3).unwrap(), 0);
+
+        assert_eq!(
+            changelog_entries(&EVERY, &storage),
+            vec![("A".to_string(), 5), ("D".to_string(), 5)]
+        );
+        assert_final_values(&EVERY, &storage);
+        assert_values_at_height(&EVERY, &storage, 5, VALUES_START_5);
+        assert_missing_checkpoint(&EVERY, &storage, 3);
+
+        // an older watermark doesn't make pruned heights queryable again
+        EVERY.prune(&mut storage, 1, 10).unwrap();
+        assert_eq!(EVERY.pruned_before(&storage).unwrap(), 5);
+    }
+
+    #[test]
+    fn retention_prunes_written_keys() {
+        let mut storage = MockStorage::new();
+        init_data(&RETAINED, &mut storage);
+        assert_final_values(&RETAINED, &storage);
+
+        // the last write happened at 5, so 4 is the oldest queryable height
+        assert_eq!(RETAINED.pruned_before(&storage).unwrap(), 4);
+        assert_values_at_height(&RETAINED, &storage, 5, VALUES_START_5);
+        assert_values_at_height(
+            &RETAINED,
+            &storage,
+            4,
+            &[("A", Some(8)), ("B", Some(7)), ("C", Some(1)), ("D", None)],
+        );
+        assert_missing_checkpoint(&RETAINED, &storage, 3);
+
+        // A@1 and A@3 are gone as A was written at 5, B@2 as B was written at 4.
+        // C@3 is kept, as C was last written at 4
+        assert_eq!(
+            changelog_entries(&RETAINED, &storage),
+            vec![
+                ("A".to_string(), 5),
+                ("B".to_string(), 4),
+                ("C".to_string(), 3),
+                ("C".to_string(), 4),
+                ("D".to_string(), 5)
+            ]
+        );
+        // prune collects whatever the writes left behind
+        assert_eq!(RETAINED.prune(&mut storage, 4, 10).unwrap(), 1);
+    }
+
+    #[test]
+    fn timestamp_clock() {
+        const BY_TIME: TestMap =
+            SnapshotMap::new("timed", "timed__check", "timed__change", Strategy::Selected)
+                .with_clock(Clock::Timestamp);
+
+        let mut storage = MockStorage::new();
+        let at = |seconds| Timestamp::from_seconds(seconds);
+        BY_TIME.save_at_time(&mut storage, "A", &5, at(10)).unwrap();
+        BY_TIME
+            .add_checkpoint_at_time(&mut storage, at(20))
+            .unwrap();
+        BY_TIME.save_at_time(&mut storage, "A", &8, at(20)).unwrap();
+        BY_TIME.save_at_time(&mut storage, "B", &1, at(20)).unwrap();
+        BY_TIME.remove_at_time(&mut storage, "A", at(30)).unwrap();
+
+        assert_eq!(BY_TIME.may_load_at_time(&storage, "A", at(20)), Ok(Some(5)));
+        assert_eq!(BY_TIME.may_load_at_time(&storage, "B", at(20)), Ok(None));
+        assert_eq!(BY_TIME.may_load(&storage, "A"), Ok(None));
+        // only the checkpointed time is queryable
+        BY_TIME
+            .assert_checkpointed_at_time(&storage, at(20))
+            .unwrap();
+        BY_TIME.may_load_at_time(&storage, "A", at(30)).unwrap_err();
+        // points in time are nanoseconds
+        assert_eq!(
+            BY_TIME.may_load_at_height(&storage, "A", at(20).nanos()),
+            Ok(Some(5))
+        );
+
+        BY_TIME
+            .remove_checkpoint_at_time(&mut storage, at(20))
+            .unwrap();
+        BY_TIME
+            .assert_checkpointed_at_time(&storage, at(20))
+            .unwrap_err();
+
+        // the height keyed maps don't take times
+        EVERY
+            .save_at_time(&mut storage, "A", &5, at(10))
+            .unwrap_err();
+    }
+
+    #[test]
+    fn retention_by_time() {
+        const BY_TIME: TestMap = SnapshotMap::new(
+            "timed",
+            "timed__check",
+            "timed__change",
+            Strategy::EveryBlock,
+        )
+        .with_clock(Clock::Timestamp)
+        .with_retention(Retention::LastSeconds(15));
+
+        let mut storage = MockStorage::new();
+        let at = |seconds| Timestamp::from_seconds(seconds);
+        BY_TIME.save_at_time(&mut storage, "A", &5, at(10)).unwrap();
+        BY_TIME.save_at_time(&mut storage, "A", &8, at(20)).unwrap();
+        assert_eq!(BY_TIME.may_load_at_time(&storage, "A", at(15)), Ok(Some(5)));
+
+        // the change at 10s is older than 15 seconds when writing at 30s
+        BY_TIME
+            .save_at_time(&mut storage, "A", &13, at(30))
+            .unwrap();
+        BY_TIME.may_load_at_time(&storage, "A", at(12)).unwrap_err();
+        assert_eq!(BY_TIME.may_load_at_time(&storage, "A", at(25)), Ok(Some(8)));
+
+        // block counts are not mixed up with nanoseconds
+        const MISMATCHED: TestMap = SnapshotMap::new(
+            "mismatched",
+            "mismatched__check",
+            "mismatched__change",
+            Strategy::EveryBlock,
+        )
+        .with_clock(Clock::Timestamp)
+        .with_retention(Retention::LastBlocks(100));
+        MISMATCHED
+            .save_at_time(&mut storage, "A", &5, at(10))
+            .unwrap_err();
+        const BY_HEIGHT: TestMap = SnapshotMap::new(
+            "by_height",
+            "by_height__check",
+            "by_height__change",
+            Strategy::EveryBlock,
+        )
+        .with_retention(Retention::LastSeconds(100));
+        BY_HEIGHT.save(&mut storage, "A", &5, 1).unwrap_err();
+    }
 }
@@ -7,15 +7,18 @@ pub use map::SnapshotMap;
 
 use crate::bound::Bound;
 use crate::de::KeyDeserialize;
-use crate::{Map, Prefixer, PrimaryKey};
-use cosmwasm_std::{Order, StdError, StdResult, Storage};
+use crate::helpers::namespaces_with_key;
+use crate::{Item, Map, Prefixer, PrimaryKey};
+use cosmwasm_std::{Binary, Order, StdError, StdResult, Storage, Timestamp};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
 
 /// Structure holding a map of checkpoints composited from
 /// height (as u64) and counter of how many times it has
 /// been checkpointed (as u32).
 /// Stores all changes in changelog.
+/// Heights are points of the snapshot's `Clock`, which may as well be block times.
 #[derive(Debug, Clone)]
 pub(crate) struct Snapshot<'a, K, T> {
     checkpoints: Map<'a, u64, u32>,
@@ -26,6 +29,14 @@ pub(crate) struct Snapshot<'a, K, T> {
 
     // How aggressive we are about checkpointing all data
     strategy: Strategy,
+
+    // How long the changelog is kept around
+    retention: Retention,
+
+    // What the u64 points in time are measured in
+    clock: Clock,
+
+    changelog_namespace: &'a str,
 }
 
 impl<'a, K, T> Snapshot<'a, K, T> {
@@ -38,9 +49,38 @@ impl<'a, K, T> Snapshot<'a, K, T> {
             checkpoints: Map::new(checkpoints),
             changelog: Map::new(changelog),
             strategy,
+            retention: Retention::Forever,
+            clock: Clock::Height,
+            changelog_namespace: changelog,
         }
     }
 
+    pub const fn with_clock(mut self, clock: Clock) -> Snapshot<'a, K, T> {
+        self.clock = clock;
+        self
+    }
+
+    /// Converts a block time into a point of a `Clock::Timestamp` snapshot
+    pub fn time_point(&self, time: Timestamp) -> StdResult<u64> {
+        match self.clock {
+            Clock::Timestamp => Ok(time.nanos()),
+            Clock::Height => Err(StdError::generic_err(
+                "Snapshot is keyed by block height, not by time",
+            )),
+        }
+    }
+
+    pub const fn with_retention(mut self, retention: Retention) -> Snapshot<'a, K, T> {
+        self.retention = retention;
+        self
+    }
+
+    // Progress of pruning, stored under the bare changelog namespace. Changelog entries are
+    // length-prefixed, so they can't clash with it
+    fn pruning(&self) -> Item<'_, Pruning> {
+        Item::new(self.changelog_namespace)
+    }
+
     pub fn add_checkpoint(&self, store: &mut dyn Storage, height: u64) -> StdResult<()> {
         self.checkpoints
             .update::<_, StdError>(store, height, |count| Ok(count.unwrap_or_default() + 1))?;
@@ -101,8 +141,17 @@ where
         Ok(false)
     }
 
-    // If there is no checkpoint for that height, then we return StdError::NotFound
+    // If there is no checkpoint for that height, then we return StdError::NotFound.
+    // If the changelog for that height was pruned, we return StdError::GenericErr
     pub fn assert_checkpointed(&self, store: &dyn Storage, height: u64) -> StdResult<()> {
+        let pruned_before = self.pruned_before(store)?;
+        if height < pruned_before {
+            return Err(StdError::generic_err(format!(
+                "Snapshot at height {} was pruned, the oldest available height is {}",
+                height, pruned_before
+            )));
+        }
+
         let has = match self.strategy {
             Strategy::EveryBlock => true,
             Strategy::Never => false,
@@ -126,7 +175,88 @@ where
         old: Option<T>,
     ) -> StdResult<()> {
         self.changelog
-            .save(store, (key, height), &ChangeSet { old })
+            .save(store, (key.clone(), height), &ChangeSet { old })?;
+        match (self.retention, self.clock) {
+            (Retention::Forever, _) => Ok(()),
+            (Retention::LastBlocks(blocks), Clock::Height) => {
+                self.prune_key(store, key, height.saturating_sub(blocks))
+            }
+            (Retention::LastSeconds(seconds), Clock::Timestamp) => {
+                let nanos = seconds.saturating_mul(1_000_000_000);
+                self.prune_key(store, key, height.saturating_sub(nanos))
+            }
+            (Retention::LastBlocks(_), Clock::Timestamp) => Err(StdError::generic_err(
+                "Retention::LastBlocks needs a snapshot keyed by block height",
+            )),
+            (Retention::LastSeconds(_), Clock::Height) => Err(StdError::generic_err(
+                "Retention::LastSeconds needs a snapshot keyed by block time",
+            )),
+        }
+    }
+
+    /// Height below which the changelog was pruned and can't be queried anymore
+    pub fn pruned_before(&self, store: &dyn Storage) -> StdResult<u64> {
+        Ok(self.pruning().may_load(store)?.unwrap_or_default().before)
+    }
+
+    /// Raises the pruning watermark and removes the changes of the given key below it
+    fn prune_key(&self, store: &mut dyn Storage, key: K, before_height: u64) -> StdResult<()> {
+        let mut pruning = self.pruning().may_load(store)?.unwrap_or_default();
+        if before_height > pruning.before {
+            pruning.before = before_height;
+            self.pruning().save(store, &pruning)?;
+        }
+
+        let stale = self
+            .changelog
+            .prefix(key.clone())
+            .keys(
+                store,
+                None,
+                Some(Bound::exclusive(pruning.before)),
+                Order::Ascending,
+            )
+            .collect::<StdResult<Vec<_>>>()?;
+        for height in stale {
+            self.changelog.remove(store, (key.clone(), height));
+        }
+        Ok(())
+    }
+
+    /// Makes heights below `before_height` unqueryable and removes up to `limit` of their
+    /// changelog entries, returning how many were removed.
+    ///
+    /// Every call looks at no more than `limit` entries, so it is safe to call from regular
+    /// executes. The sweep resumes where the previous call stopped and starts over once it
+    /// reaches the end of the changelog.
+    pub fn prune(&self, store: &mut dyn Storage, before_height: u64, limit: u32) -> StdResult<u32> {
+        let mut pruning = self.pruning().may_load(store)?.unwrap_or_default();
+        pruning.before = pruning.before.max(before_height);
+
+        let start = pruning
+            .cursor
+            .take()
+            .map(|cursor| Bound::InclusiveRaw(cursor.0));
+        let mut keys: Vec<_> = self
+            .changelog
+            .keys_raw(store, start, None, Order::Ascending)
+            .take(limit as usize + 1)
+            .collect();
+        if keys.len() > limit as usize {
+            pruning.cursor = keys.pop().map(Binary::from);
+        }
+
+        let mut removed = 0;
+        for key in keys {
+            // the height is the last element of the (key, height) changelog key
+            let (_, height) = key.split_at(key.len() - 8);
+            if u64::from_be_bytes(height.try_into().unwrap()) < pruning.before {
+                store.remove(&namespaces_with_key(&[self.changelog.namespace()], &key));
+                removed += 1;
+            }
+        }
+        self.pruning().save(store, &pruning)?;
+        Ok(removed)
     }
 
     // may_load_at_height reads historical data from given checkpoints.
@@ -172,6 +302,44 @@ pub enum Strategy {
     Selected,
 }
 
+/// What the `u64` points in time passed to a snapshot are measured in.
+///
+/// Heights are meaningless across chains, and proposals or vesting schedules usually reason in
+/// time, so a snapshot can be keyed by block time instead. Its `*_at_time` methods take the
+/// block `Timestamp`, while the plain methods take nanoseconds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Clock {
+    /// `env.block.height`, the default
+    Height,
+    /// `env.block.time`, in nanoseconds
+    Timestamp,
+}
+
+/// How long the changelog of a snapshot is kept.
+///
+/// Heights that are no longer covered by the changelog can't be queried: `may_load_at_height`
+/// and `assert_checkpointed` return an error for them instead of a wrong value.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Retention {
+    /// Keeps every change, until removed by an explicit `prune`.
+    Forever,
+    /// Keeps the changes of the last given number of blocks, for `Clock::Height` snapshots.
+    /// Older changes of a key are removed whenever the key is written; `prune` collects the
+    /// keys that aren't written anymore.
+    LastBlocks(u64),
+    /// Keeps the changes of the last given number of seconds, for `Clock::Timestamp`
+    /// snapshots. Pruned like `LastBlocks`.
+    LastSeconds(u64),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct Pruning {
+    /// Heights below this one are no longer queryable
+    before: u64,
+    /// Changelog key the next `prune` call resumes from
+    cursor: Option<Binary>,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
 pub struct ChangeSet<T> {
     pub old: Option<T>,
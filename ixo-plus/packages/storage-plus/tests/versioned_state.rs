@@ -0,0 +1,87 @@
+#[cfg(all(test, feature = "iterator", feature = "macro"))]
+mod test {
+    use cosmwasm_std::{testing::MockStorage, Addr, StdResult};
+    use cw_storage_macro::versioned_state;
+    use cw_storage_plus::{Item, Map};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct ConfigV1 {
+        owner: Addr,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    struct Config {
+        owner: Addr,
+        paused: bool,
+    }
+
+    fn upgrade_config(old: ConfigV1) -> StdResult<Config> {
+        Ok(Config {
+            owner: old.owner,
+            paused: false,
+        })
+    }
+
+    fn upgrade_balance(old: u64) -> StdResult<u128> {
+        Ok(old.into())
+    }
+
+    #[versioned_state(from = "config", old = ConfigV1, migrate = upgrade_config)]
+    const CONFIG: Item<Config> = Item::new("config_v2");
+
+    #[versioned_state(from = "balances", old = u64, migrate = upgrade_balance)]
+    pub const BALANCES: Map<'static, &Addr, u128> = Map::new("balances_v2");
+
+    #[test]
+    fn versioned_item_migrates() {
+        const CONFIG_V1: Item<ConfigV1> = Item::new("config");
+        let mut storage = MockStorage::new();
+        let owner = Addr::unchecked("owner");
+        CONFIG_V1
+            .save(
+                &mut storage,
+                &ConfigV1 {
+                    owner: owner.clone(),
+                },
+            )
+            .unwrap();
+
+        let expected = Config {
+            owner,
+            paused: false,
+        };
+        assert_eq!(
+            CONFIG_MIGRATION.migrate(&mut storage).unwrap(),
+            Some(expected.clone())
+        );
+        assert_eq!(CONFIG.load(&storage).unwrap(), expected);
+    }
+
+    #[test]
+    fn versioned_map_migrates() {
+        const BALANCES_V1: Map<&Addr, u64> = Map::new("balances");
+        let mut storage = MockStorage::new();
+        let addrs: Vec<_> = (0..3)
+            .map(|n| Addr::unchecked(format!("addr{}", n)))
+            .collect();
+        for (n, addr) in addrs.iter().enumerate() {
+            BALANCES_V1.save(&mut storage, addr, &(n as u64)).unwrap();
+        }
+
+        assert!(
+            !BALANCES_MIGRATION
+                .migrate_chunk(&mut storage, 2)
+                .unwrap()
+                .done
+        );
+        assert_eq!(BALANCES_MIGRATION.load(&storage, &addrs[2]).unwrap(), 2);
+        assert!(
+            BALANCES_MIGRATION
+                .migrate_chunk(&mut storage, 2)
+                .unwrap()
+                .done
+        );
+        assert_eq!(BALANCES.load(&storage, &addrs[2]).unwrap(), 2u128);
+    }
+}
@@ -4,12 +4,12 @@ use std::str::FromStr;
 
 use cosmwasm_std::{
     attr, entry_point, to_json_binary, Addr, Binary, BlockInfo, Coin, CosmosMsg, Decimal, Deps,
-    DepsMut, Env, MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult, Storage,
-    SubMsg, Uint128, Uint512, WasmMsg,
+    DepsMut, Empty, Env, MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult,
+    Storage, SubMsg, Uint128, Uint512, WasmMsg,
 };
 use cw1155::{Cw1155ExecuteMsg, TokenId};
 use cw2::set_contract_version;
-use cw20_lp::{Cw20ExecuteMsg, Expiration, MinterResponse};
+use cw20::{Cw20ExecuteMsg, Expiration, MinterResponse};
 use cw_utils::{must_pay, parse_reply_instantiate_data};
 use prost::Message;
 
@@ -93,7 +93,7 @@ pub fn instantiate(
         funds: vec![],
         admin: None,
         label: "lp_token".to_string(),
-        msg: to_json_binary(&cw20_base_lp::msg::InstantiateMsg {
+        msg: to_json_binary(&cw20_base::msg::InstantiateMsg {
             name: "IxoSwap_Liquidity_Token".into(),
             symbol: "islpt".into(),
             decimals: 6,
@@ -103,6 +103,13 @@ pub fn instantiate(
                 cap: None,
             }),
             marketing: None,
+            snapshots: None,
+            // liquidity is only burned by the pool, when withdrawn
+            capabilities: Some(cw20_base::msg::Capabilities {
+                holder_burn: false,
+                minter_only_burn_from: true,
+            }),
+            admin: None,
         })?,
     };
 
@@ -607,7 +614,7 @@ fn get_cw1155_transfer_msg(
     // Convert HashMap to BTreeMap to maintain deterministic order by key
     let sorted_tokens: BTreeMap<_, _> = tokens.iter().collect();
 
-    let transfer_cw1155_msg = Cw1155ExecuteMsg::BatchSendFrom {
+    let transfer_cw1155_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchSendFrom {
         from: owner.into(),
         to: recipient.into(),
         batch: sorted_tokens
@@ -628,9 +635,9 @@ fn get_cw1155_transfer_msg(
 
 /// Queries the total supply of lp token, which is cw20 contract on chain
 fn get_lp_token_supply(deps: Deps, lp_token_addr: &Addr) -> StdResult<Uint128> {
-    let resp: cw20_lp::TokenInfoResponse = deps
+    let resp: cw20::TokenInfoResponse = deps
         .querier
-        .query_wasm_smart(lp_token_addr, &cw20_base_lp::msg::QueryMsg::TokenInfo {})?;
+        .query_wasm_smart(lp_token_addr, &cw20_base::msg::QueryMsg::TokenInfo {})?;
     Ok(resp.total_supply)
 }
 
@@ -640,9 +647,10 @@ fn mint_lp_tokens(
     liquidity_amount: Uint128,
     lp_token_address: &Addr,
 ) -> StdResult<CosmosMsg> {
-    let mint_msg = cw20_base_lp::msg::ExecuteMsg::Mint {
+    let mint_msg = cw20_base::msg::ExecuteMsg::Mint {
         recipient: recipient.into(),
         amount: liquidity_amount,
+        vesting: None,
     };
     Ok(WasmMsg::Execute {
         contract_addr: lp_token_address.to_string(),
@@ -654,9 +662,9 @@ fn mint_lp_tokens(
 
 /// Queries the balance of the given cw20 contract on chain
 fn get_token_balance(deps: Deps, contract: &Addr, addr: &Addr) -> StdResult<Uint128> {
-    let resp: cw20_lp::BalanceResponse = deps.querier.query_wasm_smart(
+    let resp: cw20::BalanceResponse = deps.querier.query_wasm_smart(
         contract,
-        &cw20_base_lp::msg::QueryMsg::Balance {
+        &cw20_base::msg::QueryMsg::Balance {
             address: addr.to_string(),
         },
     )?;
@@ -1231,7 +1239,7 @@ fn update_token_supplies(
 
 /// Creates a burn message for the given amount of lp token from the given owner
 fn get_burn_msg(contract: &Addr, owner: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
-    let msg = cw20_base_lp::msg::ExecuteMsg::BurnFrom {
+    let msg = cw20_base::msg::ExecuteMsg::BurnFrom {
         owner: owner.to_string(),
         amount,
     };
@@ -7,7 +7,7 @@ pub enum ContractError {
     Std(#[from] StdError),
 
     #[error("{0}")]
-    Cw20Error(#[from] cw20_base_lp::ContractError),
+    Cw20Error(#[from] cw20_base::ContractError),
 
     #[error("{0}")]
     CheckedMultiplyFraction(#[from] CheckedMultiplyFractionError),
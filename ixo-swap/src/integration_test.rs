@@ -5,76 +5,58 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    attr, coin, coins, to_json_binary, Addr, Api, Binary, BlockInfo, Coin, Decimal, Empty, Event,
-    Querier, StdError, Storage, Uint128, WasmMsg,
+    attr, coin, coins, to_json_binary, Addr, Coin, Decimal, Empty, Event, QueryRequest, StdError,
+    Uint128, WasmMsg,
 };
 use cw1155::{BatchBalanceResponse, Cw1155ExecuteMsg, Cw1155QueryMsg, TokenId};
-use cw20_lp::{Cw20Coin, Cw20Contract, Cw20ExecuteMsg, Expiration};
+use cw20::{BalanceResponse, Cw20Contract, Cw20ExecuteMsg, Cw20QueryMsg, Expiration};
+use cw20_base::msg::InitialBalance;
 use cw_multi_test::{
-    App, Contract, ContractWrapper, Executor, StargateKeeper, StargateMsg, StargateQueryHandler,
+    App, BankQueryHandler, Contract, ContractWrapper, DenomUnit as MockDenomUnit, Executor,
+    Metadata as MockDenomMetadata, QueryTokenMetadataResponse as MockTokenMetadata,
+    TokenMetadataHandler, QUERY_DENOM_METADATA_PATH, QUERY_TOKEN_METADATA_PATH,
 };
 use cw_utils::{parse_instantiate_response_data, PaymentError};
 use prost::Message;
 
 use crate::msg::{
-    ExecuteMsg, FeeResponse, FreezeStatusResponse, InfoResponse, InstantiateMsg, Metadata,
-    OwnershipResponse, QueryDenomMetadataRequest, QueryDenomMetadataResponse, QueryMsg,
-    QueryTokenMetadataRequest, QueryTokenMetadataResponse, SlippageResponse, TokenSelect,
+    ExecuteMsg, FeeResponse, FreezeStatusResponse, InfoResponse, InstantiateMsg, OwnershipResponse,
+    QueryDenomMetadataRequest, QueryDenomMetadataResponse, QueryMsg, SlippageResponse, TokenSelect,
     TokenSuppliesResponse,
 };
 use crate::token_amount::TokenAmount;
 use crate::utils::{MIN_FEE_PERCENT, PREDEFINED_MAX_FEES_PERCENT};
 use crate::{error::ContractError, msg::Denom};
 
-#[derive(Clone)]
-struct TokenMetadataQueryHandler;
-impl StargateQueryHandler for TokenMetadataQueryHandler {
-    fn stargate_query(
-        &self,
-        _api: &dyn Api,
-        _storage: &dyn Storage,
-        _querier: &dyn Querier,
-        _block: &BlockInfo,
-        msg: StargateMsg,
-    ) -> anyhow::Result<Binary> {
-        let request = QueryTokenMetadataRequest::decode(msg.value.as_slice())?;
-        let metadata = QueryTokenMetadataResponse {
-            name: request.id.split("/").collect::<Vec<_>>()[0].to_string(),
+/// Resolves the ixo token metadata of test credits, named after the part of the id before `/`
+fn token_metadata_handler() -> TokenMetadataHandler {
+    TokenMetadataHandler::new(|id| {
+        Some(MockTokenMetadata {
+            name: id.split('/').next().unwrap_or_default().to_string(),
             decimals: "0".to_string(),
             description: "Test credits".to_string(),
             image: "https://ipfs.io/ipfs/test".to_string(),
             index: "1".to_string(),
-        };
-
-        Ok(to_json_binary(&metadata)?)
-    }
-
-    fn register_queries(&'static self, _keeper: &mut StargateKeeper<Empty, Empty>) {}
+        })
+    })
 }
 
-#[derive(Clone)]
-struct DenomMetadataQueryHandler;
-impl StargateQueryHandler for DenomMetadataQueryHandler {
-    fn stargate_query(
-        &self,
-        _api: &dyn Api,
-        _storage: &dyn Storage,
-        _querier: &dyn Querier,
-        _block: &BlockInfo,
-        msg: StargateMsg,
-    ) -> anyhow::Result<Binary> {
-        let request = QueryDenomMetadataRequest::decode(msg.value.as_slice())?;
-        let metadata = match request.denom.as_str() {
-            "Unsupported" => None,
-            _ => Some(Metadata {
-                ..Default::default()
-            }),
-        };
-
-        Ok(to_json_binary(&QueryDenomMetadataResponse { metadata })?)
+/// The bank denom metadata of a native test denom
+fn denom_metadata(denom: &str) -> MockDenomMetadata {
+    MockDenomMetadata {
+        description: "Test denom".to_string(),
+        denom_units: vec![MockDenomUnit {
+            denom: denom.to_string(),
+            exponent: 0,
+            aliases: vec![],
+        }],
+        base: denom.to_string(),
+        display: denom.to_string(),
+        name: denom.to_string(),
+        symbol: denom.to_uppercase(),
+        uri: String::new(),
+        uri_hash: String::new(),
     }
-
-    fn register_queries(&'static self, _keeper: &mut StargateKeeper<Empty, Empty>) {}
 }
 
 fn mock_app() -> App {
@@ -93,9 +75,9 @@ pub fn contract_amm() -> Box<dyn Contract<Empty>> {
 
 pub fn contract_cw20() -> Box<dyn Contract<Empty>> {
     let contract = ContractWrapper::new(
-        cw20_base_lp::contract::execute,
-        cw20_base_lp::contract::instantiate,
-        cw20_base_lp::contract::query,
+        cw20_base::contract::execute,
+        cw20_base::contract::instantiate,
+        cw20_base::contract::query,
     );
     Box::new(contract)
 }
@@ -223,16 +205,20 @@ fn create_cw20(
 ) -> Cw20Contract {
     // set up cw20 contract with some tokens
     let cw20_id = router.store_code(contract_cw20());
-    let msg = cw20_base_lp::msg::InstantiateMsg {
+    let msg = cw20_base::msg::InstantiateMsg {
         name,
         symbol,
         decimals: 6,
-        initial_balances: vec![Cw20Coin {
+        initial_balances: vec![InitialBalance {
             address: owner.to_string(),
             amount: balance,
+            vesting: None,
         }],
         mint: None,
         marketing: None,
+        snapshots: None,
+        capabilities: None,
+        admin: None,
     };
     let addr = router
         .instantiate_contract(cw20_id, owner.clone(), &msg, &[], "CASH", None)
@@ -240,11 +226,27 @@ fn create_cw20(
     Cw20Contract(addr)
 }
 
+fn cw20_balance(router: &App, token: &Cw20Contract, address: &Addr) -> Uint128 {
+    let resp: BalanceResponse = router
+        .wrap()
+        .query_wasm_smart(
+            token.addr(),
+            &Cw20QueryMsg::Balance {
+                address: address.to_string(),
+            },
+        )
+        .unwrap();
+    resp.balance
+}
+
 // CreateCW155 create new cw155
 fn create_cw1155(router: &mut App, owner: &Addr) -> Addr {
     let cw1155_id = router.store_code(contract_cw1155());
     let msg = cw1155_base::msg::InstantiateMsg {
+        name: "Tokens".to_string(),
+        symbol: "TKN".to_string(),
         minter: owner.into(),
+        admin: None,
     };
 
     router
@@ -287,12 +289,31 @@ fn instantiate() {
     let funds = coins(2000, NATIVE_TOKEN_DENOM);
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
+    // the bank module answers the denom metadata query of the contract
+    let query_metadata = |denom: &str| -> QueryDenomMetadataResponse {
+        router
+            .wrap()
+            .query(&QueryRequest::Stargate {
+                path: QUERY_DENOM_METADATA_PATH.to_string(),
+                data: QueryDenomMetadataRequest {
+                    denom: denom.to_string(),
+                }
+                .encode_to_vec()
+                .into(),
+            })
+            .unwrap()
+    };
+    assert_eq!(
+        query_metadata(NATIVE_TOKEN_DENOM).metadata.unwrap().symbol,
+        "JUNO"
+    );
+    assert_eq!(query_metadata("Unsupported").metadata, None);
+
     let cw1155_token = create_cw1155(&mut router, &owner);
 
     let max_slippage_percent = Decimal::from_str("0.3").unwrap();
@@ -501,13 +522,12 @@ fn cw1155_to_cw1155_swap() {
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
         router.stargate.register_query(
-            "/ixo.token.v1beta1.Query/TokenMetadata",
-            Box::new(TokenMetadataQueryHandler),
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(token_metadata_handler()),
         );
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_first = create_cw1155(&mut router, &owner);
@@ -543,7 +563,7 @@ fn cw1155_to_cw1155_swap() {
     );
 
     // set up initial balances
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (
@@ -562,7 +582,7 @@ fn cw1155_to_cw1155_swap() {
     let _res = router
         .execute_contract(owner.clone(), cw1155_first.clone(), &mint_msg, &[])
         .unwrap();
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (
@@ -597,7 +617,7 @@ fn cw1155_to_cw1155_swap() {
     );
 
     // send tokens to contract address
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm1.clone().into(),
         expires: None,
     };
@@ -605,7 +625,7 @@ fn cw1155_to_cw1155_swap() {
         .execute_contract(owner.clone(), cw1155_first.clone(), &allowance_msg, &[])
         .unwrap();
 
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm2.clone().into(),
         expires: None,
     };
@@ -755,13 +775,12 @@ fn cw1155_to_cw20_swap() {
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
         router.stargate.register_query(
-            "/ixo.token.v1beta1.Query/TokenMetadata",
-            Box::new(TokenMetadataQueryHandler),
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(token_metadata_handler()),
         );
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -792,7 +811,7 @@ fn cw1155_to_cw20_swap() {
     );
 
     // set up initial balances
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (token_ids[0].clone(), Uint128::new(100_000), "".to_string()),
@@ -810,11 +829,11 @@ fn cw1155_to_cw20_swap() {
         owner_balance.balances,
         [Uint128::new(100_000), Uint128::new(100_000),]
     );
-    let owner_balance = cw20_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let owner_balance = cw20_balance(&router, &cw20_token, &owner);
     assert_eq!(owner_balance, Uint128::new(150_000));
 
     // send tokens to contract address
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm.clone().into(),
         expires: None,
     };
@@ -848,7 +867,7 @@ fn cw1155_to_cw20_swap() {
     let owner_balance =
         batch_balance_for_owner(&router, &cw1155_token, &owner, &token_ids).balances;
     assert_eq!(owner_balance, [Uint128::new(50_000), Uint128::new(50_000)]);
-    let owner_balance = cw20_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let owner_balance = cw20_balance(&router, &cw20_token, &owner);
     assert_eq!(owner_balance, Uint128::new(50_000));
 
     // try swap with unsupported 1155 token
@@ -916,7 +935,7 @@ fn cw1155_to_cw20_swap() {
     let owner_balance =
         batch_balance_for_owner(&router, &cw1155_token, &owner, &token_ids).balances;
     assert_eq!(owner_balance, [Uint128::new(25_000), Uint128::new(25_000)]);
-    let owner_balance = cw20_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let owner_balance = cw20_balance(&router, &cw20_token, &owner);
     assert_eq!(owner_balance, Uint128::new(83_266));
     let fee_recipient_balance =
         batch_balance_for_owner(&router, &cw1155_token, &protocol_fee_recipient, &token_ids)
@@ -950,11 +969,9 @@ fn cw1155_to_cw20_swap() {
     let owner_balance =
         batch_balance_for_owner(&router, &cw1155_token, &owner, &token_ids).balances;
     assert_eq!(owner_balance, [Uint128::new(62_878), Uint128::new(58_000)]);
-    let owner_balance = cw20_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let owner_balance = cw20_balance(&router, &cw20_token, &owner);
     assert_eq!(owner_balance, Uint128::new(23_266));
-    let fee_recipient_balance = cw20_token
-        .balance(&router.wrap(), protocol_fee_recipient.clone())
-        .unwrap();
+    let fee_recipient_balance = cw20_balance(&router, &cw20_token, &protocol_fee_recipient);
     assert_eq!(fee_recipient_balance, Uint128::new(60));
 }
 
@@ -971,13 +988,12 @@ fn cw1155_to_native_swap() {
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
         router.stargate.register_query(
-            "/ixo.token.v1beta1.Query/TokenMetadata",
-            Box::new(TokenMetadataQueryHandler),
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(token_metadata_handler()),
         );
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -1000,7 +1016,7 @@ fn cw1155_to_native_swap() {
     );
 
     // set up initial balances
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (token_ids[0].clone(), Uint128::new(100_000), "".to_string()),
@@ -1020,7 +1036,7 @@ fn cw1155_to_native_swap() {
     );
 
     // send tokens to contract address
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm.clone().into(),
         expires: None,
     };
@@ -1130,13 +1146,12 @@ fn cw1155_to_native_swap_low_fees() {
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
         router.stargate.register_query(
-            "/ixo.token.v1beta1.Query/TokenMetadata",
-            Box::new(TokenMetadataQueryHandler),
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(token_metadata_handler()),
         );
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -1159,7 +1174,7 @@ fn cw1155_to_native_swap_low_fees() {
     );
 
     // set up initial balances
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (token_ids[0].clone(), Uint128::new(100_000), "".to_string()),
@@ -1179,7 +1194,7 @@ fn cw1155_to_native_swap_low_fees() {
     );
 
     // send tokens to contract address
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm.clone().into(),
         expires: None,
     };
@@ -1324,13 +1339,12 @@ fn amm_add_and_remove_liquidity() {
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
         router.stargate.register_query(
-            "/ixo.token.v1beta1.Query/TokenMetadata",
-            Box::new(TokenMetadataQueryHandler),
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(token_metadata_handler()),
         );
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -1361,7 +1375,7 @@ fn amm_add_and_remove_liquidity() {
     // set up cw20 helpers
     let lp_token = Cw20Contract(Addr::unchecked(info.lp_token_address));
 
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (token_ids[0].clone(), Uint128::new(5000), "".to_string()),
@@ -1381,7 +1395,7 @@ fn amm_add_and_remove_liquidity() {
     );
 
     // send tokens to contract address
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm_addr.clone().into(),
         expires: None,
     };
@@ -1538,7 +1552,7 @@ fn amm_add_and_remove_liquidity() {
     let amm_balances =
         batch_balance_for_owner(&router, &cw1155_token, &amm_addr, &token_ids).balances;
     assert_eq!(amm_balances, [Uint128::new(70), Uint128::new(30)]);
-    let crust_balance = lp_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let crust_balance = cw20_balance(&router, &lp_token, &owner);
     assert_eq!(crust_balance, Uint128::new(100));
 
     // send tokens to contract address
@@ -1569,7 +1583,7 @@ fn amm_add_and_remove_liquidity() {
     let amm_balances =
         batch_balance_for_owner(&router, &cw1155_token, &amm_addr, &token_ids).balances;
     assert_eq!(amm_balances, [Uint128::new(120), Uint128::new(30)]);
-    let crust_balance = lp_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let crust_balance = cw20_balance(&router, &lp_token, &owner);
     assert_eq!(crust_balance, Uint128::new(150));
 
     // too low max token error
@@ -1746,7 +1760,7 @@ fn amm_add_and_remove_liquidity() {
     let amm_balances =
         batch_balance_for_owner(&router, &cw1155_token, &amm_addr, &token_ids).balances;
     assert_eq!(amm_balances, [Uint128::new(75), Uint128::new(25)]);
-    let crust_balance = lp_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let crust_balance = cw20_balance(&router, &lp_token, &owner);
     assert_eq!(crust_balance, Uint128::new(100));
 
     // Remove rest of liquidity
@@ -1791,13 +1805,12 @@ fn remove_liquidity_with_partially_and_any_filling() {
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
         router.stargate.register_query(
-            "/ixo.token.v1beta1.Query/TokenMetadata",
-            Box::new(TokenMetadataQueryHandler),
+            QUERY_TOKEN_METADATA_PATH,
+            Box::new(token_metadata_handler()),
         );
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -1830,7 +1843,7 @@ fn remove_liquidity_with_partially_and_any_filling() {
     // set up cw20 helpers
     let lp_token = Cw20Contract(Addr::unchecked(info.lp_token_address));
 
-    let mint_msg = Cw1155ExecuteMsg::BatchMint {
+    let mint_msg = Cw1155ExecuteMsg::<Option<Empty>>::BatchMint {
         to: owner.clone().into(),
         batch: vec![
             (token_ids[0].clone(), Uint128::new(5000), "".to_string()),
@@ -1857,7 +1870,7 @@ fn remove_liquidity_with_partially_and_any_filling() {
     );
 
     // send tokens to contract address
-    let allowance_msg = Cw1155ExecuteMsg::ApproveAll {
+    let allowance_msg = Cw1155ExecuteMsg::<Option<Empty>>::ApproveAll {
         operator: amm_addr.clone().into(),
         expires: None,
     };
@@ -1921,7 +1934,7 @@ fn remove_liquidity_with_partially_and_any_filling() {
             Uint128::new(10)
         ]
     );
-    let crust_balance = lp_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let crust_balance = cw20_balance(&router, &lp_token, &owner);
     assert_eq!(crust_balance, Uint128::new(135));
 
     // remove liquidity for specific cw1155 tokens
@@ -1981,7 +1994,7 @@ fn remove_liquidity_with_partially_and_any_filling() {
             Uint128::new(10)
         ]
     );
-    let crust_balance = lp_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let crust_balance = cw20_balance(&router, &lp_token, &owner);
     assert_eq!(crust_balance, Uint128::new(55));
 
     // remove liquidity for any cw1155 tokens
@@ -2037,7 +2050,7 @@ fn remove_liquidity_with_partially_and_any_filling() {
             Uint128::new(0)
         ]
     );
-    let crust_balance = lp_token.balance(&router.wrap(), owner.clone()).unwrap();
+    let crust_balance = cw20_balance(&router, &lp_token, &owner);
     assert_eq!(crust_balance, Uint128::new(0));
 }
 
@@ -2049,10 +2062,9 @@ fn freeze_pool() {
     let funds = coins(100, NATIVE_TOKEN_DENOM);
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     const NATIVE_TOKEN_DENOM: &str = "juno";
@@ -2135,10 +2147,9 @@ fn transfer_ownership() {
     let funds = coins(2000, NATIVE_TOKEN_DENOM);
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -2259,10 +2270,9 @@ fn update_slippage() {
     let funds = coins(2000, NATIVE_TOKEN_DENOM);
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);
@@ -2315,10 +2325,9 @@ fn update_fee() {
     let funds = coins(2000, NATIVE_TOKEN_DENOM);
     router.borrow_mut().init_modules(|router, _, storage| {
         router.bank.init_balance(storage, &owner, funds).unwrap();
-        router.stargate.register_query(
-            "/cosmos.bank.v1beta1.Query/DenomMetadata",
-            Box::new(DenomMetadataQueryHandler),
-        )
+        BankQueryHandler
+            .set_denom_metadata(storage, denom_metadata(NATIVE_TOKEN_DENOM))
+            .unwrap();
     });
 
     let cw1155_token = create_cw1155(&mut router, &owner);